@@ -319,6 +319,8 @@ fn buy_opp(
         amount,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
 
@@ -344,6 +346,8 @@ fn buy_opp_fail(
         amount: Uint128::new(2_000_000_000), //20
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     };
 
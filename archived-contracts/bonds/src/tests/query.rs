@@ -165,7 +165,8 @@ fn get_permit() -> QueryPermit {
     QueryPermit {
         params: PermitData {
             key: "key".to_string(),
-            data: Binary::from_base64("c29tZSBzdHJpbmc=").unwrap()
+            data: Binary::from_base64("c29tZSBzdHJpbmc=").unwrap(),
+            epoch: None,
         },
         signature: PermitSignature {
             pub_key: PubKey::new(
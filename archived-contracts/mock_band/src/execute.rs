@@ -77,7 +77,7 @@ pub fn receive(
 
 pub fn try_update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     config: Config,
 ) -> StdResult<Response> {
@@ -85,6 +85,7 @@ pub fn try_update_config(
 
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::ScrtStakingAdmin,
         &info.sender,
         &cur_config.admin_auth,
@@ -151,7 +152,7 @@ pub fn update(deps: DepsMut, env: Env, _info: MessageInfo, asset: Addr) -> StdRe
 
 pub fn unbond(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     asset: Addr,
     amount: Uint128,
@@ -166,6 +167,7 @@ pub fn unbond(
 
     if validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::ScrtStakingAdmin,
         &info.sender,
         &config.admin_auth,
@@ -3,10 +3,13 @@ use shade_protocol::{
         errors::unauthorized_super,
         AdminAuthStatus,
         AdminsResponse,
+        ApproverSet,
         ConfigResponse,
         ExecuteMsg,
         InstantiateMsg,
-        PermissionsResponse,
+        PendingActionsResponse,
+        PendingSuperResponse,
+        ProtectedAction,
         QueryMsg,
     },
     c_std::{
@@ -27,14 +30,23 @@ use shade_protocol::{
 
 use crate::{
     execute::{
-        try_self_destruct,
+        try_accept_super,
+        try_approve_action,
+        try_cancel_action,
+        try_cancel_super_transfer,
+        try_protected_action,
         try_toggle_status,
-        try_transfer_super,
-        try_update_registry,
-        try_update_registry_bulk,
     },
-    query::query_validate_permission,
-    shared::{ADMINS, PERMISSIONS, STATUS, SUPER},
+    query::{
+        query_audit_log,
+        query_expiring_permissions,
+        query_permission_holders,
+        query_permissions,
+        query_role_members,
+        query_roles,
+        query_validate_permission,
+    },
+    shared::{ADMINS, APPROVERS, PENDING_ACTIONS, PENDING_SUPER, STATUS, SUPER},
 };
 
 pub const RESPONSE_BLOCK_SIZE: usize = 256;
@@ -62,22 +74,67 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), shd_entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> StdResult<Response> {
-    // Only the super user can execute anything on this contract.
-    is_super(deps.storage, &info.sender)?;
-    // Super user is assumed to have been verified by this point.
+    // Only the super user can execute anything else on this contract.
+    // The pending super user and approvers are verified by their own handlers.
+    if !matches!(
+        msg,
+        ExecuteMsg::AcceptSuper {} | ExecuteMsg::ApproveAction { .. }
+    ) {
+        is_super(deps.storage, &info.sender)?;
+    }
+    let sender = &info.sender;
+    let time = env.block.time.seconds();
     pad_handle_result(
         match msg {
-            ExecuteMsg::UpdateRegistry { action } => {
-                try_update_registry(deps.storage, deps.api, action)
+            ExecuteMsg::UpdateRegistry { action } => try_protected_action(
+                deps,
+                ProtectedAction::UpdateRegistry { action },
+                sender,
+                time,
+            ),
+            ExecuteMsg::UpdateRegistryBulk { actions } => try_protected_action(
+                deps,
+                ProtectedAction::UpdateRegistryBulk { actions },
+                sender,
+                time,
+            ),
+            ExecuteMsg::TransferSuper { new_super } => try_protected_action(
+                deps,
+                ProtectedAction::TransferSuper { new_super },
+                sender,
+                time,
+            ),
+            ExecuteMsg::AcceptSuper {} => try_accept_super(deps, sender.clone(), time),
+            ExecuteMsg::CancelSuperTransfer {} => try_cancel_super_transfer(deps, sender, time),
+            ExecuteMsg::SelfDestruct {} => {
+                try_protected_action(deps, ProtectedAction::SelfDestruct {}, sender, time)
+            }
+            ExecuteMsg::ToggleStatus { new_status } => match new_status {
+                AdminAuthStatus::Shutdown => {
+                    try_protected_action(deps, ProtectedAction::Shutdown {}, sender, time)
+                }
+                _ => try_toggle_status(deps, new_status, sender, &[], time),
+            },
+            ExecuteMsg::SetApprovers {
+                approvers,
+                threshold,
+            } => try_protected_action(
+                deps,
+                ProtectedAction::SetApprovers {
+                    approvers,
+                    threshold,
+                },
+                sender,
+                time,
+            ),
+            ExecuteMsg::ApproveAction { id } => {
+                try_approve_action(deps, sender.clone(), id, time)
             }
-            ExecuteMsg::UpdateRegistryBulk { actions } => try_update_registry_bulk(deps, actions),
-            ExecuteMsg::TransferSuper { new_super } => try_transfer_super(deps, new_super),
-            ExecuteMsg::SelfDestruct {} => try_self_destruct(deps),
-            ExecuteMsg::ToggleStatus { new_status } => try_toggle_status(deps, new_status),
+            ExecuteMsg::CancelAction { id } => try_cancel_action(deps, id, sender, time),
         },
         RESPONSE_BLOCK_SIZE,
     )
@@ -93,15 +150,23 @@ fn is_super(storage: &dyn Storage, address: &Addr) -> StdResult<()> {
 }
 
 #[cfg_attr(not(feature = "library"), shd_entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
     Ok(match msg {
         QueryMsg::GetConfig {} => to_binary(&ConfigResponse {
             super_admin: SUPER.load(deps.storage)?,
             status: STATUS.load(deps.storage)?,
         }),
-        QueryMsg::ValidateAdminPermission { permission, user } => {
-            to_binary(&query_validate_permission(deps, permission, user)?)
-        }
+        QueryMsg::ValidateAdminPermission {
+            permission,
+            user,
+            contract,
+        } => to_binary(&query_validate_permission(
+            deps,
+            env.block.time.seconds(),
+            permission,
+            user,
+            contract,
+        )?),
         QueryMsg::GetAdmins {} => {
             STATUS
                 .load(deps.storage)?
@@ -111,15 +176,33 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
                 admins: ADMINS.load(deps.storage)?,
             })
         }
-        QueryMsg::GetPermissions { user } => {
-            STATUS
-                .load(deps.storage)?
-                .not_shutdown()?
-                .not_under_maintenance()?;
-            let validated_user = deps.api.addr_validate(user.as_str())?;
-            to_binary(&PermissionsResponse {
-                permissions: PERMISSIONS.load(deps.storage, &validated_user)?,
-            })
+        QueryMsg::GetPermissions { user, contract } => {
+            to_binary(&query_permissions(deps, user, contract)?)
+        }
+        QueryMsg::GetExpiringPermissions { within } => to_binary(
+            &query_expiring_permissions(deps, env.block.time.seconds(), within)?,
+        ),
+        QueryMsg::GetPendingSuper {} => to_binary(&PendingSuperResponse {
+            pending_super: PENDING_SUPER.may_load(deps.storage)?,
+        }),
+        QueryMsg::GetApprovers {} => to_binary(&APPROVERS.may_load(deps.storage)?.unwrap_or(
+            ApproverSet {
+                approvers: vec![],
+                threshold: 0,
+            },
+        )),
+        QueryMsg::GetPendingActions {} => to_binary(&PendingActionsResponse {
+            actions: PENDING_ACTIONS.may_load(deps.storage)?.unwrap_or_default(),
+        }),
+        QueryMsg::GetRoles {} => to_binary(&query_roles(deps)?),
+        QueryMsg::GetRoleMembers { role } => to_binary(&query_role_members(deps, role)?),
+        QueryMsg::GetPermissionHolders {
+            permission,
+            start,
+            limit,
+        } => to_binary(&query_permission_holders(deps, permission, start, limit)?),
+        QueryMsg::GetAuditLog { start, limit } => {
+            to_binary(&query_audit_log(deps, start, limit)?)
         }
     }?)
 }
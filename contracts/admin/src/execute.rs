@@ -1,17 +1,37 @@
-use shade_protocol::admin::errors::{no_permission, unregistered_admin};
+use shade_protocol::admin::errors::{
+    already_approved, invalid_approval_threshold, invalid_permission_window, no_pending_super,
+    no_permission, not_pending_super, pending_action_not_found, role_already_exists,
+    role_not_found, unauthorized_approver, unregistered_admin,
+};
 use shade_protocol::c_std::{DepsMut, Response, Storage, Api, Addr, StdResult};
-use shade_protocol::admin::{RegistryAction, AdminAuthStatus};
-use crate::shared::{STATUS, ADMINS, PERMISSIONS, SUPER, validate_permissions};
+use shade_protocol::admin::{
+    AdminAuthStatus, ApproverSet, AuditAction, PendingAction, PermissionWindow, ProtectedAction,
+    RegistryAction,
+};
+use crate::shared::{
+    add_permission_holder, is_valid_role, log_action, log_approved_action,
+    prune_expiring_permissions, remove_permission_holder, save_permission_window,
+    validate_permissions, ACTION_COUNT, ADMINS, APPROVERS, PENDING_ACTIONS, PENDING_SUPER,
+    PERMISSIONS, ROLES, ROLE_MEMBERS, ROLE_PERMISSIONS, SCOPED_CONTRACTS, SCOPED_PERMISSIONS,
+    STATUS, SUPER, USER_ROLES,
+};
 
 /// Performs one registry update. Cannot be run during a shutdown.
 pub fn try_update_registry(
     store: &mut dyn Storage,
     api: &dyn Api,
     action: RegistryAction,
+    actor: &Addr,
+    approvers: &[Addr],
+    time: u64,
 ) -> StdResult<Response> {
     STATUS.load(store)?.not_shutdown()?;
     let mut admins = ADMINS.load(store)?;
+    log_approved_action(store, actor, approvers, time, AuditAction::Registry {
+        action: action.clone(),
+    })?;
     resolve_registry_action(store, &mut admins, api, action)?;
+    prune_expiring_permissions(store, time)?;
     ADMINS.save(store, &admins)?;
     Ok(Response::default())
 }
@@ -20,38 +40,86 @@ pub fn try_update_registry(
 pub fn try_update_registry_bulk(
     deps: DepsMut,
     actions: Vec<RegistryAction>,
+    actor: &Addr,
+    approvers: &[Addr],
+    time: u64,
 ) -> StdResult<Response> {
     STATUS.load(deps.storage)?.not_shutdown()?;
     let mut admins = ADMINS.load(deps.storage)?;
     for action in actions {
+        log_approved_action(deps.storage, actor, approvers, time, AuditAction::Registry {
+            action: action.clone(),
+        })?;
         resolve_registry_action(deps.storage, &mut admins, deps.api, action)?;
     }
+    prune_expiring_permissions(deps.storage, time)?;
     ADMINS.save(deps.storage, &admins)?;
     Ok(Response::default())
 }
 
-pub fn try_transfer_super(deps: DepsMut, new_super: String) -> StdResult<Response> {
+/// Proposes a new super user, the transfer only happens once they accept it.
+pub fn try_transfer_super(
+    deps: DepsMut,
+    new_super: String,
+    actor: &Addr,
+    approvers: &[Addr],
+    time: u64,
+) -> StdResult<Response> {
     let valid_super = deps.api.addr_validate(new_super.as_str())?;
     // If you're trying to transfer the super permissions to someone who hasn't been registered as an admin,
     // it won't work. This is a safeguard.
-    let mut admins = ADMINS.load(deps.storage)?;
+    let admins = ADMINS.load(deps.storage)?;
     if !admins.contains(&valid_super) {
         return Err(unregistered_admin(valid_super.as_str()));
-    } else {
-        // Update the super and remove them from the admin list.
-        SUPER.save(deps.storage, &valid_super)?;
-        delete_admin(deps.storage, &mut admins, deps.api, new_super)?;
-        ADMINS.save(deps.storage, &admins)?;
     }
+    PENDING_SUPER.save(deps.storage, &valid_super)?;
+    log_approved_action(deps.storage, actor, approvers, time, AuditAction::TransferSuper {
+        new_super,
+    })?;
+    Ok(Response::default())
+}
+
+pub fn try_accept_super(deps: DepsMut, sender: Addr, time: u64) -> StdResult<Response> {
+    if PENDING_SUPER.may_load(deps.storage)?.as_ref() != Some(&sender) {
+        return Err(not_pending_super(sender.as_str()));
+    }
+    // They could have been removed as an admin since the transfer was proposed.
+    let mut admins = ADMINS.load(deps.storage)?;
+    if !admins.contains(&sender) {
+        return Err(unregistered_admin(sender.as_str()));
+    }
+    // Update the super and remove them from the admin list.
+    SUPER.save(deps.storage, &sender)?;
+    delete_admin(deps.storage, &mut admins, deps.api, sender.to_string())?;
+    ADMINS.save(deps.storage, &admins)?;
+    PENDING_SUPER.remove(deps.storage);
+    log_action(deps.storage, &sender, time, AuditAction::AcceptSuper {})?;
+    Ok(Response::default())
+}
+
+pub fn try_cancel_super_transfer(deps: DepsMut, actor: &Addr, time: u64) -> StdResult<Response> {
+    if PENDING_SUPER.may_load(deps.storage)?.is_none() {
+        return Err(no_pending_super());
+    }
+    PENDING_SUPER.remove(deps.storage);
+    log_action(deps.storage, actor, time, AuditAction::CancelSuperTransfer {})?;
     Ok(Response::default())
 }
 
-pub fn try_self_destruct(deps: DepsMut) -> StdResult<Response> {
+pub fn try_self_destruct(
+    deps: DepsMut,
+    actor: &Addr,
+    approvers: &[Addr],
+    time: u64,
+) -> StdResult<Response> {
+    log_approved_action(deps.storage, actor, approvers, time, AuditAction::SelfDestruct {})?;
     // Clear permissions
     let admins = ADMINS.load(deps.storage)?;
-    admins
-        .iter()
-        .for_each(|admin| PERMISSIONS.remove(deps.storage, admin));
+    for admin in admins.iter() {
+        clear_global_permissions(deps.storage, admin)?;
+        clear_scoped_permissions(deps.storage, admin)?;
+        clear_roles(deps.storage, admin)?;
+    }
     // Clear admins
     ADMINS.save(deps.storage, &vec![])?;
     // Disable contract
@@ -59,11 +127,178 @@ pub fn try_self_destruct(deps: DepsMut) -> StdResult<Response> {
     Ok(Response::default())
 }
 
-pub fn try_toggle_status(deps: DepsMut, new_status: AdminAuthStatus) -> StdResult<Response> {
+pub fn try_toggle_status(
+    deps: DepsMut,
+    new_status: AdminAuthStatus,
+    actor: &Addr,
+    approvers: &[Addr],
+    time: u64,
+) -> StdResult<Response> {
+    log_approved_action(deps.storage, actor, approvers, time, AuditAction::ToggleStatus {
+        new_status: new_status.clone(),
+    })?;
     STATUS.update(deps.storage, |_| -> StdResult<_> { Ok(new_status) })?;
     Ok(Response::default())
 }
 
+/// Runs the action right away unless approvers are set, in which case it waits for approvals.
+/// Queued actions are logged under the proposer along with the approvers.
+pub fn try_protected_action(
+    deps: DepsMut,
+    action: ProtectedAction,
+    actor: &Addr,
+    time: u64,
+) -> StdResult<Response> {
+    let requires_approval = APPROVERS
+        .may_load(deps.storage)?
+        .map_or(false, |approvers| approvers.is_required());
+    if !requires_approval {
+        return execute_protected_action(deps, action, actor, &[], time);
+    }
+
+    // Fail early instead of once approvals have been collected
+    match &action {
+        ProtectedAction::SetApprovers {
+            approvers,
+            threshold,
+        } => {
+            validate_approvers(deps.api, approvers, *threshold)?;
+        }
+        ProtectedAction::TransferSuper { new_super } => {
+            deps.api.addr_validate(new_super.as_str())?;
+        }
+        _ => {}
+    }
+
+    let id = ACTION_COUNT.may_load(deps.storage)?.unwrap_or_default();
+    ACTION_COUNT.save(deps.storage, &(id + 1))?;
+    let mut pending = PENDING_ACTIONS.may_load(deps.storage)?.unwrap_or_default();
+    pending.push(PendingAction {
+        id,
+        action,
+        proposer: actor.clone(),
+        approvals: vec![],
+    });
+    PENDING_ACTIONS.save(deps.storage, &pending)?;
+    Ok(Response::new().add_attribute("pending_action", id.to_string()))
+}
+
+/// Executes the pending action once enough approvers have signed off on it.
+pub fn try_approve_action(
+    deps: DepsMut,
+    sender: Addr,
+    id: u64,
+    time: u64,
+) -> StdResult<Response> {
+    let approvers = match APPROVERS.may_load(deps.storage)? {
+        Some(approvers) if approvers.approvers.contains(&sender) => approvers,
+        _ => return Err(unauthorized_approver(sender.as_str())),
+    };
+    let mut pending = PENDING_ACTIONS.may_load(deps.storage)?.unwrap_or_default();
+    let index = pending
+        .iter()
+        .position(|action| action.id == id)
+        .ok_or_else(|| pending_action_not_found(&id.to_string()))?;
+    if pending[index].approvals.contains(&sender) {
+        return Err(already_approved(sender.as_str(), &id.to_string()));
+    }
+    pending[index].approvals.push(sender.clone());
+
+    if pending[index].approvals.len() >= approvers.threshold as usize {
+        let action = pending.remove(index);
+        PENDING_ACTIONS.save(deps.storage, &pending)?;
+        return execute_protected_action(
+            deps,
+            action.action,
+            &action.proposer,
+            &action.approvals,
+            time,
+        );
+    }
+    PENDING_ACTIONS.save(deps.storage, &pending)?;
+    Ok(Response::default())
+}
+
+pub fn try_cancel_action(deps: DepsMut, id: u64, actor: &Addr, time: u64) -> StdResult<Response> {
+    let mut pending = PENDING_ACTIONS.may_load(deps.storage)?.unwrap_or_default();
+    let len = pending.len();
+    pending.retain(|action| action.id != id);
+    if pending.len() == len {
+        return Err(pending_action_not_found(&id.to_string()));
+    }
+    PENDING_ACTIONS.save(deps.storage, &pending)?;
+    log_action(deps.storage, actor, time, AuditAction::CancelAction { id })?;
+    Ok(Response::default())
+}
+
+fn execute_protected_action(
+    deps: DepsMut,
+    action: ProtectedAction,
+    actor: &Addr,
+    approvers: &[Addr],
+    time: u64,
+) -> StdResult<Response> {
+    match action {
+        ProtectedAction::UpdateRegistry { action } => {
+            try_update_registry(deps.storage, deps.api, action, actor, approvers, time)
+        }
+        ProtectedAction::UpdateRegistryBulk { actions } => {
+            try_update_registry_bulk(deps, actions, actor, approvers, time)
+        }
+        ProtectedAction::TransferSuper { new_super } => {
+            try_transfer_super(deps, new_super, actor, approvers, time)
+        }
+        ProtectedAction::SelfDestruct {} => try_self_destruct(deps, actor, approvers, time),
+        ProtectedAction::Shutdown {} => {
+            try_toggle_status(deps, AdminAuthStatus::Shutdown, actor, approvers, time)
+        }
+        ProtectedAction::SetApprovers {
+            approvers: new_approvers,
+            threshold,
+        } => {
+            let approver_set = validate_approvers(deps.api, &new_approvers, threshold)?;
+            log_approved_action(deps.storage, actor, approvers, time, AuditAction::SetApprovers {
+                approvers: approver_set.approvers.clone(),
+                threshold,
+            })?;
+            APPROVERS.save(deps.storage, &approver_set)?;
+            // Approvals collected under the previous set no longer apply
+            PENDING_ACTIONS.save(deps.storage, &vec![])?;
+            Ok(Response::default())
+        }
+    }
+}
+
+/// An empty set with a threshold of 0 disables approvals.
+fn validate_approvers(
+    api: &dyn Api,
+    approvers: &[String],
+    threshold: u8,
+) -> StdResult<ApproverSet> {
+    let mut valid_approvers: Vec<Addr> = vec![];
+    for approver in approvers {
+        let approver = api.addr_validate(approver.as_str())?;
+        if !valid_approvers.contains(&approver) {
+            valid_approvers.push(approver);
+        }
+    }
+    let valid_threshold = if valid_approvers.is_empty() {
+        threshold == 0
+    } else {
+        threshold > 0 && threshold as usize <= valid_approvers.len()
+    };
+    if !valid_threshold {
+        return Err(invalid_approval_threshold(
+            &threshold.to_string(),
+            &valid_approvers.len().to_string(),
+        ));
+    }
+    Ok(ApproverSet {
+        approvers: valid_approvers,
+        threshold,
+    })
+}
+
 fn resolve_registry_action(
     store: &mut dyn Storage,
     admins: &mut Vec<Addr>,
@@ -72,13 +307,27 @@ fn resolve_registry_action(
 ) -> StdResult<()> {
     match action {
         RegistryAction::RegisterAdmin { user } => register_admin(store, admins, api, user),
-        RegistryAction::GrantAccess { permissions, user } => {
-            grant_access(store, api, admins, permissions, user)
-        }
-        RegistryAction::RevokeAccess { permissions, user } => {
-            revoke_access(store, api, admins, permissions, user)
+        RegistryAction::GrantAccess {
+            permissions,
+            user,
+            contract,
+            not_before,
+            expires_at,
+        } => {
+            let window = permission_window(not_before, expires_at)?;
+            grant_access(store, api, admins, permissions, user, contract, window)
         }
+        RegistryAction::RevokeAccess {
+            permissions,
+            user,
+            contract,
+        } => revoke_access(store, api, admins, permissions, user, contract),
         RegistryAction::DeleteAdmin { user } => delete_admin(store, admins, api, user),
+        RegistryAction::CreateRole { role, permissions } => create_role(store, role, permissions),
+        RegistryAction::EditRole { role, permissions } => edit_role(store, role, permissions),
+        RegistryAction::DeleteRole { role } => delete_role(store, role),
+        RegistryAction::AssignRole { role, user } => assign_role(store, api, admins, role, user),
+        RegistryAction::UnassignRole { role, user } => unassign_role(store, api, role, user),
     }?;
     Ok(())
 }
@@ -109,21 +358,110 @@ fn delete_admin(
         // Delete admin from list.
         admins.retain(|x| x.ne(&user_addr));
         // Delete their permissions.
-        PERMISSIONS.remove(store, &user_addr);
+        clear_global_permissions(store, &user_addr)?;
+        clear_scoped_permissions(store, &user_addr)?;
+        clear_roles(store, &user_addr)?;
     };
     Ok(())
 }
 
+fn clear_global_permissions(store: &mut dyn Storage, user: &Addr) -> StdResult<()> {
+    if let Some(permissions) = PERMISSIONS.may_load(store, user)? {
+        clear_grants(store, user, None, &permissions)?;
+        PERMISSIONS.remove(store, user);
+    }
+    Ok(())
+}
+
+fn clear_scoped_permissions(store: &mut dyn Storage, user: &Addr) -> StdResult<()> {
+    if let Some(contracts) = SCOPED_CONTRACTS.may_load(store, user)? {
+        for contract in contracts.iter() {
+            let permissions = SCOPED_PERMISSIONS
+                .may_load(store, (user, contract))?
+                .unwrap_or_default();
+            clear_grants(store, user, Some(contract), &permissions)?;
+            SCOPED_PERMISSIONS.remove(store, (user, contract));
+        }
+        SCOPED_CONTRACTS.remove(store, user);
+    }
+    Ok(())
+}
+
+fn clear_roles(store: &mut dyn Storage, user: &Addr) -> StdResult<()> {
+    if let Some(roles) = USER_ROLES.may_load(store, user)? {
+        for role in roles.iter() {
+            remove_role_member(store, role, user)?;
+        }
+        USER_ROLES.remove(store, user);
+    }
+    Ok(())
+}
+
+/// Drops the window and holder entry of each grant.
+fn clear_grants(
+    store: &mut dyn Storage,
+    user: &Addr,
+    contract: Option<&Addr>,
+    permissions: &[String],
+) -> StdResult<()> {
+    for permission in permissions {
+        save_permission_window(store, user, contract, permission, None)?;
+        remove_permission_holder(store, permission, user, contract)?;
+    }
+    Ok(())
+}
+
+/// Grants without either bound are permanent.
+fn permission_window(
+    not_before: Option<u64>,
+    expires_at: Option<u64>,
+) -> StdResult<Option<PermissionWindow>> {
+    match (not_before, expires_at) {
+        (None, None) => Ok(None),
+        (Some(start), Some(end)) if end <= start => Err(invalid_permission_window()),
+        _ => Ok(Some(PermissionWindow {
+            not_before,
+            expires_at,
+        })),
+    }
+}
+
 fn grant_access(
     store: &mut dyn Storage,
     api: &dyn Api,
     admins: &[Addr],
     mut permissions: Vec<String>,
     user: String,
+    contract: Option<String>,
+    window: Option<PermissionWindow>,
 ) -> StdResult<()> {
     let user = api.addr_validate(user.as_str())?;
     validate_permissions(permissions.as_slice())?;
     verify_registered(admins, &user)?;
+    // Re-granting a permission replaces its previous window
+    let contract = contract
+        .map(|contract| api.addr_validate(contract.as_str()))
+        .transpose()?;
+    for permission in permissions.iter() {
+        save_permission_window(store, &user, contract.as_ref(), permission, window.as_ref())?;
+        add_permission_holder(store, permission, &user, contract.as_ref())?;
+    }
+    if let Some(contract) = contract {
+        let mut old_perms = SCOPED_PERMISSIONS
+            .may_load(store, (&user, &contract))?
+            .unwrap_or_default();
+        permissions.retain(|c| !old_perms.contains(c));
+        old_perms.append(&mut permissions);
+        SCOPED_PERMISSIONS.save(store, (&user, &contract), &old_perms)?;
+        SCOPED_CONTRACTS.update(store, &user, |contracts| -> StdResult<_> {
+            let mut contracts = contracts.unwrap_or_default();
+            if !contracts.contains(&contract) {
+                contracts.push(contract.clone());
+            }
+            Ok(contracts)
+        })?;
+        return Ok(());
+    }
     PERMISSIONS.update(store, &user, |old_perms| -> StdResult<_> {
         match old_perms {
             Some(mut old_perms) => {
@@ -143,10 +481,32 @@ fn revoke_access(
     admins: &[Addr],
     permissions: Vec<String>,
     user: String,
+    contract: Option<String>,
 ) -> StdResult<()> {
     let user = api.addr_validate(user.as_str())?;
     validate_permissions(permissions.as_slice())?;
     verify_registered(admins, &user)?;
+    if let Some(contract) = contract {
+        let contract = api.addr_validate(contract.as_str())?;
+        let mut old_perms = SCOPED_PERMISSIONS
+            .may_load(store, (&user, &contract))?
+            .unwrap_or_default();
+        old_perms.retain(|c| !permissions.contains(c));
+        clear_grants(store, &user, Some(&contract), &permissions)?;
+        if old_perms.is_empty() {
+            // Drop the contract once nothing is scoped to it
+            SCOPED_PERMISSIONS.remove(store, (&user, &contract));
+            SCOPED_CONTRACTS.update(store, &user, |contracts| -> StdResult<_> {
+                let mut contracts = contracts.unwrap_or_default();
+                contracts.retain(|c| c.ne(&contract));
+                Ok(contracts)
+            })?;
+        } else {
+            SCOPED_PERMISSIONS.save(store, (&user, &contract), &old_perms)?;
+        }
+        return Ok(());
+    }
+    clear_grants(store, &user, None, &permissions)?;
     PERMISSIONS.update(store, &user, |old_perms| -> StdResult<_> {
         match old_perms {
             Some(mut old_perms) => {
@@ -164,4 +524,96 @@ fn verify_registered(admins: &[Addr], user: &Addr) -> StdResult<()> {
         return Err(no_permission(user.as_str()));
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+fn create_role(store: &mut dyn Storage, role: String, permissions: Vec<String>) -> StdResult<()> {
+    is_valid_role(role.as_str())?;
+    validate_permissions(permissions.as_slice())?;
+    let mut roles = ROLES.may_load(store)?.unwrap_or_default();
+    if roles.contains(&role) {
+        return Err(role_already_exists(role.as_str()));
+    }
+    ROLE_PERMISSIONS.save(store, &role, &permissions)?;
+    ROLE_MEMBERS.save(store, &role, &vec![])?;
+    roles.push(role);
+    ROLES.save(store, &roles)
+}
+
+fn edit_role(store: &mut dyn Storage, role: String, permissions: Vec<String>) -> StdResult<()> {
+    validate_permissions(permissions.as_slice())?;
+    verify_role(store, &role)?;
+    ROLE_PERMISSIONS.save(store, &role, &permissions)
+}
+
+fn delete_role(store: &mut dyn Storage, role: String) -> StdResult<()> {
+    verify_role(store, &role)?;
+    for member in ROLE_MEMBERS.load(store, &role)?.iter() {
+        USER_ROLES.update(store, member, |roles| -> StdResult<_> {
+            let mut roles = roles.unwrap_or_default();
+            roles.retain(|r| r.ne(&role));
+            Ok(roles)
+        })?;
+    }
+    ROLE_PERMISSIONS.remove(store, &role);
+    ROLE_MEMBERS.remove(store, &role);
+    ROLES.update(store, |mut roles| -> StdResult<_> {
+        roles.retain(|r| r.ne(&role));
+        Ok(roles)
+    })?;
+    Ok(())
+}
+
+fn assign_role(
+    store: &mut dyn Storage,
+    api: &dyn Api,
+    admins: &[Addr],
+    role: String,
+    user: String,
+) -> StdResult<()> {
+    let user = api.addr_validate(user.as_str())?;
+    verify_registered(admins, &user)?;
+    verify_role(store, &role)?;
+    let mut roles = USER_ROLES.may_load(store, &user)?.unwrap_or_default();
+    if !roles.contains(&role) {
+        ROLE_MEMBERS.update(store, &role, |members| -> StdResult<_> {
+            let mut members = members.unwrap_or_default();
+            members.push(user.clone());
+            Ok(members)
+        })?;
+        roles.push(role);
+        USER_ROLES.save(store, &user, &roles)?;
+    }
+    Ok(())
+}
+
+fn unassign_role(
+    store: &mut dyn Storage,
+    api: &dyn Api,
+    role: String,
+    user: String,
+) -> StdResult<()> {
+    let user = api.addr_validate(user.as_str())?;
+    verify_role(store, &role)?;
+    remove_role_member(store, &role, &user)?;
+    USER_ROLES.update(store, &user, |roles| -> StdResult<_> {
+        let mut roles = roles.unwrap_or_default();
+        roles.retain(|r| r.ne(&role));
+        Ok(roles)
+    })?;
+    Ok(())
+}
+
+fn remove_role_member(store: &mut dyn Storage, role: &str, user: &Addr) -> StdResult<()> {
+    if let Some(mut members) = ROLE_MEMBERS.may_load(store, role)? {
+        members.retain(|member| member.ne(user));
+        ROLE_MEMBERS.save(store, role, &members)?;
+    }
+    Ok(())
+}
+
+fn verify_role(store: &dyn Storage, role: &str) -> StdResult<()> {
+    if ROLE_PERMISSIONS.may_load(store, role)?.is_none() {
+        return Err(role_not_found(role));
+    }
+    Ok(())
+}
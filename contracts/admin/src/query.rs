@@ -1,14 +1,39 @@
-use crate::shared::{is_valid_permission, PERMISSIONS, STATUS, SUPER};
+use crate::shared::{
+    is_active_grant, is_valid_permission, role_grants, AUDIT_LOG, AUDIT_LOG_SIZE,
+    EXPIRING_PERMISSIONS, PERMISSIONS, PERMISSION_HOLDERS, ROLES, ROLE_MEMBERS, ROLE_PERMISSIONS,
+    SCOPED_PERMISSIONS, STATUS, SUPER,
+};
 use shade_protocol::{
-    admin::{errors::unregistered_admin, ValidateAdminPermissionResponse},
+    admin::{
+        errors::{role_not_found, unregistered_admin},
+        AuditLogResponse,
+        ExpiringPermissionsResponse,
+        PermissionHolder,
+        PermissionHoldersResponse,
+        PermissionsResponse,
+        Role,
+        RoleMembersResponse,
+        RolesResponse,
+        ValidateAdminPermissionResponse,
+    },
     c_std::{Deps, StdResult},
 };
 
+const DEFAULT_AUDIT_LIMIT: u64 = 30;
+const MAX_AUDIT_LIMIT: u64 = 100;
+const DEFAULT_HOLDERS_LIMIT: u64 = 30;
+const MAX_HOLDERS_LIMIT: u64 = 100;
+
 /// Checks if the user has the requested permission. Permissions are case sensitive.
+/// Permissions scoped to a contract are only valid when that contract is given.
+/// Grants are ignored outside of their time window.
+/// Permissions granted by the user's roles are always global.
 pub fn query_validate_permission(
     deps: Deps,
+    now: u64,
     permission: String,
     user: String,
+    contract: Option<String>,
 ) -> StdResult<ValidateAdminPermissionResponse> {
     STATUS
         .load(deps.storage)?
@@ -27,8 +52,26 @@ pub fn query_validate_permission(
         let permissions = PERMISSIONS.may_load(deps.storage, &valid_user)?;
         match permissions {
             Some(permissions) => {
-                if permissions.iter().any(|perm| permission.eq(perm)) {
+                if permissions.iter().any(|perm| permission.eq(perm))
+                    && is_active_grant(deps.storage, &valid_user, None, &permission, now)?
+                {
+                    has_permission = true;
+                } else if role_grants(deps.storage, &valid_user, &permission)? {
                     has_permission = true;
+                } else if let Some(contract) = contract {
+                    let valid_contract = deps.api.addr_validate(contract.as_str())?;
+                    has_permission = SCOPED_PERMISSIONS
+                        .may_load(deps.storage, (&valid_user, &valid_contract))?
+                        .unwrap_or_default()
+                        .iter()
+                        .any(|perm| permission.eq(perm))
+                        && is_active_grant(
+                            deps.storage,
+                            &valid_user,
+                            Some(&valid_contract),
+                            &permission,
+                            now,
+                        )?;
                 } else {
                     has_permission = false;
                 }
@@ -38,3 +81,118 @@ pub fn query_validate_permission(
     }
     Ok(ValidateAdminPermissionResponse { has_permission })
 }
+
+/// Returns the user's global permissions, or the ones scoped to the contract if given.
+pub fn query_permissions(
+    deps: Deps,
+    user: String,
+    contract: Option<String>,
+) -> StdResult<PermissionsResponse> {
+    STATUS
+        .load(deps.storage)?
+        .not_shutdown()?
+        .not_under_maintenance()?;
+    let validated_user = deps.api.addr_validate(user.as_str())?;
+    let permissions = PERMISSIONS.load(deps.storage, &validated_user)?;
+    let permissions = match contract {
+        None => permissions,
+        Some(contract) => {
+            let validated_contract = deps.api.addr_validate(contract.as_str())?;
+            SCOPED_PERMISSIONS
+                .may_load(deps.storage, (&validated_user, &validated_contract))?
+                .unwrap_or_default()
+        }
+    };
+    Ok(PermissionsResponse { permissions })
+}
+
+/// Returns the grants which expire within the given amount of seconds.
+pub fn query_expiring_permissions(
+    deps: Deps,
+    now: u64,
+    within: u64,
+) -> StdResult<ExpiringPermissionsResponse> {
+    STATUS
+        .load(deps.storage)?
+        .not_shutdown()?
+        .not_under_maintenance()?;
+    let deadline = now.saturating_add(within);
+    let mut permissions = EXPIRING_PERMISSIONS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    permissions.retain(|grant| grant.expires_at > now && grant.expires_at <= deadline);
+    permissions.sort_by_key(|grant| grant.expires_at);
+    Ok(ExpiringPermissionsResponse { permissions })
+}
+
+pub fn query_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let mut roles = vec![];
+    for name in ROLES.may_load(deps.storage)?.unwrap_or_default() {
+        let permissions = ROLE_PERMISSIONS.load(deps.storage, &name)?;
+        roles.push(Role { name, permissions });
+    }
+    Ok(RolesResponse { roles })
+}
+
+pub fn query_role_members(deps: Deps, role: String) -> StdResult<RoleMembersResponse> {
+    match ROLE_MEMBERS.may_load(deps.storage, &role)? {
+        Some(members) => Ok(RoleMembersResponse { members }),
+        None => Err(role_not_found(role.as_str())),
+    }
+}
+
+/// Returns the direct holders of the permission followed by the members of every role
+/// granting it, starting at the given holder.
+pub fn query_permission_holders(
+    deps: Deps,
+    permission: String,
+    start: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<PermissionHoldersResponse> {
+    is_valid_permission(permission.as_str())?;
+    let mut holders = PERMISSION_HOLDERS
+        .may_load(deps.storage, &permission)?
+        .unwrap_or_default();
+    for role in ROLES.may_load(deps.storage)?.unwrap_or_default() {
+        if !ROLE_PERMISSIONS
+            .may_load(deps.storage, &role)?
+            .unwrap_or_default()
+            .contains(&permission)
+        {
+            continue;
+        }
+        for user in ROLE_MEMBERS.may_load(deps.storage, &role)?.unwrap_or_default() {
+            holders.push(PermissionHolder {
+                user,
+                contract: None,
+                role: Some(role.clone()),
+            });
+        }
+    }
+
+    let total = holders.len() as u64;
+    let start = start.unwrap_or_default().min(total) as usize;
+    let limit = limit.unwrap_or(DEFAULT_HOLDERS_LIMIT).min(MAX_HOLDERS_LIMIT) as usize;
+    Ok(PermissionHoldersResponse {
+        holders: holders.into_iter().skip(start).take(limit).collect(),
+        total,
+    })
+}
+
+/// Returns log entries in the order they happened, starting at the given entry.
+pub fn query_audit_log(
+    deps: Deps,
+    start: Option<u64>,
+    limit: Option<u64>,
+) -> StdResult<AuditLogResponse> {
+    let total = AUDIT_LOG_SIZE.may_load(deps.storage)?.unwrap_or_default();
+    let start = start.unwrap_or_default();
+    let limit = limit.unwrap_or(DEFAULT_AUDIT_LIMIT).min(MAX_AUDIT_LIMIT);
+    let end = start.saturating_add(limit).min(total);
+
+    let mut entries = vec![];
+    for id in start..end {
+        entries.push(AUDIT_LOG.load(deps.storage, id)?);
+    }
+    Ok(AuditLogResponse { entries, total })
+}
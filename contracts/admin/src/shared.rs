@@ -1,13 +1,57 @@
 use shade_protocol::c_std::Addr;
-use shade_protocol::{admin::{AdminAuthStatus, errors::invalid_permission_format}, c_std::StdResult};
+use shade_protocol::{
+    admin::{
+        errors::{invalid_permission_format, invalid_role_format},
+        AdminAuthStatus,
+        ApproverSet,
+        AuditAction,
+        AuditEntry,
+        ExpiringPermission,
+        PendingAction,
+        PermissionHolder,
+        PermissionWindow,
+    },
+    c_std::{StdResult, Storage},
+};
 use shade_protocol::utils::storage::plus::{Map, Item};
 
 /// Maps user to permissions for which they have user.
 pub const PERMISSIONS: Map<&Addr, Vec<String>> = Map::new("permissions");
+/// Maps user and contract to permissions which the user only has on that contract.
+pub const SCOPED_PERMISSIONS: Map<(&Addr, &Addr), Vec<String>> = Map::new("scoped_permissions");
+/// Contracts on which the user has scoped permissions.
+pub const SCOPED_CONTRACTS: Map<&Addr, Vec<Addr>> = Map::new("scoped_contracts");
+/// Time window of a grant, keyed by user, contract and permission.
+/// Global grants use an empty contract.
+pub const PERMISSION_WINDOWS: Map<(&Addr, &str, &str), PermissionWindow> =
+    Map::new("permission_windows");
+/// All grants which have an expiration, expired grants are pruned on registry updates.
+pub const EXPIRING_PERMISSIONS: Item<Vec<ExpiringPermission>> = Item::new("expiring_permissions");
+/// Maps permission to the users which were directly granted it.
+pub const PERMISSION_HOLDERS: Map<&str, Vec<PermissionHolder>> = Map::new("permission_holders");
+/// Append only log of registry, super and status changes.
+pub const AUDIT_LOG: Map<u64, AuditEntry> = Map::new("audit_log");
+pub const AUDIT_LOG_SIZE: Item<u64> = Item::new("audit_log_size");
+/// Names of all roles.
+pub const ROLES: Item<Vec<String>> = Item::new("roles");
+/// Maps role to the permissions it grants.
+pub const ROLE_PERMISSIONS: Map<&str, Vec<String>> = Map::new("role_permissions");
+/// Maps role to its members.
+pub const ROLE_MEMBERS: Map<&str, Vec<Addr>> = Map::new("role_members");
+/// Maps user to the roles assigned to them.
+pub const USER_ROLES: Map<&Addr, Vec<String>> = Map::new("user_roles");
 /// List of all admins.
 pub const ADMINS: Item<Vec<Addr>> = Item::new("admins");
 /// Super user.
 pub const SUPER: Item<Addr> = Item::new("super");
+/// Proposed super user, must accept before becoming the super user.
+pub const PENDING_SUPER: Item<Addr> = Item::new("pending_super");
+/// Approvers required for destructive actions.
+pub const APPROVERS: Item<ApproverSet> = Item::new("approvers");
+/// Destructive actions waiting for approvals.
+pub const PENDING_ACTIONS: Item<Vec<PendingAction>> = Item::new("pending_actions");
+/// Used to assign pending action ids.
+pub const ACTION_COUNT: Item<u64> = Item::new("action_count");
 /// Whether or not this contract can be consumed.
 pub const STATUS: Item<AdminAuthStatus> = Item::new("is_active");
 
@@ -33,4 +77,155 @@ pub fn is_valid_permission(permission: &str) -> StdResult<()> {
         ));
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+fn window_contract(contract: Option<&Addr>) -> &str {
+    contract.map_or("", |contract| contract.as_str())
+}
+
+/// Checks that the grant is not outside of its time window, grants without one are always active.
+pub fn is_active_grant(
+    store: &dyn Storage,
+    user: &Addr,
+    contract: Option<&Addr>,
+    permission: &str,
+    now: u64,
+) -> StdResult<bool> {
+    Ok(PERMISSION_WINDOWS
+        .may_load(store, (user, window_contract(contract), permission))?
+        .map_or(true, |window| window.is_active(now)))
+}
+
+/// Replaces the time window of a grant, None makes the grant permanent.
+pub fn save_permission_window(
+    store: &mut dyn Storage,
+    user: &Addr,
+    contract: Option<&Addr>,
+    permission: &str,
+    window: Option<&PermissionWindow>,
+) -> StdResult<()> {
+    let key = (user, window_contract(contract), permission);
+    let mut expiring = EXPIRING_PERMISSIONS.may_load(store)?.unwrap_or_default();
+    expiring.retain(|grant| {
+        !(grant.user.eq(user)
+            && grant.contract.as_ref() == contract
+            && grant.permission == permission)
+    });
+
+    match window {
+        Some(window) => {
+            PERMISSION_WINDOWS.save(store, key, window)?;
+            if let Some(expires_at) = window.expires_at {
+                expiring.push(ExpiringPermission {
+                    user: user.clone(),
+                    permission: permission.to_string(),
+                    contract: contract.cloned(),
+                    expires_at,
+                });
+            }
+        }
+        None => PERMISSION_WINDOWS.remove(store, key),
+    }
+
+    EXPIRING_PERMISSIONS.save(store, &expiring)
+}
+
+/// Drops grants which already expired, their windows keep them inactive.
+pub fn prune_expiring_permissions(store: &mut dyn Storage, now: u64) -> StdResult<()> {
+    let mut expiring = EXPIRING_PERMISSIONS.may_load(store)?.unwrap_or_default();
+    let len = expiring.len();
+    expiring.retain(|grant| grant.expires_at > now);
+    if expiring.len() != len {
+        EXPIRING_PERMISSIONS.save(store, &expiring)?;
+    }
+    Ok(())
+}
+
+pub fn is_valid_role(role: &str) -> StdResult<()> {
+    let valid_chars = role.bytes().all(|byte| {
+        (b'a'..=b'z').contains(&byte) || (b'0'..=b'9').contains(&byte) || b'-'.eq(&byte)
+    });
+    if role.is_empty() || role.len() > 64 || !valid_chars {
+        return Err(invalid_role_format(role));
+    }
+    Ok(())
+}
+
+/// Checks if any of the user's roles grants the permission.
+pub fn role_grants(store: &dyn Storage, user: &Addr, permission: &str) -> StdResult<bool> {
+    for role in USER_ROLES.may_load(store, user)?.unwrap_or_default() {
+        if ROLE_PERMISSIONS
+            .may_load(store, &role)?
+            .unwrap_or_default()
+            .iter()
+            .any(|perm| permission.eq(perm))
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+pub fn add_permission_holder(
+    store: &mut dyn Storage,
+    permission: &str,
+    user: &Addr,
+    contract: Option<&Addr>,
+) -> StdResult<()> {
+    let holder = PermissionHolder {
+        user: user.clone(),
+        contract: contract.cloned(),
+        role: None,
+    };
+    let mut holders = PERMISSION_HOLDERS.may_load(store, permission)?.unwrap_or_default();
+    if !holders.contains(&holder) {
+        holders.push(holder);
+        PERMISSION_HOLDERS.save(store, permission, &holders)?;
+    }
+    Ok(())
+}
+
+pub fn remove_permission_holder(
+    store: &mut dyn Storage,
+    permission: &str,
+    user: &Addr,
+    contract: Option<&Addr>,
+) -> StdResult<()> {
+    if let Some(mut holders) = PERMISSION_HOLDERS.may_load(store, permission)? {
+        holders.retain(|holder| !(holder.user.eq(user) && holder.contract.as_ref() == contract));
+        if holders.is_empty() {
+            PERMISSION_HOLDERS.remove(store, permission);
+        } else {
+            PERMISSION_HOLDERS.save(store, permission, &holders)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn log_action(
+    store: &mut dyn Storage,
+    actor: &Addr,
+    time: u64,
+    action: AuditAction,
+) -> StdResult<()> {
+    log_approved_action(store, actor, &[], time, action)
+}
+
+/// Actions which waited for approvals are logged under the admin that proposed them.
+pub fn log_approved_action(
+    store: &mut dyn Storage,
+    actor: &Addr,
+    approvers: &[Addr],
+    time: u64,
+    action: AuditAction,
+) -> StdResult<()> {
+    let id = AUDIT_LOG_SIZE.may_load(store)?.unwrap_or_default();
+    AUDIT_LOG.save(store, id, &AuditEntry {
+        id,
+        actor: actor.clone(),
+        approvers: approvers.to_vec(),
+        action,
+        time,
+    })?;
+    AUDIT_LOG_SIZE.save(store, &(id + 1))
+}
@@ -1,5 +1,11 @@
 use crate::{
-    shared::is_valid_permission,
+    shared::{
+        is_active_grant,
+        is_valid_permission,
+        prune_expiring_permissions,
+        save_permission_window,
+        EXPIRING_PERMISSIONS,
+    },
 };
 use rstest::*;
 use shade_multi_test::multi::admin::Admin;
@@ -7,15 +13,25 @@ use shade_protocol::{
     admin::{
         AdminAuthStatus,
         AdminsResponse,
+        AuditAction,
+        AuditLogResponse,
         ConfigResponse,
         ExecuteMsg,
+        ExpiringPermissionsResponse,
+        PendingActionsResponse,
+        PendingSuperResponse,
+        PermissionHolder,
+        PermissionHoldersResponse,
         InstantiateMsg,
+        PermissionWindow,
         PermissionsResponse,
         QueryMsg,
         RegistryAction,
+        RoleMembersResponse,
+        RolesResponse,
         ValidateAdminPermissionResponse,
     },
-    c_std::Addr,
+    c_std::{testing::mock_dependencies, Addr, Timestamp},
     multi_test::App,
     utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
 };
@@ -70,6 +86,8 @@ fn test_status(#[case] status: AdminAuthStatus, #[case] expect_success: Vec<bool
         //set super admin to 'super'
         let result = ExecuteMsg::TransferSuper { new_super: "super".to_string() }.test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[]);
         assert_eq!(&result.is_ok(), expect_success.get(2).unwrap());
+        let result = ExecuteMsg::AcceptSuper {}.test_exec(&contract, &mut chain, Addr::unchecked("super"), &[]);
+        assert_eq!(&result.is_ok(), expect_success.get(2).unwrap());
 
         //register 'admin' as admin without being the super user
         let action = RegistryAction::RegisterAdmin { user: "admin".to_string() };
@@ -84,6 +102,8 @@ fn test_status(#[case] status: AdminAuthStatus, #[case] expect_success: Vec<bool
         //set super admin to 'admin'
         let result = ExecuteMsg::TransferSuper { new_super: "admin".to_string() }.test_exec(&contract, &mut chain, Addr::unchecked("super"), &[]);
         assert_eq!(&result.is_ok(), expect_success.get(5).unwrap());
+        let result = ExecuteMsg::AcceptSuper {}.test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[]);
+        assert_eq!(&result.is_ok(), expect_success.get(5).unwrap());
 
         //self destruct
         let result = ExecuteMsg::SelfDestruct {  }.test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[]);
@@ -230,6 +250,9 @@ fn test_permissions(
             RegistryAction::GrantAccess {
                 permissions: permission.1.iter().map(|&i| i.to_string()).collect(),
                 user: permission.0.to_string(),
+                contract: None,
+                not_before: None,
+                expires_at: None,
             },
         ])
     }
@@ -254,6 +277,7 @@ fn test_permissions(
         // Check that the permissions are correctly returned
         let stored_permissions: PermissionsResponse = QueryMsg::GetPermissions {
             user: permission.0.to_string(),
+            contract: None,
         }
         .test_query(&admin, &chain)
         .unwrap();
@@ -266,6 +290,7 @@ fn test_permissions(
             let res: ValidateAdminPermissionResponse = QueryMsg::ValidateAdminPermission {
                 permission: perm.to_string(),
                 user: permission.0.to_string(),
+                contract: None,
             }
             .test_query(&admin, &chain)
             .unwrap();
@@ -279,6 +304,7 @@ fn test_permissions(
         .map(|permission| RegistryAction::RevokeAccess {
             permissions: permission.1.iter().map(|&item| item.to_string()).collect(),
             user: permission.0.to_string(),
+            contract: None,
         })
         .collect();
 
@@ -302,6 +328,7 @@ fn test_permissions(
         // Check that the permissions are correctly returned
         let stored_permissions: PermissionsResponse = QueryMsg::GetPermissions {
             user: permission.0.to_string(),
+            contract: None,
         }
         .test_query(&admin, &chain)
         .unwrap();
@@ -330,6 +357,7 @@ fn test_permissions(
             let res: ValidateAdminPermissionResponse = QueryMsg::ValidateAdminPermission {
                 permission: perm.to_string(),
                 user: permission.0.to_string(),
+                contract: None,
             }
             .test_query(&admin, &chain)
             .unwrap();
@@ -337,3 +365,816 @@ fn test_permissions(
         }
     }
 }
+
+#[test]
+fn test_scoped_permissions() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "user".to_string(),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+                user: "user".to_string(),
+                contract: Some("treasury_a".to_string()),
+                not_before: None,
+                expires_at: None,
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let has_permission = |chain: &App, contract: Option<&str>| -> bool {
+        let res: ValidateAdminPermissionResponse = QueryMsg::ValidateAdminPermission {
+            permission: "SHADE_TREASURY_ADMIN".to_string(),
+            user: "user".to_string(),
+            contract: contract.map(|c| c.to_string()),
+        }
+        .test_query(&admin, chain)
+        .unwrap();
+        res.has_permission
+    };
+
+    // Scoped grants only apply to their contract
+    assert!(has_permission(&chain, Some("treasury_a")));
+    assert!(!has_permission(&chain, Some("treasury_b")));
+    assert!(!has_permission(&chain, None));
+
+    let stored_permissions: PermissionsResponse = QueryMsg::GetPermissions {
+        user: "user".to_string(),
+        contract: None,
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert!(stored_permissions.permissions.is_empty());
+
+    let stored_permissions: PermissionsResponse = QueryMsg::GetPermissions {
+        user: "user".to_string(),
+        contract: Some("treasury_a".to_string()),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(stored_permissions.permissions, vec![
+        "SHADE_TREASURY_ADMIN".to_string()
+    ]);
+
+    // Global grants apply to every contract
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::GrantAccess {
+            permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+            user: "user".to_string(),
+            contract: None,
+            not_before: None,
+            expires_at: None,
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    assert!(has_permission(&chain, Some("treasury_b")));
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RevokeAccess {
+                permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+                user: "user".to_string(),
+                contract: None,
+            },
+            RegistryAction::RevokeAccess {
+                permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+                user: "user".to_string(),
+                contract: Some("treasury_a".to_string()),
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    assert!(!has_permission(&chain, Some("treasury_a")));
+
+    // Deleting the admin clears their scoped grants
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::GrantAccess {
+                permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+                user: "user".to_string(),
+                contract: Some("treasury_a".to_string()),
+                not_before: None,
+                expires_at: None,
+            },
+            RegistryAction::DeleteAdmin {
+                user: "user".to_string(),
+            },
+            RegistryAction::RegisterAdmin {
+                user: "user".to_string(),
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    assert!(!has_permission(&chain, Some("treasury_a")));
+}
+
+#[test]
+fn test_permission_windows() {
+    let mut chain = App::default();
+    chain.update_block(|block| block.time = Timestamp::from_seconds(0));
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    // Windows must end after they start
+    assert!(
+        ExecuteMsg::UpdateRegistryBulk {
+            actions: vec![
+                RegistryAction::RegisterAdmin {
+                    user: "user".to_string(),
+                },
+                RegistryAction::GrantAccess {
+                    permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+                    user: "user".to_string(),
+                    contract: None,
+                    not_before: Some(100),
+                    expires_at: Some(100),
+                },
+            ],
+        }
+        .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+        .is_err()
+    );
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "user".to_string(),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+                user: "user".to_string(),
+                contract: None,
+                not_before: Some(100),
+                expires_at: Some(200),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec!["SHADE_TREASURY_MANAGER".to_string()],
+                user: "user".to_string(),
+                contract: Some("treasury_a".to_string()),
+                not_before: None,
+                expires_at: Some(1000),
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let has_permission = |chain: &App, permission: &str, contract: Option<&str>| -> bool {
+        let res: ValidateAdminPermissionResponse = QueryMsg::ValidateAdminPermission {
+            permission: permission.to_string(),
+            user: "user".to_string(),
+            contract: contract.map(|c| c.to_string()),
+        }
+        .test_query(&admin, chain)
+        .unwrap();
+        res.has_permission
+    };
+
+    // Not yet active
+    assert!(!has_permission(&chain, "SHADE_TREASURY_ADMIN", None));
+    assert!(has_permission(&chain, "SHADE_TREASURY_MANAGER", Some("treasury_a")));
+
+    let expiring: ExpiringPermissionsResponse =
+        QueryMsg::GetExpiringPermissions { within: 500 }
+            .test_query(&admin, &chain)
+            .unwrap();
+    assert_eq!(expiring.permissions.len(), 1);
+    assert_eq!(expiring.permissions[0].permission, "SHADE_TREASURY_ADMIN");
+    assert_eq!(expiring.permissions[0].expires_at, 200);
+
+    chain.update_block(|block| block.time = Timestamp::from_seconds(150));
+    assert!(has_permission(&chain, "SHADE_TREASURY_ADMIN", None));
+
+    let expiring: ExpiringPermissionsResponse =
+        QueryMsg::GetExpiringPermissions { within: 1000 }
+            .test_query(&admin, &chain)
+            .unwrap();
+    assert_eq!(expiring.permissions.len(), 2);
+    assert_eq!(
+        expiring.permissions[1].contract,
+        Some(Addr::unchecked("treasury_a"))
+    );
+
+    // Expired
+    chain.update_block(|block| block.time = Timestamp::from_seconds(200));
+    assert!(!has_permission(&chain, "SHADE_TREASURY_ADMIN", None));
+    assert!(has_permission(&chain, "SHADE_TREASURY_MANAGER", Some("treasury_a")));
+
+    // Re-granting without a window makes the grant permanent
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::GrantAccess {
+            permissions: vec!["SHADE_TREASURY_ADMIN".to_string()],
+            user: "user".to_string(),
+            contract: None,
+            not_before: None,
+            expires_at: None,
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    assert!(has_permission(&chain, "SHADE_TREASURY_ADMIN", None));
+
+    let expiring: ExpiringPermissionsResponse =
+        QueryMsg::GetExpiringPermissions { within: 1000 }
+            .test_query(&admin, &chain)
+            .unwrap();
+    assert_eq!(expiring.permissions.len(), 1);
+}
+
+#[test]
+fn test_prune_expiring_permissions() {
+    let mut deps = mock_dependencies();
+    let user = Addr::unchecked("user");
+
+    let grants = [("SHADE_TREASURY_ADMIN", 100), ("SHADE_TREASURY_MANAGER", 300)];
+    for (permission, expires_at) in grants {
+        save_permission_window(
+            deps.as_mut().storage,
+            &user,
+            None,
+            permission,
+            Some(&PermissionWindow {
+                not_before: None,
+                expires_at: Some(expires_at),
+            }),
+        )
+        .unwrap();
+    }
+
+    prune_expiring_permissions(deps.as_mut().storage, 200).unwrap();
+    let expiring = EXPIRING_PERMISSIONS.load(deps.as_ref().storage).unwrap();
+    assert_eq!(expiring.len(), 1);
+    assert_eq!(expiring[0].permission, "SHADE_TREASURY_MANAGER");
+
+    // The window of the pruned grant still denies it
+    assert!(
+        !is_active_grant(deps.as_ref().storage, &user, None, "SHADE_TREASURY_ADMIN", 200).unwrap()
+    );
+}
+
+#[test]
+fn test_super_transfer() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "new_super".to_string(),
+            },
+            RegistryAction::RegisterAdmin {
+                user: "other".to_string(),
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    ExecuteMsg::TransferSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    // Nothing changes until the transfer is accepted
+    let config: ConfigResponse = QueryMsg::GetConfig {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(config.super_admin.as_str(), "admin");
+    let pending: PendingSuperResponse = QueryMsg::GetPendingSuper {}
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert_eq!(pending.pending_super, Some(Addr::unchecked("new_super")));
+
+    assert!(
+        ExecuteMsg::AcceptSuper {}
+            .test_exec(&admin, &mut chain, Addr::unchecked("other"), &[])
+            .is_err()
+    );
+
+    ExecuteMsg::CancelSuperTransfer {}
+        .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+        .unwrap();
+    assert!(
+        ExecuteMsg::AcceptSuper {}
+            .test_exec(&admin, &mut chain, Addr::unchecked("new_super"), &[])
+            .is_err()
+    );
+    // Nothing left to cancel
+    assert!(
+        ExecuteMsg::CancelSuperTransfer {}
+            .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+            .is_err()
+    );
+
+    ExecuteMsg::TransferSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    ExecuteMsg::AcceptSuper {}
+        .test_exec(&admin, &mut chain, Addr::unchecked("new_super"), &[])
+        .unwrap();
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(config.super_admin.as_str(), "new_super");
+    let pending: PendingSuperResponse = QueryMsg::GetPendingSuper {}
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert!(pending.pending_super.is_none());
+    let response: AdminsResponse = QueryMsg::GetAdmins {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(response.admins, vec![Addr::unchecked("other")]);
+}
+
+#[test]
+fn test_approvals() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    // Threshold can't exceed the approvers
+    assert!(
+        ExecuteMsg::SetApprovers {
+            approvers: vec!["alice".to_string(), "bob".to_string()],
+            threshold: 3,
+        }
+        .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+        .is_err()
+    );
+
+    ExecuteMsg::SetApprovers {
+        approvers: vec!["alice".to_string(), "bob".to_string(), "carol".to_string()],
+        threshold: 2,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    // Registry updates and super transfers wait for approvals too
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::RegisterAdmin {
+            user: "user".to_string(),
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    ExecuteMsg::TransferSuper {
+        new_super: "user".to_string(),
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    let response: AdminsResponse = QueryMsg::GetAdmins {}.test_query(&admin, &chain).unwrap();
+    assert!(response.admins.is_empty());
+
+    let pending: PendingActionsResponse = QueryMsg::GetPendingActions {}
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert_eq!(pending.actions.len(), 2);
+    for action in pending.actions {
+        for approver in ["alice", "bob"] {
+            ExecuteMsg::ApproveAction { id: action.id }
+                .test_exec(&admin, &mut chain, Addr::unchecked(approver), &[])
+                .unwrap();
+        }
+    }
+    let response: AdminsResponse = QueryMsg::GetAdmins {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(response.admins, vec![Addr::unchecked("user")]);
+    let pending: PendingSuperResponse = QueryMsg::GetPendingSuper {}
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert_eq!(pending.pending_super, Some(Addr::unchecked("user")));
+
+    // Approved actions are audited under the admin that proposed them
+    let log: AuditLogResponse = QueryMsg::GetAuditLog {
+        start: None,
+        limit: None,
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert!(log.entries[0].approvers.is_empty());
+    for entry in &log.entries[1..3] {
+        assert_eq!(entry.actor, Addr::unchecked("admin"));
+        assert_eq!(entry.approvers, vec![
+            Addr::unchecked("alice"),
+            Addr::unchecked("bob")
+        ]);
+    }
+
+    ExecuteMsg::ToggleStatus {
+        new_status: AdminAuthStatus::Shutdown,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    let config: ConfigResponse = QueryMsg::GetConfig {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(config.status, AdminAuthStatus::Active);
+
+    let pending: PendingActionsResponse = QueryMsg::GetPendingActions {}
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert_eq!(pending.actions.len(), 1);
+    let id = pending.actions[0].id;
+
+    // Only approvers can approve, and only once
+    assert!(
+        ExecuteMsg::ApproveAction { id }
+            .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+            .is_err()
+    );
+    ExecuteMsg::ApproveAction { id }
+        .test_exec(&admin, &mut chain, Addr::unchecked("alice"), &[])
+        .unwrap();
+    assert!(
+        ExecuteMsg::ApproveAction { id }
+            .test_exec(&admin, &mut chain, Addr::unchecked("alice"), &[])
+            .is_err()
+    );
+    let config: ConfigResponse = QueryMsg::GetConfig {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(config.status, AdminAuthStatus::Active);
+
+    // Reaching the threshold executes the action
+    ExecuteMsg::ApproveAction { id }
+        .test_exec(&admin, &mut chain, Addr::unchecked("bob"), &[])
+        .unwrap();
+    let config: ConfigResponse = QueryMsg::GetConfig {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(config.status, AdminAuthStatus::Shutdown);
+    let pending: PendingActionsResponse = QueryMsg::GetPendingActions {}
+        .test_query(&admin, &chain)
+        .unwrap();
+    assert!(pending.actions.is_empty());
+
+    // Cancelled actions can't be approved
+    ExecuteMsg::SelfDestruct {}
+        .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+        .unwrap();
+    let pending: PendingActionsResponse = QueryMsg::GetPendingActions {}
+        .test_query(&admin, &chain)
+        .unwrap();
+    let id = pending.actions[0].id;
+    ExecuteMsg::CancelAction { id }
+        .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+        .unwrap();
+    assert!(
+        ExecuteMsg::ApproveAction { id }
+            .test_exec(&admin, &mut chain, Addr::unchecked("carol"), &[])
+            .is_err()
+    );
+
+    // Approver changes and cancellations are audited
+    let log: AuditLogResponse = QueryMsg::GetAuditLog {
+        start: None,
+        limit: None,
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(log.entries[0].action, AuditAction::SetApprovers {
+        approvers: vec![
+            Addr::unchecked("alice"),
+            Addr::unchecked("bob"),
+            Addr::unchecked("carol"),
+        ],
+        threshold: 2,
+    });
+    assert_eq!(
+        log.entries.last().unwrap().action,
+        AuditAction::CancelAction { id }
+    );
+}
+
+#[test]
+fn test_roles() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    // Role names are validated
+    assert!(
+        ExecuteMsg::UpdateRegistry {
+            action: RegistryAction::CreateRole {
+                role: "DAO_OPERATOR".to_string(),
+                permissions: vec![],
+            },
+        }
+        .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+        .is_err()
+    );
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "operator".to_string(),
+            },
+            RegistryAction::CreateRole {
+                role: "dao-operator".to_string(),
+                permissions: vec![
+                    "SHADE_TREASURY_ADMIN".to_string(),
+                    "SHADE_TREASURY_MANAGER".to_string(),
+                ],
+            },
+            RegistryAction::AssignRole {
+                role: "dao-operator".to_string(),
+                user: "operator".to_string(),
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let has_permission = |chain: &App, permission: &str| -> bool {
+        let res: ValidateAdminPermissionResponse = QueryMsg::ValidateAdminPermission {
+            permission: permission.to_string(),
+            user: "operator".to_string(),
+            contract: None,
+        }
+        .test_query(&admin, chain)
+        .unwrap();
+        res.has_permission
+    };
+
+    assert!(has_permission(&chain, "SHADE_TREASURY_ADMIN"));
+    assert!(has_permission(&chain, "SHADE_TREASURY_MANAGER"));
+    assert!(!has_permission(&chain, "SHADE_SCRT_STAKING_ADMIN"));
+
+    let roles: RolesResponse = QueryMsg::GetRoles {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(roles.roles.len(), 1);
+    assert_eq!(roles.roles[0].name, "dao-operator");
+    let members: RoleMembersResponse = QueryMsg::GetRoleMembers {
+        role: "dao-operator".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(members.members, vec![Addr::unchecked("operator")]);
+
+    // Editing the role updates every member
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::EditRole {
+            role: "dao-operator".to_string(),
+            permissions: vec!["SHADE_SCRT_STAKING_ADMIN".to_string()],
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    assert!(!has_permission(&chain, "SHADE_TREASURY_ADMIN"));
+    assert!(has_permission(&chain, "SHADE_SCRT_STAKING_ADMIN"));
+
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::DeleteRole {
+            role: "dao-operator".to_string(),
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    assert!(!has_permission(&chain, "SHADE_SCRT_STAKING_ADMIN"));
+    assert!(
+        QueryMsg::GetRoleMembers {
+            role: "dao-operator".to_string(),
+        }
+        .test_query::<RoleMembersResponse>(&admin, &chain)
+        .is_err()
+    );
+}
+
+#[test]
+fn test_permission_holders_and_audit_log() {
+    let mut chain = App::default();
+    chain.update_block(|block| block.time = Timestamp::from_seconds(100));
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "user".to_string(),
+            },
+            RegistryAction::RegisterAdmin {
+                user: "other".to_string(),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec!["SHADE_SKY_ADMIN".to_string()],
+                user: "user".to_string(),
+                contract: None,
+                not_before: None,
+                expires_at: None,
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec!["SHADE_SKY_ADMIN".to_string()],
+                user: "other".to_string(),
+                contract: Some("sky".to_string()),
+                not_before: None,
+                expires_at: None,
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let holders = |chain: &App| -> Vec<PermissionHolder> {
+        let res: PermissionHoldersResponse = QueryMsg::GetPermissionHolders {
+            permission: "SHADE_SKY_ADMIN".to_string(),
+            start: None,
+            limit: None,
+        }
+        .test_query(&admin, chain)
+        .unwrap();
+        res.holders
+    };
+
+    assert_eq!(holders(&chain), vec![
+        PermissionHolder {
+            user: Addr::unchecked("user"),
+            contract: None,
+            role: None,
+        },
+        PermissionHolder {
+            user: Addr::unchecked("other"),
+            contract: Some(Addr::unchecked("sky")),
+            role: None,
+        },
+    ]);
+
+    chain.update_block(|block| block.time = Timestamp::from_seconds(200));
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::RevokeAccess {
+            permissions: vec!["SHADE_SKY_ADMIN".to_string()],
+            user: "user".to_string(),
+            contract: None,
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::DeleteAdmin {
+            user: "other".to_string(),
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    assert!(holders(&chain).is_empty());
+
+    ExecuteMsg::ToggleStatus {
+        new_status: AdminAuthStatus::Maintenance,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let log: AuditLogResponse = QueryMsg::GetAuditLog {
+        start: None,
+        limit: None,
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(log.total, 7);
+    assert_eq!(log.entries.len(), 7);
+    assert_eq!(log.entries[0].actor, Addr::unchecked("admin"));
+    assert_eq!(log.entries[0].time, 100);
+    assert_eq!(log.entries[4].time, 200);
+    assert_eq!(log.entries[6].action, AuditAction::ToggleStatus {
+        new_status: AdminAuthStatus::Maintenance,
+    });
+
+    // Pagination
+    let log: AuditLogResponse = QueryMsg::GetAuditLog {
+        start: Some(5),
+        limit: Some(5),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(log.entries.len(), 2);
+    assert_eq!(log.entries[0].id, 5);
+    assert_eq!(log.entries[0].action, AuditAction::Registry {
+        action: RegistryAction::DeleteAdmin {
+            user: "other".to_string(),
+        },
+    });
+}
+
+#[test]
+fn test_permission_holders_through_roles() {
+    let mut chain = App::default();
+
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "user".to_string(),
+            },
+            RegistryAction::RegisterAdmin {
+                user: "operator".to_string(),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec!["SHADE_SKY_ADMIN".to_string()],
+                user: "user".to_string(),
+                contract: None,
+                not_before: None,
+                expires_at: None,
+            },
+            RegistryAction::CreateRole {
+                role: "sky-operator".to_string(),
+                permissions: vec!["SHADE_SKY_ADMIN".to_string()],
+            },
+            RegistryAction::AssignRole {
+                role: "sky-operator".to_string(),
+                user: "operator".to_string(),
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let holders = |chain: &App, start: Option<u64>, limit: Option<u64>| {
+        let res: PermissionHoldersResponse = QueryMsg::GetPermissionHolders {
+            permission: "SHADE_SKY_ADMIN".to_string(),
+            start,
+            limit,
+        }
+        .test_query(&admin, chain)
+        .unwrap();
+        res
+    };
+
+    let res = holders(&chain, None, None);
+    assert_eq!(res.total, 2);
+    assert_eq!(res.holders, vec![
+        PermissionHolder {
+            user: Addr::unchecked("user"),
+            contract: None,
+            role: None,
+        },
+        PermissionHolder {
+            user: Addr::unchecked("operator"),
+            contract: None,
+            role: Some("sky-operator".to_string()),
+        },
+    ]);
+
+    // Pagination
+    let res = holders(&chain, Some(1), Some(1));
+    assert_eq!(res.total, 2);
+    assert_eq!(res.holders.len(), 1);
+    assert_eq!(res.holders[0].user, Addr::unchecked("operator"));
+    assert!(holders(&chain, Some(2), None).holders.is_empty());
+}
@@ -129,11 +129,11 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
     )
 }
 
-pub fn authenticate(deps: Deps, auth: Auth, query_auth: Contract) -> StdResult<Addr> {
+pub fn authenticate(deps: Deps, env: &Env, auth: Auth, query_auth: Contract) -> StdResult<Addr> {
     match auth {
         Auth::ViewingKey { key, address } => {
             let address = deps.api.addr_validate(&address)?;
-            if !authenticate_vk(address.clone(), key, &deps.querier, &query_auth)? {
+            if !authenticate_vk(address.clone(), key, &deps.querier, env, &query_auth)? {
                 return Err(StdError::generic_err("Invalid Viewing Key"));
             }
             Ok(address)
@@ -163,7 +163,7 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             unbonding_ids,
         } => {
             let config = CONFIG.load(deps.storage)?;
-            let user = authenticate(deps, auth, config.query_auth)?;
+            let user = authenticate(deps, &env, auth, config.query_auth)?;
             let unbonding_ids = match unbonding_ids {
                 Some(ids) => ids,
                 None => {
@@ -185,25 +185,33 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let config = CONFIG.load(deps.storage)?;
             to_binary(&query::user_staked(
                 deps,
-                authenticate(deps, auth, config.query_auth)?,
+                authenticate(deps, &env, auth, config.query_auth)?,
             )?)
         }
-        QueryMsg::Rewards { auth } => {
+        QueryMsg::StakedAt { auth, time } => {
             let config = CONFIG.load(deps.storage)?;
-            to_binary(&query::user_rewards(
+            to_binary(&query::user_staked_at(
                 deps,
-                env,
-                authenticate(deps, auth, config.query_auth)?,
+                authenticate(deps, &env, auth, config.query_auth)?,
+                time,
             )?)
         }
+        QueryMsg::TotalStakedAt { time } => to_binary(&query::total_staked_at(deps, time)?),
+        QueryMsg::Rewards { auth } => {
+            let config = CONFIG.load(deps.storage)?;
+            let user = authenticate(deps, &env, auth, config.query_auth)?;
+            to_binary(&query::user_rewards(deps, env, user)?)
+        }
         QueryMsg::Unbonding { auth, ids } => {
             let config = CONFIG.load(deps.storage)?;
-            let user = authenticate(deps, auth, config.query_auth)?;
-            to_binary(&query::user_unbondings(
-                deps,
-                user.clone(),
-                ids.unwrap_or(USER_UNBONDING_IDS.load(deps.storage, user)?),
-            )?)
+            let user = authenticate(deps, &env, auth, config.query_auth)?;
+            let ids = match ids {
+                Some(ids) => ids,
+                None => USER_UNBONDING_IDS
+                    .may_load(deps.storage, user.clone())?
+                    .unwrap_or_default(),
+            };
+            to_binary(&query::user_unbondings(deps, user, ids)?)
         }
         QueryMsg::TransferWhitelist {} => to_binary(&QueryAnswer::TransferWhitelist {
             whitelist: TRANSFER_WL.load(deps.storage)?,
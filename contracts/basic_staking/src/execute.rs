@@ -1,6 +1,6 @@
 use shade_protocol::{
     admin::helpers::{admin_is_valid, validate_admin, AdminPermissions},
-    basic_staking::{Action, ExecuteAnswer, RewardPoolInternal, Unbonding},
+    basic_staking::{Action, Checkpoint, ExecuteAnswer, RewardPoolInternal, Unbonding},
     c_std::{
         from_binary, to_binary, Addr, Binary, DepsMut, Env, MessageInfo, Response, StdError,
         StdResult, Storage, Uint128,
@@ -19,7 +19,7 @@ use std::cmp::{max, min};
 
 pub fn update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     admin_auth: Option<RawContract>,
     query_auth: Option<RawContract>,
@@ -31,6 +31,7 @@ pub fn update_config(
 
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::StakingAdmin,
         info.sender.to_string(),
         &config.admin_auth,
@@ -74,6 +75,7 @@ pub fn register_reward(
     let config = CONFIG.load(deps.storage)?;
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::StakingAdmin,
         info.sender.to_string(),
         &config.admin_auth,
@@ -199,12 +201,13 @@ pub fn receive(
                     response = response.add_attribute("compounded", compound_amount);
                 }
 
-                USER_STAKED.save(
+                save_user_staked(
                     deps.storage,
                     from.clone(),
-                    &(user_staked + amount + compound_amount),
+                    user_staked + amount + compound_amount,
+                    now,
                 )?;
-                TOTAL_STAKED.save(deps.storage, &(total_staked + amount + compound_amount))?;
+                save_total_staked(deps.storage, total_staked + amount + compound_amount, now)?;
 
                 REWARD_POOLS.save(deps.storage, &reward_pools.clone())?;
 
@@ -235,6 +238,7 @@ pub fn receive(
                     let config = CONFIG.load(deps.storage)?;
                     let is_admin = match admin_is_valid(
                         &deps.querier,
+                        &env,
                         AdminPermissions::StakingAdmin,
                         from.to_string(),
                         &config.admin_auth,
@@ -369,6 +373,97 @@ pub fn reward_pool_claim(
     Ok(user_reward)
 }
 
+/*
+ * Index the next checkpoint is written to and the resulting count,
+ * changes within the same block overwrite each other
+ */
+fn checkpoint_index(last: Option<Checkpoint>, count: u64, now: Uint128) -> (u64, u64) {
+    match last {
+        Some(last) if last.time == now => (count - 1, count),
+        _ => (count, count + 1),
+    }
+}
+
+/*
+ * Returns the amount held at the end of `time`,
+ * binary searching the `count` checkpoints returned by `load`
+ */
+pub fn checkpoint_at(
+    count: u64,
+    time: Uint128,
+    load: impl Fn(u64) -> StdResult<Checkpoint>,
+) -> StdResult<Uint128> {
+    // first checkpoint made after `time`
+    let (mut low, mut high) = (0, count);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if load(mid)?.time <= time {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    match low {
+        0 => Ok(Uint128::zero()),
+        i => Ok(load(i - 1)?.amount),
+    }
+}
+
+pub fn save_user_staked(
+    storage: &mut dyn Storage,
+    user: Addr,
+    amount: Uint128,
+    now: Uint128,
+) -> StdResult<()> {
+    let count = match USER_STAKED_CHECKPOINT_COUNT.may_load(storage, &user)? {
+        Some(count) => count,
+        // Stake from before checkpoints existed is carried over
+        None => match USER_STAKED.may_load(storage, user.clone())? {
+            Some(staked) if !staked.is_zero() => {
+                USER_STAKED_CHECKPOINTS.save(storage, (&user, 0), &Checkpoint {
+                    time: Uint128::zero(),
+                    amount: staked,
+                })?;
+                1
+            }
+            _ => 0,
+        },
+    };
+    let last = match count {
+        0 => None,
+        _ => Some(USER_STAKED_CHECKPOINTS.load(storage, (&user, count - 1))?),
+    };
+    let (index, count) = checkpoint_index(last, count, now);
+
+    USER_STAKED_CHECKPOINTS.save(storage, (&user, index), &Checkpoint { time: now, amount })?;
+    USER_STAKED_CHECKPOINT_COUNT.save(storage, &user, &count)?;
+    USER_STAKED.save(storage, user, &amount)
+}
+
+pub fn save_total_staked(
+    storage: &mut dyn Storage,
+    amount: Uint128,
+    now: Uint128,
+) -> StdResult<()> {
+    let count = match TOTAL_STAKED_CHECKPOINT_COUNT.may_load(storage)? {
+        Some(count) => count,
+        // Stake from before checkpoints existed is carried over
+        None => {
+            TOTAL_STAKED_CHECKPOINTS.save(storage, 0, &Checkpoint {
+                time: Uint128::zero(),
+                amount: TOTAL_STAKED.load(storage)?,
+            })?;
+            1
+        }
+    };
+    let last = TOTAL_STAKED_CHECKPOINTS.load(storage, count - 1)?;
+    let (index, count) = checkpoint_index(Some(last), count, now);
+
+    TOTAL_STAKED_CHECKPOINTS.save(storage, index, &Checkpoint { time: now, amount })?;
+    TOTAL_STAKED_CHECKPOINT_COUNT.save(storage, &count)?;
+    TOTAL_STAKED.save(storage, &amount)
+}
+
 pub fn claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
     let user_staked = USER_STAKED.load(deps.storage, info.sender.clone())?;
 
@@ -435,7 +530,7 @@ pub fn unbond(
             )));
         }
 
-        let now = env.block.time.seconds();
+        let now = Uint128::new(env.block.time.seconds() as u128);
 
         let mut total_staked = TOTAL_STAKED.load(deps.storage)?;
 
@@ -486,8 +581,8 @@ pub fn unbond(
         user_staked = (user_staked + compound_amount) - amount;
         total_staked = (total_staked + compound_amount) - amount;
 
-        TOTAL_STAKED.save(deps.storage, &total_staked)?;
-        USER_STAKED.save(deps.storage, info.sender.clone(), &user_staked)?;
+        save_total_staked(deps.storage, total_staked, now)?;
+        save_user_staked(deps.storage, info.sender.clone(), user_staked, now)?;
         REWARD_POOLS.save(deps.storage, &reward_pools)?;
 
         let mut user_unbonding_ids = USER_UNBONDING_IDS
@@ -505,7 +600,7 @@ pub fn unbond(
             &Unbonding {
                 id: next_id,
                 amount,
-                complete: Uint128::new(now as u128) + config.unbond_period,
+                complete: now + config.unbond_period,
             },
         )?;
 
@@ -644,12 +739,14 @@ pub fn compound(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Respons
         response = response.add_attribute("compounded", compound_amount);
     }
 
-    USER_STAKED.save(
+    let now = Uint128::new(env.block.time.seconds() as u128);
+    save_user_staked(
         deps.storage,
         info.sender.clone(),
-        &(user_staked + compound_amount),
+        user_staked + compound_amount,
+        now,
     )?;
-    TOTAL_STAKED.save(deps.storage, &(total_staked + compound_amount))?;
+    save_total_staked(deps.storage, total_staked + compound_amount, now)?;
 
     Ok(response.set_data(to_binary(&ExecuteAnswer::Compound {
         compounded: compound_amount,
@@ -668,6 +765,7 @@ pub fn end_reward_pool(
 
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::StakingAdmin,
         info.sender.to_string(),
         &config.admin_auth,
@@ -743,7 +841,7 @@ pub fn end_reward_pool(
 
 pub fn add_transfer_whitelist(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     user: Addr,
 ) -> StdResult<Response> {
@@ -751,6 +849,7 @@ pub fn add_transfer_whitelist(
 
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::StakingAdmin,
         info.sender.to_string(),
         &config.admin_auth,
@@ -775,7 +874,7 @@ pub fn add_transfer_whitelist(
 
 pub fn rm_transfer_whitelist(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     user: Addr,
 ) -> StdResult<Response> {
@@ -783,6 +882,7 @@ pub fn rm_transfer_whitelist(
 
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::StakingAdmin,
         info.sender.to_string(),
         &config.admin_auth,
@@ -889,11 +989,14 @@ pub fn transfer_stake(
         response = response.add_attribute("compounded", sender_compound_amount);
     }
 
+    let now = Uint128::new(env.block.time.seconds() as u128);
+
     // Adjust sender staked
-    USER_STAKED.save(
+    save_user_staked(
         deps.storage,
         info.sender,
-        &(sender_staked + sender_compound_amount - amount),
+        sender_staked + sender_compound_amount - amount,
+        now,
     )?;
 
     // Claim for receiving user
@@ -929,7 +1032,7 @@ pub fn transfer_stake(
     }
 
     // Adjust recipient staked
-    USER_STAKED.save(deps.storage, recipient, &(recipient_staked + amount))?;
+    save_user_staked(deps.storage, recipient, recipient_staked + amount, now)?;
 
     Ok(response.set_data(to_binary(&ExecuteAnswer::TransferStake {
         transferred: amount,
@@ -4,7 +4,7 @@ use shade_protocol::{
 };
 
 use crate::{
-    execute::{reward_per_token, rewards_earned},
+    execute::{checkpoint_at, reward_per_token, rewards_earned},
     storage::*,
 };
 
@@ -172,6 +172,31 @@ pub fn user_staked(deps: Deps, user: Addr) -> StdResult<QueryAnswer> {
     })
 }
 
+pub fn total_staked_at(deps: Deps, time: Uint128) -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::TotalStakedAt {
+        amount: match TOTAL_STAKED_CHECKPOINT_COUNT.may_load(deps.storage)? {
+            Some(count) => checkpoint_at(count, time, |i| {
+                TOTAL_STAKED_CHECKPOINTS.load(deps.storage, i)
+            })?,
+            None => TOTAL_STAKED.load(deps.storage)?,
+        },
+    })
+}
+
+pub fn user_staked_at(deps: Deps, user: Addr, time: Uint128) -> StdResult<QueryAnswer> {
+    let amount = match USER_STAKED_CHECKPOINT_COUNT.may_load(deps.storage, &user)? {
+        Some(count) => checkpoint_at(count, time, |i| {
+            USER_STAKED_CHECKPOINTS.load(deps.storage, (&user, i))
+        })?,
+        // Stake has not changed since checkpoints were introduced
+        None => USER_STAKED
+            .may_load(deps.storage, user.clone())?
+            .unwrap_or(Uint128::zero()),
+    };
+
+    Ok(QueryAnswer::StakedAt { user, amount })
+}
+
 pub fn user_rewards(deps: Deps, env: Env, user: Addr) -> StdResult<QueryAnswer> {
     let mut rewards = vec![];
 
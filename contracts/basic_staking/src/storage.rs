@@ -20,6 +20,14 @@ pub const REWARD_POOLS: Item<Vec<basic_staking::RewardPoolInternal>> = Item::new
 
 pub const USER_STAKED: Map<Addr, Uint128> = Map::new("user_stake");
 
+// Stake history used for snapshot queries, stored by index so a stake change only writes one entry
+pub const TOTAL_STAKED_CHECKPOINT_COUNT: Item<u64> = Item::new("total_stake_checkpoint_count");
+pub const TOTAL_STAKED_CHECKPOINTS: Map<u64, basic_staking::Checkpoint> =
+    Map::new("total_stake_checkpoints");
+pub const USER_STAKED_CHECKPOINT_COUNT: Map<&Addr, u64> = Map::new("user_stake_checkpoint_count");
+pub const USER_STAKED_CHECKPOINTS: Map<(&Addr, u64), basic_staking::Checkpoint> =
+    Map::new("user_stake_checkpoints");
+
 pub fn user_unbonding_key(user: Addr, unbond_id: Uint128) -> String {
     format!("{}-{}", user, unbond_id)
 }
@@ -53,6 +53,7 @@ fn bad_stake_token(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -88,6 +89,7 @@ fn bad_stake_token(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -132,6 +134,8 @@ fn bad_stake_token(
     // set staking user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, staking_user.clone(), &[])
@@ -140,6 +144,8 @@ fn bad_stake_token(
     // set reward user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, reward_user.clone(), &[])
@@ -199,6 +205,8 @@ fn bad_stake_token(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&reward_token, &mut app, staking_user.clone(), &[]))
@@ -251,6 +259,8 @@ fn bad_stake_token(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&reward_token, &mut app, reward_user.clone(), &[])
@@ -268,6 +278,8 @@ fn bad_stake_token(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&reward_token, &mut app, staking_user.clone(), &[]))
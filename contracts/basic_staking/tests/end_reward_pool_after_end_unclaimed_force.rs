@@ -59,6 +59,7 @@ fn end_reward_pool_after_end() {
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -87,6 +88,7 @@ fn end_reward_pool_after_end() {
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -131,6 +133,8 @@ fn end_reward_pool_after_end() {
     // set staking user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, staking_user.clone(), &[])
@@ -139,6 +143,8 @@ fn end_reward_pool_after_end() {
     // set reward user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, reward_user.clone(), &[])
@@ -198,6 +204,8 @@ fn end_reward_pool_after_end() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -239,6 +247,8 @@ fn end_reward_pool_after_end() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -334,6 +344,8 @@ fn end_reward_pool_after_end() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&second_token, &mut app, reward_user.clone(), &[])
@@ -59,6 +59,7 @@ fn end_reward_pool_before_end_claimed() {
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -103,6 +104,8 @@ fn end_reward_pool_before_end_claimed() {
     // set staking user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, staking_user.clone(), &[])
@@ -111,6 +114,8 @@ fn end_reward_pool_before_end_claimed() {
     // set reward user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, reward_user.clone(), &[])
@@ -170,6 +175,8 @@ fn end_reward_pool_before_end_claimed() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -211,6 +218,8 @@ fn end_reward_pool_before_end_claimed() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -60,6 +60,7 @@ fn end_reward_pool_before_start() {
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -104,6 +105,8 @@ fn end_reward_pool_before_start() {
     // set staking user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, staking_user.clone(), &[])
@@ -112,6 +115,8 @@ fn end_reward_pool_before_start() {
     // set reward user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, reward_user.clone(), &[])
@@ -171,6 +176,8 @@ fn end_reward_pool_before_start() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -212,6 +219,8 @@ fn end_reward_pool_before_start() {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -69,6 +69,7 @@ fn multi_staker_single_pool(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -107,6 +108,8 @@ fn multi_staker_single_pool(
         // set staking user VK
         query_auth::ExecuteMsg::SetViewingKey {
             key: viewing_key.clone(),
+            expires_at: None,
+            contracts: None,
             padding: None,
         }
         .test_exec(&query_contract, &mut app, user.clone(), &[])
@@ -116,6 +119,8 @@ fn multi_staker_single_pool(
     // set reward user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, reward_user.clone(), &[])
@@ -172,6 +177,8 @@ fn multi_staker_single_pool(
                 .unwrap(),
             ),
             memo: None,
+            decoys: None,
+            entropy: None,
             padding: None,
         }
         .test_exec(&token, &mut app, user.clone(), &[])
@@ -209,6 +216,8 @@ fn multi_staker_single_pool(
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
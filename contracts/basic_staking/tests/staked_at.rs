@@ -0,0 +1,179 @@
+use shade_protocol::c_std::{to_binary, Addr, BlockInfo, Timestamp, Uint128};
+
+use shade_protocol::{
+    contract_interfaces::{basic_staking, query_auth, snip20},
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    basic_staking::BasicStaking,
+    query_auth::QueryAuth,
+    snip20::Snip20,
+};
+
+#[test]
+fn staked_at() {
+    let mut app = App::default();
+
+    // init block time for predictable behavior
+    app.set_block(BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(0),
+        chain_id: "chain_id".to_string(),
+    });
+
+    let viewing_key = "unguessable".to_string();
+    let admin_user = Addr::unchecked("admin");
+    let staking_user = Addr::unchecked("staker");
+
+    let token = snip20::InstantiateMsg {
+        name: "stake_token".into(),
+        admin: Some(admin_user.to_string().clone()),
+        symbol: "STKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            amount: Uint128::new(300),
+            address: staking_user.to_string(),
+        }]),
+        query_auth: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(false),
+            enable_redeem: Some(false),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+            max_supply: None,
+        }),
+    }
+    .test_init(
+        Snip20::default(),
+        &mut app,
+        admin_user.clone(),
+        "stake_token",
+        &[],
+    )
+    .unwrap();
+
+    let admin_contract = init_admin_auth(&mut app, &admin_user);
+
+    let query_contract = query_auth::InstantiateMsg {
+        admin_auth: admin_contract.clone().into(),
+        prng_seed: to_binary("").ok().unwrap(),
+    }
+    .test_init(
+        QueryAuth::default(),
+        &mut app,
+        admin_user.clone(),
+        "query_auth",
+        &[],
+    )
+    .unwrap();
+
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
+        padding: None,
+    }
+    .test_exec(&query_contract, &mut app, staking_user.clone(), &[])
+    .unwrap();
+
+    let basic_staking = basic_staking::InstantiateMsg {
+        admin_auth: admin_contract.into(),
+        query_auth: query_contract.into(),
+        airdrop: None,
+        stake_token: token.clone().into(),
+        unbond_period: Uint128::new(100),
+        max_user_pools: Uint128::one(),
+        viewing_key: viewing_key.clone(),
+    }
+    .test_init(
+        BasicStaking::default(),
+        &mut app,
+        admin_user.clone(),
+        "basic_staking",
+        &[],
+    )
+    .unwrap();
+
+    // Stake 100 at t=10, 200 at t=20, unbond 50 at t=30
+    for (now, amount) in [(10, 100), (20, 200)] {
+        app.set_block(BlockInfo {
+            height: 1,
+            time: Timestamp::from_seconds(now),
+            chain_id: "chain_id".to_string(),
+        });
+
+        snip20::ExecuteMsg::Send {
+            recipient: basic_staking.address.to_string().clone(),
+            recipient_code_hash: None,
+            amount: Uint128::new(amount),
+            msg: Some(
+                to_binary(&basic_staking::Action::Stake {
+                    compound: None,
+                    airdrop_task: None,
+                })
+                .unwrap(),
+            ),
+            memo: None,
+            decoys: None,
+            entropy: None,
+            padding: None,
+        }
+        .test_exec(&token, &mut app, staking_user.clone(), &[])
+        .unwrap();
+    }
+
+    app.set_block(BlockInfo {
+        height: 1,
+        time: Timestamp::from_seconds(30),
+        chain_id: "chain_id".to_string(),
+    });
+
+    basic_staking::ExecuteMsg::Unbond {
+        amount: Uint128::new(50),
+        compound: None,
+        padding: None,
+    }
+    .test_exec(&basic_staking, &mut app, staking_user.clone(), &[])
+    .unwrap();
+
+    for (time, expected) in [(0, 0), (10, 100), (15, 100), (20, 300), (29, 300), (30, 250)] {
+        match (basic_staking::QueryMsg::StakedAt {
+            auth: basic_staking::Auth::ViewingKey {
+                key: viewing_key.clone(),
+                address: staking_user.clone().into(),
+            },
+            time: Uint128::new(time),
+        })
+        .test_query(&basic_staking, &app)
+        .unwrap()
+        {
+            basic_staking::QueryAnswer::StakedAt { user, amount } => {
+                assert_eq!(user, staking_user);
+                assert_eq!(amount, Uint128::new(expected), "user staked at {}", time);
+            }
+            _ => {
+                panic!("Staked at query failed");
+            }
+        };
+
+        match (basic_staking::QueryMsg::TotalStakedAt {
+            time: Uint128::new(time),
+        })
+        .test_query(&basic_staking, &app)
+        .unwrap()
+        {
+            basic_staking::QueryAnswer::TotalStakedAt { amount } => {
+                assert_eq!(amount, Uint128::new(expected), "total staked at {}", time);
+            }
+            _ => {
+                panic!("Total staked at query failed");
+            }
+        };
+    }
+}
@@ -68,6 +68,7 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -113,6 +114,8 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
     // set staking user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, staking_user.clone(), &[])
@@ -120,6 +123,8 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
     // set transfer user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, transfer_user.clone(), &[])
@@ -156,6 +161,8 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -174,6 +181,8 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -192,6 +201,8 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, transfer_user.clone(), &[])
@@ -53,6 +53,7 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -98,6 +99,8 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
     // set staking user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, receiving_user.clone(), &[])
@@ -106,6 +109,8 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
     // set transfer user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, sending_user.clone(), &[])
@@ -143,6 +148,8 @@ fn transfer_stake(stake_amount: Uint128, transfer_amount: Uint128) {
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, receiving_user.clone(), &[])
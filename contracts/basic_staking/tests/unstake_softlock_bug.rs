@@ -56,6 +56,7 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -93,6 +94,8 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
     // set staking user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, staking_user.clone(), &[])
@@ -101,6 +104,8 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
     // set reward user VK
     query_auth::ExecuteMsg::SetViewingKey {
         key: viewing_key.clone(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&query_contract, &mut app, reward_user.clone(), &[])
@@ -148,6 +153,8 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, reward_user.clone(), &[])
@@ -179,6 +186,8 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -280,6 +289,8 @@ fn unstake_softlock_bug(stake_amount: Uint128, unbond_period: Uint128, reward_am
             .unwrap(),
         ),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, staking_user.clone(), &[])
@@ -38,6 +38,7 @@ fn basic_scrt_staking_integration(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
         query_auth: None,
     }
@@ -98,6 +99,8 @@ fn basic_scrt_staking_integration(
             amount: deposit,
             msg: None,
             memo: None,
+            decoys: None,
+            entropy: None,
             padding: None,
         }
         .test_exec(&token, &mut app, admin.clone(), &[])
@@ -56,7 +56,7 @@ pub fn receive(
 
 pub fn try_update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     config: Config,
 ) -> StdResult<Response> {
@@ -64,6 +64,7 @@ pub fn try_update_config(
 
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::ScrtStakingAdmin,
         &info.sender,
         &cur_config.admin_auth,
@@ -81,7 +82,7 @@ pub fn try_update_config(
 
 pub fn unbond(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     asset: Addr,
     amount: Uint128,
@@ -94,6 +95,7 @@ pub fn unbond(
 
     if validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::ScrtStakingAdmin,
         &info.sender,
         &config.admin_auth,
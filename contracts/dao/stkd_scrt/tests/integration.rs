@@ -33,6 +33,7 @@ fn bonded_adapter_test(deposit: Uint128, rewards: Uint128, reserves: Uint128, ba
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
         query_auth: None,
     }
@@ -93,6 +94,8 @@ fn bonded_adapter_test(deposit: Uint128, rewards: Uint128, reserves: Uint128, ba
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -1,4 +1,4 @@
-use crate::{execute, query, storage::*};
+use crate::{execute, query, storage::*, stream};
 use shade_protocol::{
     c_std::{
         shd_entry_point,
@@ -81,6 +81,38 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             execute::set_run_level(deps, &env, info, run_level)
         }
         ExecuteMsg::WrapCoins {} => execute::wrap_coins(deps, &env, info),
+        ExecuteMsg::SetOracle {
+            oracle,
+            max_price_age,
+        } => {
+            let oracle = match oracle {
+                Some(o) => Some(o.into_valid(deps.api)?),
+                None => None,
+            };
+            execute::set_oracle(deps, &env, info, oracle, max_price_age)
+        }
+        ExecuteMsg::SetPriceKey { asset, key } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            execute::set_price_key(deps, &env, info, asset, key)
+        }
+        ExecuteMsg::CreateStream {
+            recipient,
+            asset,
+            amount,
+            cycle,
+            start,
+            end,
+        } => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            let asset = deps.api.addr_validate(&asset)?;
+            stream::create_stream(
+                deps, &env, info, recipient, asset, amount, cycle, start, end,
+            )
+        }
+        ExecuteMsg::WithdrawStream { id } => stream::withdraw_stream(deps, &env, info, id),
+        ExecuteMsg::PauseStream { id } => stream::pause_stream(deps, &env, info, id),
+        ExecuteMsg::ResumeStream { id } => stream::resume_stream(deps, &env, info, id),
+        ExecuteMsg::CancelStream { id } => stream::cancel_stream(deps, &env, info, id),
     }
 }
 
@@ -124,5 +156,20 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             let asset = deps.api.addr_validate(&asset)?;
             to_binary(&query::reserves(deps, env, asset)?)
         }
+        QueryMsg::Portfolio {} => to_binary(&query::portfolio(deps, env)?),
+        QueryMsg::Stream { id } => to_binary(&query::stream(deps, env, id)?),
+        QueryMsg::Streams { asset } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            to_binary(&query::streams(deps, asset)?)
+        }
+        QueryMsg::PeriodReport {
+            asset,
+            date,
+            epoch,
+            period,
+        } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            to_binary(&query::period_report(deps, env, asset, date, epoch, period)?)
+        }
     }
 }
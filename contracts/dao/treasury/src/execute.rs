@@ -1,4 +1,4 @@
-use crate::storage::*;
+use crate::{query::portfolio_values, storage::*, stream};
 use shade_protocol::{
     c_std::{
         to_binary,
@@ -47,7 +47,17 @@ use shade_protocol::{
 };
 use std::collections::HashMap;
 
-const ONE_HUNDRED_PERCENT: Uint128 = Uint128::new(10u128.pow(18u32));
+pub const ONE_HUNDRED_PERCENT: Uint128 = Uint128::new(10u128.pow(18u32));
+
+/// Order allowances are rebalanced in, amounts & portfolio shares are taken from the
+/// total balance before portions of what remains are calculated
+fn allowance_order(allowance_type: &AllowanceType) -> u8 {
+    match allowance_type {
+        AllowanceType::Amount => 0,
+        AllowanceType::Portfolio => 1,
+        AllowanceType::Portion => 2,
+    }
+}
 
 pub fn receive(
     deps: DepsMut,
@@ -77,7 +87,7 @@ pub fn receive(
 
 pub fn try_update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     admin_auth: Option<RawContract>,
     multisig: Option<String>,
@@ -86,6 +96,7 @@ pub fn try_update_config(
 
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::TreasuryAdmin,
         &info.sender,
         &config.admin_auth,
@@ -114,6 +125,7 @@ pub fn update(deps: DepsMut, env: &Env, info: MessageInfo, asset: Addr) -> StdRe
             let config = CONFIG.load(deps.storage)?;
             validate_admin(
                 &deps.querier,
+                env,
                 AdminPermissions::TreasuryAdmin,
                 &info.sender,
                 &config.admin_auth,
@@ -138,13 +150,19 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
     };
 
     let mut allowances = ALLOWANCES.load(deps.storage, asset.clone())?;
+    // lists saved before portfolio allowances existed may not be in order
+    allowances.sort_by_key(|a| allowance_order(&a.allowance_type));
+
+    // funds owed to streams are left in reserves
+    let committed = stream::committed(deps.storage, &asset, env.block.time.seconds())?;
 
     let mut total_balance = balance_query(
         &deps.querier,
         env.contract.address.clone(),
         viewing_key.clone(),
         &full_asset.contract.clone(),
-    )?;
+    )?
+    .saturating_sub(committed);
     let mut token_balance = total_balance;
 
     // { spender: (balance, allowance) }
@@ -230,8 +248,25 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
         total_balance += balance + unbonding;
     }
 
+    // (portfolio value, asset price), only queried when an allowance targets the portfolio
+    let portfolio = if allowances
+        .iter()
+        .any(|a| a.allowance_type == AllowanceType::Portfolio)
+    {
+        let (total, values) = portfolio_values(deps.as_ref(), env)?;
+        match values.into_iter().find(|v| v.asset == asset) {
+            Some(v) if !v.price.is_zero() => Some((total, v.price)),
+            _ => {
+                return Err(StdError::generic_err("Asset has no portfolio price"));
+            }
+        }
+    } else {
+        None
+    };
+    let unit = Uint128::new(10u128.pow(full_asset.token_info.decimals as u32));
+
     /* Amounts given priority sice the array is sorted
-     * portions are calculated after amounts are taken from total
+     * portions are calculated after amounts & portfolio shares are taken from total
      */
     for (i, allowance) in allowances.clone().iter().enumerate() {
         let last_refresh = parse_utc_datetime(&allowance.last_refresh)?;
@@ -270,13 +305,31 @@ fn rebalance(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdRe
                 // This just gives a ratio of total balance where allowance.amount is the percent
                 total_balance.multiply_ratio(allowance.amount, ONE_HUNDRED_PERCENT)
             }
+            AllowanceType::Portfolio => {
+                // share of portfolio value converted back to tokens
+                let amount = match portfolio {
+                    Some((value, price)) => value
+                        .multiply_ratio(allowance.amount, ONE_HUNDRED_PERCENT)
+                        .multiply_ratio(unit, price),
+                    None => Uint128::zero(),
+                };
+                // like amounts, portfolio shares are not used in the portion calculation
+                total_balance = total_balance.saturating_sub(amount);
+                amount
+            }
         };
 
         let (balance, cur_allowance) = metadata[&allowance.spender];
         let total = balance + cur_allowance;
 
         // calculate threshold
-        let threshold = desired_amount.multiply_ratio(allowance.tolerance, ONE_HUNDRED_PERCENT);
+        let threshold = match (&allowance.allowance_type, portfolio) {
+            // portfolio tolerance is a share of portfolio value rather than of the target
+            (AllowanceType::Portfolio, Some((value, price))) => value
+                .multiply_ratio(allowance.tolerance, ONE_HUNDRED_PERCENT)
+                .multiply_ratio(unit, price),
+            _ => desired_amount.multiply_ratio(allowance.tolerance, ONE_HUNDRED_PERCENT),
+        };
 
         match desired_amount.cmp(&total) {
             // Decrease Allowance
@@ -539,7 +592,7 @@ pub fn migrate(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> Std
 
 pub fn set_run_level(
     deps: DepsMut,
-    _env: &Env,
+    env: &Env,
     info: MessageInfo,
     run_level: RunLevel,
 ) -> StdResult<Response> {
@@ -548,6 +601,7 @@ pub fn set_run_level(
     // TODO force super-admin?
     validate_admin(
         &deps.querier,
+        env,
         AdminPermissions::TreasuryAdmin,
         &info.sender,
         &config.admin_auth,
@@ -558,6 +612,72 @@ pub fn set_run_level(
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RunLevel { run_level })?))
 }
 
+pub fn set_oracle(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    oracle: Option<Contract>,
+    max_price_age: Option<u64>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    validate_admin(
+        &deps.querier,
+        env,
+        AdminPermissions::TreasuryAdmin,
+        &info.sender,
+        &config.admin_auth,
+    )?;
+
+    match oracle {
+        Some(o) => ORACLE.save(deps.storage, &o)?,
+        None => ORACLE.remove(deps.storage),
+    }
+    match max_price_age {
+        Some(0) => {
+            return Err(StdError::generic_err("Max price age cannot be zero"));
+        }
+        Some(age) => PRICE_MAX_AGE.save(deps.storage, &age)?,
+        None => PRICE_MAX_AGE.remove(deps.storage),
+    }
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetOracle {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+pub fn set_price_key(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    asset: Addr,
+    key: String,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    validate_admin(
+        &deps.querier,
+        env,
+        AdminPermissions::TreasuryAdmin,
+        &info.sender,
+        &config.admin_auth,
+    )?;
+
+    if ASSET.may_load(deps.storage, asset.clone())?.is_none() {
+        return Err(StdError::generic_err("Not a registered asset"));
+    }
+
+    PRICE_KEY.save(deps.storage, asset, &key)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetPriceKey {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
 pub fn try_register_asset(
     deps: DepsMut,
     env: &Env,
@@ -568,6 +688,7 @@ pub fn try_register_asset(
 
     validate_admin(
         &deps.querier,
+        env,
         AdminPermissions::TreasuryAdmin,
         &info.sender,
         &config.admin_auth,
@@ -601,7 +722,7 @@ pub fn try_register_asset(
 
 pub fn register_wrap(
     deps: DepsMut,
-    _env: &Env,
+    env: &Env,
     info: MessageInfo,
     denom: String,
     contract: &Contract,
@@ -610,6 +731,7 @@ pub fn register_wrap(
 
     validate_admin(
         &deps.querier,
+        env,
         AdminPermissions::TreasuryAdmin,
         &info.sender,
         &config.admin_auth,
@@ -639,7 +761,7 @@ pub fn register_wrap(
 
 pub fn register_manager(
     deps: DepsMut,
-    _env: &Env,
+    env: &Env,
     info: MessageInfo,
     contract: &mut Contract,
 ) -> StdResult<Response> {
@@ -647,6 +769,7 @@ pub fn register_manager(
 
     validate_admin(
         &deps.querier,
+        env,
         AdminPermissions::TreasuryAdmin,
         &info.sender,
         &config.admin_auth,
@@ -668,7 +791,7 @@ pub fn register_manager(
 
 pub fn allowance(
     deps: DepsMut,
-    _env: &Env,
+    env: &Env,
     info: MessageInfo,
     asset: Addr,
     allowance: Allowance,
@@ -678,6 +801,7 @@ pub fn allowance(
 
     validate_admin(
         &deps.querier,
+        env,
         AdminPermissions::TreasuryAdmin,
         &info.sender,
         &config.admin_auth,
@@ -743,17 +867,37 @@ pub fn allowance(
         ));
     }
 
+    if allowance.allowance_type == AllowanceType::Portfolio {
+        if ORACLE.may_load(deps.storage)?.is_none() {
+            return Err(StdError::generic_err("Portfolio oracle not set"));
+        }
+        if PRICE_KEY.may_load(deps.storage, asset.clone())?.is_none() {
+            return Err(StdError::generic_err("Asset has no price key"));
+        }
+
+        // portfolio weights are shared across all assets
+        let mut total = Uint128::zero();
+        for a in ASSET_LIST.iter(deps.storage) {
+            let list = if a == asset {
+                allowances.clone()
+            } else {
+                ALLOWANCES.may_load(deps.storage, a)?.unwrap_or(vec![])
+            };
+            for meta in list {
+                if meta.allowance_type == AllowanceType::Portfolio {
+                    total += meta.amount;
+                }
+            }
+        }
+        if total > ONE_HUNDRED_PERCENT {
+            return Err(StdError::generic_err(
+                "Invalid portfolio allowance total exceeding 100%",
+            ));
+        }
+    }
+
     // Sort list before going into storage
-    allowances.sort_by(|a, b| match a.allowance_type {
-        AllowanceType::Amount => match b.allowance_type {
-            AllowanceType::Amount => std::cmp::Ordering::Equal,
-            AllowanceType::Portion => std::cmp::Ordering::Less,
-        },
-        AllowanceType::Portion => match b.allowance_type {
-            AllowanceType::Amount => std::cmp::Ordering::Greater,
-            AllowanceType::Portion => std::cmp::Ordering::Equal,
-        },
-    });
+    allowances.sort_by_key(|a| allowance_order(&a.allowance_type));
 
     ALLOWANCES.save(deps.storage, asset, &allowances)?;
 
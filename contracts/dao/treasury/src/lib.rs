@@ -2,3 +2,4 @@ pub mod contract;
 pub mod execute;
 pub mod query;
 pub mod storage;
+pub mod stream;
@@ -1,11 +1,17 @@
-use crate::storage::*;
+use crate::{execute::ONE_HUNDRED_PERCENT, storage::*, stream::{committed, withdrawable}};
 use shade_protocol::{
     c_std::{Addr, Deps, Env, StdError, StdResult, Uint128},
-    contract_interfaces::dao::{adapter, manager, treasury},
+    contract_interfaces::{
+        dao::{adapter, manager, treasury},
+        shade_oracles::query_prices,
+    },
     snip20::helpers::{allowance_query, balance_query},
-    utils::{asset::Contract, cycle::parse_utc_datetime, storage::plus::period_storage::Period},
+    utils::{
+        asset::Contract,
+        storage::plus::period_storage::{period_key, Period},
+    },
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 pub fn config(deps: Deps) -> StdResult<treasury::QueryAnswer> {
     Ok(treasury::QueryAnswer::Config {
@@ -20,23 +26,29 @@ pub fn metrics(
     epoch: Option<Uint128>,
     period: Period,
 ) -> StdResult<treasury::QueryAnswer> {
-    if date.is_some() && epoch.is_some() {
-        return Err(StdError::generic_err("cannot pass both epoch and date"));
-    }
-    let key = {
-        if let Some(d) = date {
-            parse_utc_datetime(&d)?.timestamp() as u64
-        } else if let Some(e) = epoch {
-            e.u128() as u64
-        } else {
-            env.block.time.seconds()
-        }
-    };
+    let key = period_key(env.block.time.seconds(), date, epoch)?;
     Ok(treasury::QueryAnswer::Metrics {
         metrics: METRICS.load_period(deps.storage, key, period)?,
     })
 }
 
+pub fn period_report(
+    deps: Deps,
+    env: Env,
+    asset: Addr,
+    date: Option<String>,
+    epoch: Option<Uint128>,
+    period: Period,
+) -> StdResult<treasury::QueryAnswer> {
+    let key = period_key(env.block.time.seconds(), date, epoch)?;
+
+    let totals = METRICS.period_totals(deps.storage, key, period, |m| {
+        (m.token == asset).then(|| (m.action, m.context, m.amount))
+    })?;
+
+    Ok(treasury::QueryAnswer::PeriodReport { totals })
+}
+
 pub fn batch_balance(deps: Deps, env: Env, assets: Vec<Addr>) -> StdResult<Vec<Uint128>> {
     let mut balances = vec![];
     let mut managers: HashSet<Contract> = HashSet::new();
@@ -171,3 +183,135 @@ pub fn allowances(deps: Deps, asset: Addr) -> StdResult<treasury::QueryAnswer> {
         allowances: ALLOWANCES.may_load(deps.storage, asset)?.unwrap_or(vec![]),
     })
 }
+
+pub fn stream(deps: Deps, env: Env, id: u64) -> StdResult<treasury::QueryAnswer> {
+    let stream = match STREAMS.may_load(deps.storage, id)? {
+        Some(s) => s,
+        None => {
+            return Err(StdError::generic_err(format!("Stream {} not found", id)));
+        }
+    };
+    Ok(treasury::QueryAnswer::Stream {
+        withdrawable: withdrawable(&stream, env.block.time.seconds())?,
+        stream,
+    })
+}
+
+pub fn streams(deps: Deps, asset: Addr) -> StdResult<treasury::QueryAnswer> {
+    let mut streams = vec![];
+    for id in ASSET_STREAMS
+        .may_load(deps.storage, asset)?
+        .unwrap_or(vec![])
+    {
+        streams.push(STREAMS.load(deps.storage, id)?);
+    }
+    Ok(treasury::QueryAnswer::Streams { streams })
+}
+
+pub fn portfolio(deps: Deps, env: Env) -> StdResult<treasury::QueryAnswer> {
+    let (total, assets) = portfolio_values(deps, &env)?;
+    Ok(treasury::QueryAnswer::Portfolio { total, assets })
+}
+
+/// USD valuation of every registered asset that has a price key,
+/// holdings include what managers hold & are unbonding on the treasury's behalf,
+/// less what is committed to streams
+pub fn portfolio_values(deps: Deps, env: &Env) -> StdResult<(Uint128, Vec<treasury::AssetValue>)> {
+    let oracle = match ORACLE.may_load(deps.storage)? {
+        Some(o) => o,
+        None => {
+            return Err(StdError::generic_err("Portfolio oracle not set"));
+        }
+    };
+
+    let mut priced = vec![];
+    for asset in ASSET_LIST.iter(deps.storage) {
+        if let Some(key) = PRICE_KEY.may_load(deps.storage, asset.clone())? {
+            priced.push((asset, key));
+        }
+    }
+
+    if priced.is_empty() {
+        return Ok((Uint128::zero(), vec![]));
+    }
+
+    let keys = priced.iter().map(|(_, key)| key.clone()).collect();
+    let max_age = PRICE_MAX_AGE
+        .may_load(deps.storage)?
+        .unwrap_or(DEFAULT_PRICE_MAX_AGE);
+    let oldest = env.block.time.seconds().saturating_sub(max_age);
+
+    let mut prices: HashMap<String, Uint128> = HashMap::new();
+    for price in query_prices(&oracle, &deps.querier, keys)? {
+        if price.data.last_updated_base < oldest || price.data.last_updated_quote < oldest {
+            return Err(StdError::generic_err(format!(
+                "Price for {} is stale",
+                price.key
+            )));
+        }
+        prices.insert(price.key, price.data.rate);
+    }
+
+    let viewing_key = VIEWING_KEY.load(deps.storage)?;
+    let mut total = Uint128::zero();
+    let mut values = vec![];
+
+    for (asset, key) in priced {
+        let full_asset = ASSET.load(deps.storage, asset.clone())?;
+        let price = match prices.get(&key) {
+            Some(p) => *p,
+            None => {
+                return Err(StdError::generic_err(format!("No price for {}", key)));
+            }
+        };
+
+        let mut amount = balance_query(
+            &deps.querier,
+            env.contract.address.clone(),
+            viewing_key.clone(),
+            &full_asset.contract.clone(),
+        )?;
+
+        for allowance in ALLOWANCES
+            .may_load(deps.storage, asset.clone())?
+            .unwrap_or(vec![])
+        {
+            if let Some(m) = MANAGER.may_load(deps.storage, allowance.spender)? {
+                amount += manager::balance_query(
+                    deps.querier,
+                    &asset.clone(),
+                    env.contract.address.clone(),
+                    m.clone(),
+                )?;
+                amount += manager::unbonding_query(
+                    deps.querier,
+                    &asset.clone(),
+                    env.contract.address.clone(),
+                    m,
+                )?;
+            }
+        }
+        amount = amount.saturating_sub(committed(deps.storage, &asset, env.block.time.seconds())?);
+
+        // price is per whole token
+        let value = amount.multiply_ratio(price, 10u128.pow(full_asset.token_info.decimals as u32));
+        total += value;
+
+        values.push(treasury::AssetValue {
+            asset,
+            key,
+            price,
+            amount,
+            value,
+            weight: Uint128::zero(),
+        });
+    }
+
+    if !total.is_zero() {
+        for v in values.iter_mut() {
+            v.weight = v.value.multiply_ratio(ONE_HUNDRED_PERCENT, total);
+        }
+    }
+
+    Ok((total, values))
+}
@@ -1,6 +1,6 @@
 use shade_protocol::{
     c_std::Addr,
-    dao::treasury::{AllowanceMeta, Config, Metric, RunLevel},
+    dao::treasury::{AllowanceMeta, Config, Metric, RunLevel, Stream},
     secret_storage_plus::{Item, Map},
     snip20::helpers::Snip20Asset,
     utils::{
@@ -21,6 +21,18 @@ pub const WRAP: Map<String, Addr> = Map::new("wrap");
 pub const MANAGER: Map<Addr, Contract> = Map::new("managers");
 pub const ALLOWANCES: Map<Addr, Vec<AllowanceMeta>> = Map::new("allowances");
 
+// Shade oracle router & { asset: price key } used for portfolio valuation
+pub const ORACLE: Item<Contract> = Item::new("oracle");
+pub const PRICE_KEY: Map<Addr, String> = Map::new("price_key");
+// Seconds before an oracle price is considered stale
+pub const PRICE_MAX_AGE: Item<u64> = Item::new("price_max_age");
+pub const DEFAULT_PRICE_MAX_AGE: u64 = 3600;
+
+pub const STREAM_ID: Item<u64> = Item::new("stream_id");
+pub const STREAMS: Map<u64, Stream> = Map::new("streams");
+// { asset: [stream id] }
+pub const ASSET_STREAMS: Map<Addr, Vec<u64>> = Map::new("asset_streams");
+
 pub const RUN_LEVEL: Item<RunLevel> = Item::new("runlevel");
 
 pub const METRICS: PeriodStorage<Metric> =
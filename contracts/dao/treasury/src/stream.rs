@@ -0,0 +1,327 @@
+use crate::storage::*;
+use shade_protocol::{
+    c_std::{
+        to_binary,
+        Addr,
+        CosmosMsg,
+        DepsMut,
+        Env,
+        MessageInfo,
+        Response,
+        StdError,
+        StdResult,
+        Storage,
+        Uint128,
+    },
+    chrono::prelude::*,
+    contract_interfaces::{
+        admin::helpers::{validate_admin, AdminPermissions},
+        dao::treasury::{Action, Context, ExecuteAnswer, Metric, RunLevel, Stream},
+    },
+    snip20::helpers::send_msg,
+    utils::{
+        cycle::{utc_from_seconds, Cycle},
+        generic_response::ResponseStatus,
+    },
+};
+
+/// Whole calendar months between start & until, a month completes on the same day & time
+fn calendar_months(start: i64, until: i64) -> u128 {
+    let (start, until) = (utc_from_seconds(start), utc_from_seconds(until));
+    let mut months =
+        (until.year() - start.year()) as i64 * 12 + until.month() as i64 - start.month() as i64;
+    if (until.day(), until.time()) < (start.day(), start.time()) {
+        months -= 1;
+    }
+    months.max(0) as u128
+}
+
+/// Number of completed cycles between start & until
+fn periods(start: u64, until: u64, cycle: &Cycle) -> u128 {
+    if until < start {
+        return 0;
+    }
+    let elapsed = (until - start) as u128;
+    match cycle {
+        Cycle::Once => 1,
+        Cycle::Constant => 0,
+        Cycle::Seconds { seconds } => elapsed / seconds.u128(),
+        Cycle::Minutes { minutes } => elapsed / minutes.u128().saturating_mul(60),
+        Cycle::Hourly { hours } => elapsed / hours.u128().saturating_mul(60 * 60),
+        Cycle::Daily { days } => elapsed / days.u128().saturating_mul(60 * 60 * 24),
+        Cycle::Monthly { months } => calendar_months(start as i64, until as i64) / months.u128(),
+        Cycle::Yearly { years } => {
+            calendar_months(start as i64, until as i64) / years.u128().saturating_mul(12)
+        }
+    }
+}
+
+fn valid_cycle(cycle: &Cycle) -> bool {
+    match cycle {
+        Cycle::Once => true,
+        Cycle::Constant => false,
+        Cycle::Seconds { seconds } => !seconds.is_zero(),
+        Cycle::Minutes { minutes } => !minutes.is_zero(),
+        Cycle::Hourly { hours } => !hours.is_zero(),
+        Cycle::Daily { days } => !days.is_zero(),
+        Cycle::Monthly { months } => !months.is_zero(),
+        Cycle::Yearly { years } => !years.is_zero(),
+    }
+}
+
+/// Total paid out by the stream for cycles completed before until
+fn accrued_until(stream: &Stream, until: u64) -> StdResult<Uint128> {
+    let until = match stream.end {
+        Some(end) => until.min(end),
+        None => until,
+    };
+    Ok(stream
+        .amount
+        .checked_mul(Uint128::new(periods(stream.start, until, &stream.cycle)))?)
+}
+
+/// Accrued but not yet withdrawn, nothing accrues while paused
+pub fn withdrawable(stream: &Stream, now: u64) -> StdResult<Uint128> {
+    let until = stream.paused_at.unwrap_or(now);
+    Ok(accrued_until(stream, until)?.saturating_sub(stream.paid))
+}
+
+/// Funds owed to an asset's streams, which rebalancing must leave in reserves.
+/// Streams commit what has accrued plus the next cycle, bounded streams never more than is
+/// left until end, so reserves are topped up as long as the asset is updated every cycle
+pub fn committed(storage: &dyn Storage, asset: &Addr, now: u64) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    for id in ASSET_STREAMS
+        .may_load(storage, asset.clone())?
+        .unwrap_or(vec![])
+    {
+        let stream = STREAMS.load(storage, id)?;
+        let next = withdrawable(&stream, now)? + stream.amount;
+        total += match stream.end {
+            Some(end) => next.min(accrued_until(&stream, end)?.saturating_sub(stream.paid)),
+            None => next,
+        };
+    }
+    Ok(total)
+}
+
+fn load_stream(storage: &dyn Storage, id: u64) -> StdResult<Stream> {
+    match STREAMS.may_load(storage, id)? {
+        Some(s) => Ok(s),
+        None => Err(StdError::generic_err(format!("Stream {} not found", id))),
+    }
+}
+
+fn remove_stream(storage: &mut dyn Storage, stream: &Stream) -> StdResult<()> {
+    STREAMS.remove(storage, stream.id);
+    let mut ids = ASSET_STREAMS.load(storage, stream.asset.clone())?;
+    ids.retain(|id| *id != stream.id);
+    ASSET_STREAMS.save(storage, stream.asset.clone(), &ids)
+}
+
+/// Sends amount to the stream recipient and records the payout
+fn payout(deps: &mut DepsMut, env: &Env, stream: &Stream, amount: Uint128) -> StdResult<CosmosMsg> {
+    let full_asset = ASSET.load(deps.storage, stream.asset.clone())?;
+
+    METRICS.push(deps.storage, env.block.time, Metric {
+        action: Action::StreamPayout,
+        context: Context::Stream,
+        timestamp: env.block.time.seconds(),
+        token: stream.asset.clone(),
+        amount,
+        user: stream.recipient.clone(),
+    })?;
+
+    send_msg(
+        stream.recipient.clone(),
+        amount,
+        None,
+        None,
+        None,
+        &full_asset.contract,
+    )
+}
+
+fn validate_treasury_admin(deps: &DepsMut, env: &Env, info: &MessageInfo) -> StdResult<()> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_admin(
+        &deps.querier,
+        env,
+        AdminPermissions::TreasuryAdmin,
+        &info.sender,
+        &config.admin_auth,
+    )
+}
+
+pub fn create_stream(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    recipient: Addr,
+    asset: Addr,
+    amount: Uint128,
+    cycle: Cycle,
+    start: Option<u64>,
+    end: Option<u64>,
+) -> StdResult<Response> {
+    validate_treasury_admin(&deps, env, &info)?;
+
+    if ASSET.may_load(deps.storage, asset.clone())?.is_none() {
+        return Err(StdError::generic_err("Not a registered asset"));
+    }
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Stream amount cannot be zero"));
+    }
+    if !valid_cycle(&cycle) {
+        return Err(StdError::generic_err("Invalid stream cycle"));
+    }
+
+    let start = start.unwrap_or(env.block.time.seconds());
+    if let Some(end) = end {
+        if end <= start {
+            return Err(StdError::generic_err("Stream end must be after start"));
+        }
+    }
+
+    let id = STREAM_ID.may_load(deps.storage)?.unwrap_or(0);
+    STREAM_ID.save(deps.storage, &(id + 1))?;
+
+    STREAMS.save(deps.storage, id, &Stream {
+        id,
+        recipient,
+        asset: asset.clone(),
+        amount,
+        cycle,
+        start,
+        end,
+        paid: Uint128::zero(),
+        paused_at: None,
+    })?;
+
+    let mut ids = ASSET_STREAMS
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+    ids.push(id);
+    ASSET_STREAMS.save(deps.storage, asset, &ids)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::CreateStream {
+            status: ResponseStatus::Success,
+            id,
+        })?),
+    )
+}
+
+pub fn withdraw_stream(
+    mut deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    id: u64,
+) -> StdResult<Response> {
+    if RUN_LEVEL.load(deps.storage)? == RunLevel::Deactivated {
+        return Err(StdError::generic_err("Contract Deactivated"));
+    }
+
+    let mut stream = load_stream(deps.storage, id)?;
+    if stream.recipient != info.sender {
+        return Err(StdError::generic_err("Only the recipient can withdraw"));
+    }
+
+    let now = env.block.time.seconds();
+    let amount = withdrawable(&stream, now)?;
+    if amount.is_zero() {
+        return Err(StdError::generic_err("Nothing to withdraw"));
+    }
+    stream.paid += amount;
+
+    // streams are dropped once everything owed has been paid, paused or not
+    let finished = match (&stream.cycle, stream.end) {
+        (Cycle::Once, _) => true,
+        (_, Some(end)) => stream.paid >= accrued_until(&stream, end)?,
+        _ => false,
+    };
+    if finished {
+        remove_stream(deps.storage, &stream)?;
+    } else {
+        STREAMS.save(deps.storage, id, &stream)?;
+    }
+
+    let msg = payout(&mut deps, env, &stream, amount)?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .set_data(to_binary(&ExecuteAnswer::WithdrawStream {
+            status: ResponseStatus::Success,
+            amount,
+        })?))
+}
+
+pub fn pause_stream(deps: DepsMut, env: &Env, info: MessageInfo, id: u64) -> StdResult<Response> {
+    validate_treasury_admin(&deps, env, &info)?;
+
+    let mut stream = load_stream(deps.storage, id)?;
+    if stream.paused_at.is_some() {
+        return Err(StdError::generic_err("Stream already paused"));
+    }
+    stream.paused_at = Some(env.block.time.seconds());
+    STREAMS.save(deps.storage, id, &stream)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::PauseStream {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+pub fn resume_stream(deps: DepsMut, env: &Env, info: MessageInfo, id: u64) -> StdResult<Response> {
+    validate_treasury_admin(&deps, env, &info)?;
+
+    let mut stream = load_stream(deps.storage, id)?;
+    let paused_at = match stream.paused_at {
+        Some(p) => p,
+        None => {
+            return Err(StdError::generic_err("Stream is not paused"));
+        }
+    };
+
+    // shift the schedule so the paused time is not paid for, end is left as is
+    let now = env.block.time.seconds();
+    if paused_at > stream.start {
+        stream.start += now - paused_at;
+    } else if now > stream.start {
+        stream.start = now;
+    }
+    stream.paused_at = None;
+    STREAMS.save(deps.storage, id, &stream)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::ResumeStream {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+pub fn cancel_stream(
+    mut deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    id: u64,
+) -> StdResult<Response> {
+    validate_treasury_admin(&deps, env, &info)?;
+
+    let stream = load_stream(deps.storage, id)?;
+    let amount = withdrawable(&stream, env.block.time.seconds())?;
+    remove_stream(deps.storage, &stream)?;
+
+    let mut messages = vec![];
+    if !amount.is_zero() {
+        messages.push(payout(&mut deps, env, &stream, amount)?);
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .set_data(to_binary(&ExecuteAnswer::CancelStream {
+            status: ResponseStatus::Success,
+            amount,
+        })?))
+}
@@ -119,6 +119,8 @@ pub fn dao_int_test(
                 alloc_type[i][j].clone(),
                 Uint128::zero(),
                 alloc_tolerance[i][j].clone(),
+                0,
+                0,
                 i,
             )
             .unwrap();
@@ -63,6 +63,7 @@ fn allowance_cycle(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
         query_auth: None,
     }
@@ -117,6 +118,8 @@ fn allowance_cycle(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -163,6 +166,8 @@ fn allowance_cycle(
         amount: removed,
         memo: None,
         msg: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
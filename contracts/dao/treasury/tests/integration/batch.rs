@@ -45,6 +45,7 @@ fn batch_balance_test(amounts: Vec<Uint128>) {
                 enable_mint: Some(false),
                 enable_burn: Some(false),
                 enable_transfer: Some(true),
+                max_supply: None,
             }),
             query_auth: None,
         }
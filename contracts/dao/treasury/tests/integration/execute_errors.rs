@@ -242,3 +242,66 @@ pub fn admin_errors() {
         .is_ok()
     );
 }
+
+#[test]
+pub fn portfolio_errors() {
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1500),
+        "SSCRT",
+        vec![AllowanceType::Amount, AllowanceType::Portion],
+        vec![Cycle::Constant; 2],
+        vec![Uint128::new(200), Uint128::new(5 * 10u128.pow(17))],
+        vec![Uint128::zero(); 2],
+        vec![vec![AllocationType::Portion]; 2],
+        vec![vec![Uint128::new(10u128.pow(18))]; 2],
+        vec![vec![Uint128::zero()]; 2],
+        true,
+        true,
+    )
+    .unwrap();
+    let portfolio = |app: &mut App, manager: usize, amount: u128| {
+        treasury::allowance_exec(
+            app,
+            "admin",
+            &contracts,
+            "SSCRT",
+            manager,
+            AllowanceType::Portfolio,
+            Cycle::Constant,
+            Uint128::new(amount),
+            Uint128::zero(),
+            true,
+        )
+    };
+    let oracle = RawContract {
+        address: "oracle".to_string(),
+        code_hash: "code_hash".to_string(),
+    };
+    // no oracle configured
+    assert!(portfolio(&mut app, 0, 6 * 10u128.pow(17)).is_err());
+    assert!(
+        treasury::set_oracle_exec(&mut app, "not_admin", &contracts, Some(oracle.clone()), None)
+            .is_err()
+    );
+    assert!(
+        treasury::set_oracle_exec(&mut app, "admin", &contracts, Some(oracle.clone()), Some(0))
+            .is_err()
+    );
+    treasury::set_oracle_exec(&mut app, "admin", &contracts, Some(oracle), None).unwrap();
+    // no price key for the asset
+    assert!(portfolio(&mut app, 0, 6 * 10u128.pow(17)).is_err());
+    assert!(
+        treasury::set_price_key_exec(&mut app, "not_admin", &contracts, "SSCRT", "SCRT").is_err()
+    );
+    treasury::set_price_key_exec(&mut app, "admin", &contracts, "SSCRT", "SCRT").unwrap();
+    portfolio(&mut app, 0, 6 * 10u128.pow(17)).unwrap();
+    // portfolio weights cannot exceed 100%
+    assert!(portfolio(&mut app, 1, 5 * 10u128.pow(17)).is_err());
+    // oracle is unreachable so valuation fails
+    assert!(treasury::update_exec(&mut app, "admin", &contracts, "SSCRT").is_err());
+}
@@ -5,8 +5,10 @@ pub mod config;
 pub mod execute_errors;
 pub mod migration;
 pub mod non_manager_allowances;
+pub mod portfolio;
 pub mod query;
 pub mod scrt_staking;
+pub mod stream;
 pub mod tolerance;
 pub mod treasury;
 pub mod wrap;
@@ -0,0 +1,346 @@
+use shade_multi_test::multi::{admin::init_admin_auth, snip20::Snip20, treasury::Treasury};
+use shade_protocol::{
+    c_std::{
+        from_slice,
+        to_binary,
+        to_vec,
+        Addr,
+        Binary,
+        ContractInfo,
+        Deps,
+        DepsMut,
+        Empty,
+        Env,
+        MessageInfo,
+        Response,
+        StdResult,
+        Uint128,
+    },
+    contract_interfaces::{
+        dao::{treasury, treasury::AllowanceType},
+        shade_oracles::{
+            OraclePrice,
+            OracleResponse,
+            PriceResponse,
+            PricesResponse,
+            ReferenceData,
+        },
+        snip20,
+    },
+    multi_test::{App, Contract as MultiContract, ContractWrapper},
+    serde::{Deserialize, Serialize},
+    utils::{
+        asset::Contract,
+        cycle::Cycle,
+        ExecuteCallback,
+        InstantiateCallback,
+        MultiTestable,
+        Query,
+    },
+};
+
+/// Serves as both the oracle router & the oracle, prices are set directly
+struct MockOracle;
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "shade_protocol::serde", rename_all = "snake_case")]
+struct MockInit {}
+
+impl InstantiateCallback for MockInit {
+    const BLOCK_SIZE: usize = 256;
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "shade_protocol::serde", rename_all = "snake_case")]
+enum MockExecute {
+    SetPrice { key: String, data: ReferenceData },
+}
+
+impl ExecuteCallback for MockExecute {
+    const BLOCK_SIZE: usize = 256;
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "shade_protocol::serde", rename_all = "snake_case")]
+enum MockQuery {
+    GetOracle { key: String },
+    GetOracles { keys: Vec<String> },
+    GetPrice { key: String },
+    GetPrices { keys: Vec<String> },
+}
+
+fn mock_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: MockInit,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn mock_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: MockExecute,
+) -> StdResult<Response> {
+    match msg {
+        MockExecute::SetPrice { key, data } => deps.storage.set(key.as_bytes(), &to_vec(&data)?),
+    }
+    Ok(Response::new())
+}
+
+fn mock_price(deps: Deps, key: String) -> StdResult<OraclePrice> {
+    let data = match deps.storage.get(key.as_bytes()) {
+        Some(data) => from_slice(&data)?,
+        None => ReferenceData::default(),
+    };
+    Ok(OraclePrice { key, data })
+}
+
+fn mock_query(deps: Deps, env: Env, msg: MockQuery) -> StdResult<Binary> {
+    let oracle = Contract {
+        address: env.contract.address.clone(),
+        code_hash: env.contract.code_hash.clone(),
+    };
+    match msg {
+        MockQuery::GetOracle { key } => to_binary(&OracleResponse { key, oracle }),
+        MockQuery::GetOracles { keys } => to_binary(
+            &keys
+                .into_iter()
+                .map(|key| OracleResponse {
+                    key,
+                    oracle: oracle.clone(),
+                })
+                .collect::<Vec<_>>(),
+        ),
+        MockQuery::GetPrice { key } => to_binary(&PriceResponse {
+            price: mock_price(deps, key)?,
+        }),
+        MockQuery::GetPrices { keys } => to_binary(&PricesResponse {
+            prices: keys
+                .into_iter()
+                .map(|key| mock_price(deps, key))
+                .collect::<StdResult<_>>()?,
+        }),
+    }
+}
+
+impl MultiTestable for MockOracle {
+    fn contract(&self) -> Box<dyn MultiContract<Empty>> {
+        Box::new(ContractWrapper::new_with_empty(
+            mock_execute,
+            mock_instantiate,
+            mock_query,
+        ))
+    }
+
+    fn default() -> Self {
+        MockOracle
+    }
+}
+
+fn set_price(app: &mut App, oracle: &ContractInfo, rate: Uint128) {
+    let now = app.block_info().time.seconds();
+    MockExecute::SetPrice {
+        key: "TKN".into(),
+        data: ReferenceData {
+            rate,
+            last_updated_base: now,
+            last_updated_quote: now,
+        },
+    }
+    .test_exec(oracle, app, Addr::unchecked("admin"), &[])
+    .unwrap();
+}
+
+fn deposit(app: &mut App, token: &ContractInfo, treasury: &ContractInfo, amount: Uint128) {
+    snip20::ExecuteMsg::Send {
+        recipient: treasury.address.to_string(),
+        recipient_code_hash: None,
+        amount,
+        msg: None,
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(token, app, Addr::unchecked("admin"), &[])
+    .unwrap();
+}
+
+fn allowance(app: &App, token: &ContractInfo, treasury: &ContractInfo, spender: &Addr) -> Uint128 {
+    match (treasury::QueryMsg::Allowance {
+        asset: token.address.to_string(),
+        spender: spender.to_string(),
+    }
+    .test_query(treasury, app)
+    .unwrap())
+    {
+        treasury::QueryAnswer::Allowance { amount } => amount,
+        _ => panic!("query failed"),
+    }
+}
+
+#[test]
+fn portfolio_rebalance() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let portfolio_spender = Addr::unchecked("portfolio_spender");
+    let portion_spender = Addr::unchecked("portion_spender");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let viewing_key = "viewing_key".to_string();
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string(),
+            amount: Uint128::new(1100),
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: Some(snip20::InitConfig {
+            public_total_supply: Some(true),
+            enable_deposit: Some(true),
+            enable_redeem: Some(true),
+            enable_mint: Some(false),
+            enable_burn: Some(false),
+            enable_transfer: Some(true),
+            max_supply: None,
+        }),
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let oracle = MockInit {}
+        .test_init(MockOracle::default(), &mut app, admin.clone(), "oracle", &[])
+        .unwrap();
+
+    let treasury = treasury::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key,
+        multisig: admin.to_string(),
+    }
+    .test_init(Treasury::default(), &mut app, admin.clone(), "treasury", &[])
+    .unwrap();
+
+    treasury::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury::ExecuteMsg::SetOracle {
+        oracle: Some(oracle.clone().into()),
+        max_price_age: None,
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury::ExecuteMsg::SetPriceKey {
+        asset: token.address.to_string(),
+        key: "TKN".into(),
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // $2 per whole token
+    set_price(&mut app, &oracle, Uint128::new(2 * 10u128.pow(18)));
+
+    // 40% of the portfolio, tolerance of 10% of portfolio value
+    treasury::ExecuteMsg::Allowance {
+        asset: token.address.to_string(),
+        allowance: treasury::RawAllowance {
+            spender: portfolio_spender.to_string(),
+            allowance_type: AllowanceType::Portfolio,
+            cycle: Cycle::Constant,
+            amount: Uint128::new(4 * 10u128.pow(17)),
+            tolerance: Uint128::new(10u128.pow(17)),
+        },
+        refresh_now: false,
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // 50% of what is left after the portfolio share
+    treasury::ExecuteMsg::Allowance {
+        asset: token.address.to_string(),
+        allowance: treasury::RawAllowance {
+            spender: portion_spender.to_string(),
+            allowance_type: AllowanceType::Portion,
+            cycle: Cycle::Constant,
+            amount: Uint128::new(5 * 10u128.pow(17)),
+            tolerance: Uint128::zero(),
+        },
+        refresh_now: false,
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    deposit(&mut app, &token, &treasury, Uint128::new(1000));
+
+    let update = |app: &mut App| {
+        treasury::ExecuteMsg::Update {
+            asset: token.address.to_string(),
+        }
+        .test_exec(&treasury, app, admin.clone(), &[])
+    };
+
+    update(&mut app).unwrap();
+    assert_eq!(
+        allowance(&app, &token, &treasury, &portfolio_spender),
+        Uint128::new(400)
+    );
+    assert_eq!(
+        allowance(&app, &token, &treasury, &portion_spender),
+        Uint128::new(300)
+    );
+
+    // Portfolio grows by 40 tokens, within the 110 token tolerance
+    deposit(&mut app, &token, &treasury, Uint128::new(100));
+    update(&mut app).unwrap();
+    assert_eq!(
+        allowance(&app, &token, &treasury, &portfolio_spender),
+        Uint128::new(400)
+    );
+    assert_eq!(
+        allowance(&app, &token, &treasury, &portion_spender),
+        Uint128::new(330)
+    );
+
+    // Prices older than an hour are rejected
+    app.update_block(|block| block.time = block.time.plus_seconds(3601));
+    assert!(update(&mut app).is_err());
+    set_price(&mut app, &oracle, Uint128::new(2 * 10u128.pow(18)));
+    update(&mut app).unwrap();
+
+    // Funds committed to streams are left out of the portfolio
+    treasury::ExecuteMsg::CreateStream {
+        recipient: "recipient".into(),
+        asset: token.address.to_string(),
+        amount: Uint128::new(100),
+        cycle: Cycle::Daily {
+            days: Uint128::new(1),
+        },
+        start: None,
+        end: None,
+    }
+    .test_exec(&treasury, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    match (treasury::QueryMsg::Portfolio {}
+        .test_query(&treasury, &app)
+        .unwrap())
+    {
+        treasury::QueryAnswer::Portfolio { total, assets } => {
+            assert_eq!(assets[0].amount, Uint128::new(1000));
+            assert_eq!(total, Uint128::new(2 * 10u128.pow(15)));
+        }
+        _ => panic!("query failed"),
+    }
+}
@@ -83,6 +83,7 @@ fn single_asset_manager_scrt_staking_integration(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
         query_auth: None,
     }
@@ -190,6 +191,8 @@ fn single_asset_manager_scrt_staking_integration(
             alloc_type,
             amount: alloc_amount,
             tolerance: Uint128::zero(),
+            unbond_priority: 0,
+            unbond_latency: 21,
         },
     }
     .test_exec(&manager, &mut app, admin.clone(), &[])
@@ -220,6 +223,8 @@ fn single_asset_manager_scrt_staking_integration(
         amount: Uint128::new(deposit.u128()),
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -0,0 +1,183 @@
+use shade_multi_test::interfaces::{
+    dao::{init_dao, update_dao},
+    snip20,
+    treasury,
+    utils::DeployedContracts,
+};
+use shade_protocol::{
+    c_std::Uint128,
+    contract_interfaces::dao::{treasury::AllowanceType, treasury_manager::AllocationType},
+    multi_test::App,
+    utils::cycle::Cycle,
+};
+
+const DAY: u64 = 60 * 60 * 24;
+
+fn skip_days(app: &mut App, days: u64) {
+    app.update_block(|block| block.time = block.time.plus_seconds(days * DAY));
+}
+
+#[test]
+pub fn stream() {
+    const PAYEE: &str = "payee";
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1500),
+        "SSCRT",
+        vec![AllowanceType::Portion],
+        vec![Cycle::Constant],
+        vec![Uint128::new(10u128.pow(18))],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Portion]],
+        vec![vec![Uint128::new(10u128.pow(18))]],
+        vec![vec![Uint128::zero()]],
+        true,
+        false,
+    )
+    .unwrap();
+    snip20::set_viewing_key_exec(&mut app, PAYEE, &contracts, "SSCRT", PAYEE.to_string()).unwrap();
+
+    // 50 a day for 10 days
+    let end = Some(app.block_info().time.seconds() + 10 * DAY);
+    let daily = Cycle::Daily {
+        days: Uint128::one(),
+    };
+    assert!(
+        treasury::create_stream_exec(
+            &mut app,
+            PAYEE,
+            &contracts,
+            "SSCRT",
+            PAYEE,
+            Uint128::new(50),
+            daily.clone(),
+            end
+        )
+        .is_err()
+    );
+    assert!(
+        treasury::create_stream_exec(
+            &mut app,
+            "admin",
+            &contracts,
+            "SSCRT",
+            PAYEE,
+            Uint128::new(50),
+            Cycle::Constant,
+            end
+        )
+        .is_err()
+    );
+    treasury::create_stream_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        PAYEE,
+        Uint128::new(50),
+        daily,
+        end,
+    )
+    .unwrap();
+
+    // the next cycle stays in reserves
+    update_dao(&mut app, "admin", &contracts, "SSCRT", 1).unwrap();
+    assert_eq!(
+        treasury::reserves_query(&app, &contracts, "SSCRT").unwrap(),
+        Uint128::new(50)
+    );
+
+    assert!(treasury::withdraw_stream_exec(&mut app, PAYEE, &contracts, 0).is_err());
+    skip_days(&mut app, 3);
+    // accrued funds are unbonded, then claimed back on the next update
+    update_dao(&mut app, "admin", &contracts, "SSCRT", 1).unwrap();
+    update_dao(&mut app, "admin", &contracts, "SSCRT", 1).unwrap();
+    assert_eq!(
+        treasury::reserves_query(&app, &contracts, "SSCRT").unwrap(),
+        Uint128::new(200)
+    );
+    assert!(treasury::withdraw_stream_exec(&mut app, "admin", &contracts, 0).is_err());
+    treasury::withdraw_stream_exec(&mut app, PAYEE, &contracts, 0).unwrap();
+    assert_eq!(
+        snip20::balance_query(&app, PAYEE, &contracts, "SSCRT", PAYEE.to_string()).unwrap(),
+        Uint128::new(150)
+    );
+
+    // nothing accrues while paused
+    treasury::pause_stream_exec(&mut app, "admin", &contracts, 0).unwrap();
+    skip_days(&mut app, 2);
+    assert!(treasury::withdraw_stream_exec(&mut app, PAYEE, &contracts, 0).is_err());
+    treasury::resume_stream_exec(&mut app, "admin", &contracts, 0).unwrap();
+    skip_days(&mut app, 1);
+    let (_, withdrawable) = treasury::stream_query(&app, &contracts, 0).unwrap();
+    assert_eq!(withdrawable, Uint128::new(50));
+
+    // cancelling pays out what has accrued
+    assert!(treasury::cancel_stream_exec(&mut app, PAYEE, &contracts, 0).is_err());
+    treasury::cancel_stream_exec(&mut app, "admin", &contracts, 0).unwrap();
+    assert_eq!(
+        snip20::balance_query(&app, PAYEE, &contracts, "SSCRT", PAYEE.to_string()).unwrap(),
+        Uint128::new(200)
+    );
+    assert!(treasury::stream_query(&app, &contracts, 0).is_err());
+    update_dao(&mut app, "admin", &contracts, "SSCRT", 1).unwrap();
+    assert_eq!(
+        treasury::reserves_query(&app, &contracts, "SSCRT").unwrap(),
+        Uint128::zero()
+    );
+}
+
+#[test]
+pub fn stream_paused_past_end() {
+    const PAYEE: &str = "payee";
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1500),
+        "SSCRT",
+        vec![AllowanceType::Portion],
+        vec![Cycle::Constant],
+        vec![Uint128::new(10u128.pow(18))],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Portion]],
+        vec![vec![Uint128::new(10u128.pow(18))]],
+        vec![vec![Uint128::zero()]],
+        true,
+        false,
+    )
+    .unwrap();
+
+    // 50 a day for 2 days
+    let end = Some(app.block_info().time.seconds() + 2 * DAY);
+    treasury::create_stream_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        PAYEE,
+        Uint128::new(50),
+        Cycle::Daily {
+            days: Uint128::one(),
+        },
+        end,
+    )
+    .unwrap();
+
+    skip_days(&mut app, 3);
+    treasury::pause_stream_exec(&mut app, "admin", &contracts, 0).unwrap();
+
+    // paying out everything owed finishes the stream even while paused
+    treasury::withdraw_stream_exec(&mut app, PAYEE, &contracts, 0).unwrap();
+    assert!(treasury::stream_query(&app, &contracts, 0).is_err());
+    assert_eq!(
+        treasury::reserves_query(&app, &contracts, "SSCRT").unwrap(),
+        Uint128::new(1400)
+    );
+}
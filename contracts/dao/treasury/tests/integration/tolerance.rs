@@ -46,6 +46,7 @@ fn underfunded_tolerance(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
         query_auth: None,
     }
@@ -100,6 +101,8 @@ fn underfunded_tolerance(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -133,6 +136,8 @@ fn underfunded_tolerance(
         amount: added,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -256,6 +261,7 @@ fn overfunded_tolerance(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
         query_auth: None,
     }
@@ -310,6 +316,8 @@ fn overfunded_tolerance(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
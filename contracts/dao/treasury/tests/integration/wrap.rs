@@ -39,6 +39,7 @@ fn wrap_coins_test(coins: Vec<Coin>) {
                 enable_mint: Some(false),
                 enable_burn: Some(false),
                 enable_transfer: Some(true),
+                max_supply: None,
             }),
             query_auth: None,
         }
@@ -0,0 +1,119 @@
+use crate::{health, storage::*};
+use shade_protocol::{
+    c_std::{Addr, DepsMut, Env, QuerierWrapper, StdResult, Storage, Uint128},
+    dao::{
+        adapter,
+        treasury_manager::{Action, AdapterAccounting, Metric},
+    },
+    utils::asset::Contract,
+};
+
+/// Index of the adapter's accounting, adapters seen for the first time start with their
+/// bonded balance as cost basis so capital deployed before accounting existed is covered
+fn position(
+    querier: QuerierWrapper,
+    accounts: &mut Vec<AdapterAccounting>,
+    asset: &Addr,
+    contract: &Contract,
+) -> StdResult<usize> {
+    if let Some(i) = accounts.iter().position(|a| a.adapter == contract.address) {
+        return Ok(i);
+    }
+
+    let balance = adapter::balance_query(querier, asset, contract.clone())?;
+    let unbonding = adapter::unbonding_query(querier, asset, contract.clone())?;
+    accounts.push(AdapterAccounting {
+        adapter: contract.address.clone(),
+        cost_basis: balance.saturating_sub(unbonding),
+        realized_gains: Uint128::zero(),
+        realized_losses: Uint128::zero(),
+    });
+    Ok(accounts.len() - 1)
+}
+
+/* Applies funds sent to & unbonded from adapters to their cost basis, then stores the metrics.
+ * Must be called before the unbond messages execute since it prices withdrawals
+ * off of the adapters' current bonded balance
+ */
+pub fn append_metrics(
+    deps: &mut DepsMut,
+    env: &Env,
+    asset: &Addr,
+    metrics: &mut Vec<Metric>,
+) -> StdResult<()> {
+    let allocations = ALLOCATIONS
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+    let mut accounts = ACCOUNTING
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+    // { adapter: unbonded } for multiple unbonds from one adapter in the same tx
+    let mut unbonded: Vec<(Addr, Uint128)> = vec![];
+
+    for metric in metrics.iter() {
+        let contract = match allocations
+            .iter()
+            .find(|a| a.contract.address == metric.user)
+        {
+            Some(a) => a.contract.clone(),
+            None => continue,
+        };
+        match metric.action {
+            Action::SendFunds | Action::SendFundsFrom => {
+                let i = position(deps.querier, &mut accounts, asset, &contract)?;
+                accounts[i].cost_basis += metric.amount;
+            }
+            Action::Unbond => {
+                let i = position(deps.querier, &mut accounts, asset, &contract)?;
+                let balance = adapter::balance_query(deps.querier, asset, contract.clone())?;
+                let unbonding = adapter::unbonding_query(deps.querier, asset, contract)?;
+
+                let prior = match unbonded.iter().position(|(a, _)| *a == metric.user) {
+                    Some(u) => {
+                        let prior = unbonded[u].1;
+                        unbonded[u].1 += metric.amount;
+                        prior
+                    }
+                    None => {
+                        unbonded.push((metric.user.clone(), metric.amount));
+                        Uint128::zero()
+                    }
+                };
+                let bonded = balance.saturating_sub(unbonding).saturating_sub(prior);
+
+                let basis = accounts[i].cost_basis;
+                let released = if metric.amount >= bonded {
+                    basis
+                } else {
+                    basis.multiply_ratio(metric.amount, bonded)
+                };
+                accounts[i].cost_basis = basis - released;
+
+                if metric.amount > released {
+                    accounts[i].realized_gains += metric.amount - released;
+                } else {
+                    accounts[i].realized_losses += released - metric.amount;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    ACCOUNTING.save(deps.storage, asset.clone(), &accounts)?;
+    health::apply_metrics(deps.storage, asset, metrics)?;
+    METRICS.append(deps.storage, env.block.time, metrics)
+}
+
+/// Realizes any basis left on an adapter that no longer holds funds
+pub fn close(storage: &mut dyn Storage, asset: &Addr, adapter: &Addr) -> StdResult<()> {
+    let mut accounts = match ACCOUNTING.may_load(storage, asset.clone())? {
+        Some(a) => a,
+        None => return Ok(()),
+    };
+    if let Some(i) = accounts.iter().position(|a| a.adapter == *adapter) {
+        accounts[i].realized_losses += accounts[i].cost_basis;
+        accounts[i].cost_basis = Uint128::zero();
+        ACCOUNTING.save(storage, asset.clone(), &accounts)?;
+    }
+    Ok(())
+}
@@ -78,6 +78,14 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             let holder = deps.api.addr_validate(&holder)?;
             execute::remove_holder(deps, &env, info, holder)
         }
+        ExecuteMsg::SetHealthPolicy { policy } => {
+            execute::set_health_policy(deps, &env, info, policy)
+        }
+        ExecuteMsg::ResetHealth { asset, adapter } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            let adapter = deps.api.addr_validate(&adapter)?;
+            execute::reset_health(deps, &env, info, asset, adapter)
+        }
         ExecuteMsg::Manager(a) => match a {
             manager::SubExecuteMsg::Unbond { asset, amount } => {
                 let asset = deps.api.addr_validate(&asset)?;
@@ -118,6 +126,27 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             epoch,
             period,
         } => to_binary(&query::metrics(deps, env, date, epoch, period)?),
+        QueryMsg::Pnl { asset } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            to_binary(&query::pnl(deps, asset)?)
+        }
+        QueryMsg::PeriodReport {
+            asset,
+            date,
+            epoch,
+            period,
+        } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            to_binary(&query::period_report(deps, env, asset, date, epoch, period)?)
+        }
+        QueryMsg::AllocationHealth { asset } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            to_binary(&query::allocation_health(deps, asset)?)
+        }
+        QueryMsg::UnbondEstimate { asset, amount } => {
+            let asset = deps.api.addr_validate(&asset)?;
+            to_binary(&query::unbond_estimate(deps, env, asset, amount)?)
+        }
 
         QueryMsg::Manager(a) => match a {
             manager::SubQueryMsg::Balance { asset, holder } => {
@@ -1,5 +1,4 @@
-use crate::storage::*;
-use itertools::{Either, Itertools};
+use crate::{accounting, health, storage::*, waterfall};
 use shade_protocol::{
     admin::helpers::{validate_admin, AdminPermissions},
     c_std::{
@@ -25,6 +24,8 @@ use shade_protocol::{
             Balance,
             Context,
             ExecuteAnswer,
+            Health,
+            HealthPolicy,
             Holding,
             Metric,
             Status,
@@ -49,7 +50,7 @@ use shade_protocol::{
     },
 };
 
-static ONE_HUNDRED_PERCENT: Uint128 = Uint128::new(10u128.pow(18));
+pub const ONE_HUNDRED_PERCENT: Uint128 = Uint128::new(10u128.pow(18));
 
 pub fn receive(
     deps: DepsMut,
@@ -122,7 +123,7 @@ pub fn receive(
 
 pub fn update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     admin_auth: Option<RawContract>,
     treasury: Option<String>,
@@ -131,6 +132,7 @@ pub fn update_config(
 
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::TreasuryManager,
         &info.sender,
         &config.admin_auth,
@@ -163,6 +165,7 @@ pub fn register_asset(
 
     validate_admin(
         &deps.querier,
+        env,
         AdminPermissions::TreasuryManager,
         &info.sender,
         &config.admin_auth,
@@ -196,7 +199,7 @@ pub fn register_asset(
 
 pub fn allocate(
     deps: DepsMut,
-    _env: &Env,
+    env: &Env,
     info: MessageInfo,
     asset: Addr,
     allocation: Allocation,
@@ -205,6 +208,7 @@ pub fn allocate(
 
     validate_admin(
         &deps.querier,
+        env,
         AdminPermissions::TreasuryManager,
         &info.sender,
         &config.admin_auth,
@@ -239,6 +243,8 @@ pub fn allocate(
         amount: allocation.amount,
         alloc_type: allocation.alloc_type,
         tolerance: allocation.tolerance,
+        unbond_priority: allocation.unbond_priority,
+        unbond_latency: allocation.unbond_latency,
     });
 
     // ensure that the portion allocations don't go above 100%
@@ -303,6 +309,7 @@ pub fn claim(deps: DepsMut, env: &Env, info: MessageInfo, asset: Addr) -> StdRes
         let claim = adapter::claimable_query(deps.querier, &asset, alloc.contract.clone())?;
         if claim > Uint128::zero() {
             messages.push(adapter::claim_msg(&asset, alloc.contract.clone())?);
+            health::withdrawn(deps.storage, &asset, &alloc.contract.address, claim)?;
             METRICS.push(deps.storage, env.block.time, Metric {
                 action: Action::Claim,
                 context: Context::Claim,
@@ -399,7 +406,12 @@ pub fn claim(deps: DepsMut, env: &Env, info: MessageInfo, asset: Addr) -> StdRes
     )?))
 }
 
-pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdResult<Response> {
+pub fn update(
+    mut deps: DepsMut,
+    env: &Env,
+    _info: MessageInfo,
+    asset: Addr,
+) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
     let full_asset = ASSETS.load(deps.storage, asset.clone())?;
@@ -415,31 +427,48 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
     let mut messages = vec![];
     let mut adapter_info = vec![];
 
-    /* this loop has 2 purposes
+    let policy = HEALTH_POLICY.may_load(deps.storage)?;
+    let mut healths = HEALTH
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+    // metrics recorded by this update, starting with health transitions
+    let mut metrics = vec![];
+
+    /* this loop has 3 purposes
      * - check for stale allocaitons that need to be removed
+     * - degrade allocations that fail their queries or lose too much balance
      * - fill the amount_total and portion_total vars with data
      */
     for (i, a) in allocations.clone().iter().enumerate() {
-        let bal = adapter::balance_query(
+        let state = match health::adapter_state(
             deps.querier,
             &full_asset.contract.address,
-            a.contract.clone(),
-        )?;
-        let mut unbonding = adapter::unbonding_query(
-            deps.querier,
-            &full_asset.contract.address,
-            a.contract.clone(),
-        )?;
-        let unbondable = adapter::unbondable_query(
-            deps.querier,
-            &full_asset.contract.address,
-            a.contract.clone(),
-        )?;
-        let claimable = adapter::claimable_query(
-            deps.querier,
-            &full_asset.contract.address,
-            a.contract.clone(),
-        )?;
+            &a.contract,
+        ) {
+            Ok(state) => state,
+            Err(e) => {
+                // count the last known balance so the funds aren't realized as losses
+                let h = health::position(
+                    &mut healths,
+                    &a.contract.address,
+                    Uint128::zero(),
+                    env.block.time.seconds(),
+                );
+                if healths[h].health == Health::Healthy {
+                    health::degrade(&mut healths[h], env, &asset, e.to_string(), &mut metrics);
+                }
+                match a.alloc_type {
+                    AllocationType::Amount => amount_total += healths[h].expected_balance,
+                    AllocationType::Portion => portion_total += healths[h].expected_balance,
+                };
+                continue;
+            }
+        };
+        let bal = state.balance;
+        let mut unbonding = state.unbonding;
+        let unbondable = state.unbondable;
+        let claimable = state.claimable;
+
         if !claimable.is_zero() {
             messages.push(adapter::claim_msg(
                 &full_asset.contract.address.clone(),
@@ -457,6 +486,44 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
             stale_allocs.push(i);
         }
 
+        // fill totals with data
+        match a.alloc_type {
+            AllocationType::Amount => amount_total += bal,
+            AllocationType::Portion => portion_total += bal,
+        };
+
+        let h = health::position(
+            &mut healths,
+            &a.contract.address,
+            bal,
+            env.block.time.seconds(),
+        );
+        if healths[h].health == Health::Healthy {
+            if let Some(reason) = health::check(&healths[h], &state, &policy) {
+                health::degrade(&mut healths[h], env, &asset, reason, &mut metrics);
+            }
+        }
+        // claimed funds leave the adapter with this update
+        healths[h].expected_balance = bal.saturating_sub(claimable);
+
+        // degraded allocations are left out of rebalancing, emergency unbonds are not holder
+        // unbondings so they stay out of UNBONDINGS & are claimed into reserves
+        if healths[h].health == Health::Degraded {
+            let bonded = bal.saturating_sub(unbonding);
+            if policy.as_ref().map_or(false, |p| p.emergency_unbond) && !bonded.is_zero() {
+                messages.push(adapter::unbond_msg(&asset, bonded, a.contract.clone())?);
+                metrics.push(Metric {
+                    action: Action::Unbond,
+                    context: Context::Health,
+                    timestamp: env.block.time.seconds(),
+                    token: asset.clone(),
+                    amount: bonded,
+                    user: a.contract.address.clone(),
+                });
+            }
+            continue;
+        }
+
         adapter_info.push(AllocationTempData {
             contract: a.contract.clone(),
             alloc_type: a.alloc_type.clone(),
@@ -465,20 +532,19 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
             balance: bal,
             unbondable,
             unbonding,
+            unbond_priority: a.unbond_priority,
+            unbond_latency: a.unbond_latency,
         });
-
-        // fill totals with data
-        match a.alloc_type {
-            AllocationType::Amount => amount_total += bal,
-            AllocationType::Portion => portion_total += bal,
-        };
     }
+    HEALTH.save(deps.storage, asset.clone(), &healths)?;
 
     // actually drop the stale allocs
     if !stale_allocs.is_empty() {
         for index in stale_allocs.iter().rev() {
             // remove used here to preserve sorted vec
-            allocations.remove(index.clone());
+            let stale = allocations.remove(index.clone());
+            accounting::close(deps.storage, &asset, &stale.contract.address)?;
+            health::remove(deps.storage, &asset, &stale.contract.address)?;
         }
         ALLOCATIONS.save(deps.storage, asset.clone(), &allocations)?;
     }
@@ -514,7 +580,6 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
     // Batch send_from actions
     let mut send_from_actions = vec![];
     let mut send_actions = vec![];
-    let mut metrics = vec![];
 
     let key = VIEWING_KEY.load(deps.storage)?;
 
@@ -613,6 +678,7 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                         amount: desired_input,
                         msg: None,
                         memo: None,
+                        decoys: None,
                     });
                     metrics.push(Metric {
                         action: Action::SendFunds,
@@ -638,6 +704,7 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                         amount: balance,
                         msg: None,
                         memo: None,
+                        decoys: None,
                     });
                     metrics.push(Metric {
                         action: Action::SendFunds,
@@ -824,7 +891,7 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
         )?);
     }
 
-    METRICS.append(deps.storage, env.block.time, &mut metrics)?;
+    accounting::append_metrics(&mut deps, env, &asset, &mut metrics)?;
 
     Ok(Response::new().add_messages(messages).set_data(to_binary(
         &adapter::ExecuteAnswer::Update {
@@ -834,7 +901,7 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
 }
 
 pub fn unbond(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: &Env,
     info: MessageInfo,
     asset: Addr,
@@ -849,6 +916,7 @@ pub fn unbond(
         false => {
             validate_admin(
                 &deps.querier,
+                env,
                 AdminPermissions::TreasuryManager,
                 &info.sender,
                 &config.admin_auth,
@@ -907,11 +975,26 @@ pub fn unbond(
     HOLDING.save(deps.storage, unbonder.clone(), &holding)?;
     let allocations = ALLOCATIONS.load(deps.storage, asset.clone())?;
 
+    let mut metrics = vec![];
+
+    // degraded allocations are neither unbonded from nor counted as unbonding for holders
+    let mut healths = HEALTH
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+    let allocations = health::unbondable_allocations(
+        deps.querier,
+        env,
+        &asset,
+        allocations,
+        &mut healths,
+        &mut metrics,
+    );
+    HEALTH.save(deps.storage, asset.clone(), &healths)?;
+
     // get the total amount that the adapters are currently unbonding
     let mut unbonding_tot = Uint128::zero();
-    for a in allocations.clone() {
-        unbonding_tot +=
-            adapter::unbonding_query(deps.querier, &asset.clone(), a.contract.clone())?;
+    for (_, state) in allocations.iter() {
+        unbonding_tot += state.unbonding;
     }
 
     // find the unbond_amount based off of amounts that the TM has unbonded independent of a holder
@@ -985,7 +1068,6 @@ pub fn unbond(
     }
 
     let mut messages = vec![];
-    let mut metrics = vec![];
 
     // Send available reserves to unbonder
     if reserves > Uint128::zero() {
@@ -1042,7 +1124,7 @@ pub fn unbond(
             }
             HOLDING.save(deps.storage, unbonder, &holding)?;
 
-            METRICS.append(deps.storage, env.block.time, &mut metrics)?;
+            accounting::append_metrics(&mut deps, env, &asset, &mut metrics)?;
             return Ok(Response::new().add_messages(messages).set_data(to_binary(
                 &adapter::ExecuteAnswer::Unbond {
                     status: ResponseStatus::Success,
@@ -1052,313 +1134,46 @@ pub fn unbond(
         }
     }
 
-    // let full_asset = ASSETS.load(deps.storage, asset.clone())?;
-
-    // Build metadata
-    let mut alloc_meta = vec![];
-    let mut amount_total = Uint128::zero();
-    let mut portion_total = Uint128::zero();
-    let mut tot_unbond_available = Uint128::zero();
-
     // Gather adapter outstanding amounts
-    for a in allocations {
-        let bal = adapter::balance_query(deps.querier, &asset, a.contract.clone())?;
-        let unbondable = adapter::unbondable_query(deps.querier, &asset, a.contract.clone())?;
-
+    let mut alloc_meta = vec![];
+    for (a, state) in allocations {
         alloc_meta.push(AllocationTempData {
             contract: a.contract.clone(),
             alloc_type: a.alloc_type.clone(),
             amount: a.amount.clone(),
             tolerance: a.tolerance.clone(),
-            balance: bal,
-            unbondable,
+            balance: state.balance,
+            unbondable: state.unbondable,
             unbonding: Uint128::zero(),
+            unbond_priority: a.unbond_priority,
+            unbond_latency: a.unbond_latency,
         });
-
-        tot_unbond_available += unbondable;
-
-        match a.alloc_type {
-            AllocationType::Amount => amount_total += bal,
-            AllocationType::Portion => portion_total += bal,
-        };
     }
 
-    // if unbond_amount == tot_amount_unbonding, unbond all unbondable amounts and return
-    if unbond_amount == tot_unbond_available {
-        for a in alloc_meta.clone() {
-            messages.push(adapter::unbond_msg(
-                &full_asset.contract.address.clone(),
-                a.unbondable.clone(),
-                a.contract.clone(),
-            )?);
-            metrics.push(Metric {
-                action: Action::Unbond,
-                context: Context::Unbond,
-                timestamp: env.block.time.seconds(),
-                token: asset.clone(),
-                amount: a.balance.clone(),
-                user: a.contract.address.clone(),
-            });
-        }
-        METRICS.append(deps.storage, env.block.time, &mut metrics)?;
-        return Ok(Response::new().add_messages(messages).set_data(to_binary(
-            &adapter::ExecuteAnswer::Unbond {
-                status: ResponseStatus::Success,
-                amount,
-            },
-        )?));
-    }
-
-    let mut total_amount_unbonding = Uint128::zero();
-    let mut unbond_amounts = vec![];
-
-    let (amounts, portions): (Vec<AllocationTempData>, Vec<AllocationTempData>) = alloc_meta
-        .clone()
-        .into_iter()
-        .partition_map(|a| match a.alloc_type {
-            AllocationType::Amount => Either::Left(a),
-            AllocationType::Portion => Either::Right(a),
+    // unbond from the adapters in priority order
+    for (meta, unbond) in waterfall::waterfall(alloc_meta, unbond_amount) {
+        messages.push(adapter::unbond_msg(
+            &full_asset.contract.address,
+            unbond,
+            meta.contract.clone(),
+        )?);
+        metrics.push(Metric {
+            action: Action::Unbond,
+            context: Context::Unbond,
+            timestamp: env.block.time.seconds(),
+            token: asset.clone(),
+            amount: unbond,
+            user: meta.contract.address.clone(),
         });
-
-    // unbond the extra tokens from the amount adapters
-    for meta in amounts.clone() {
-        if meta.unbondable > meta.amount {
-            total_amount_unbonding += meta.unbondable - meta.amount;
-            unbond_amounts.push(meta.unbondable - meta.amount);
-        } else {
-            unbond_amounts.push(Uint128::zero())
-        }
-    }
-
-    // if the extra tokens from the amount adapters covers the unbond request, push the messages
-    // and return
-    if unbond_amount == total_amount_unbonding {
-        for (i, meta) in amounts.clone().iter().enumerate() {
-            messages.push(adapter::unbond_msg(
-                &full_asset.contract.address.clone(),
-                unbond_amounts[i],
-                meta.contract.clone(),
-            )?);
-            metrics.push(Metric {
-                action: Action::Unbond,
-                context: Context::Unbond,
-                timestamp: env.block.time.seconds(),
-                token: asset.clone(),
-                amount: unbond_amounts[i],
-                user: meta.contract.address.clone(),
-            });
-        }
-        METRICS.append(deps.storage, env.block.time, &mut metrics)?;
-        return Ok(Response::new().add_messages(messages).set_data(to_binary(
-            &adapter::ExecuteAnswer::Unbond {
-                status: ResponseStatus::Success,
-                amount,
-            },
-        )?));
-    } else if unbond_amount < total_amount_unbonding {
-        // if the extra tokens are greater than the unbond request, unbond proportionally to the
-        // extra tokens available and return
-        let mut modified_total_amount_unbonding = Uint128::zero();
-        for (i, meta) in amounts.clone().iter().enumerate() {
-            unbond_amounts[i] =
-                unbond_amount.multiply_ratio(unbond_amounts[i], total_amount_unbonding);
-            modified_total_amount_unbonding += unbond_amounts[i];
-            // avoid off by one error
-            if i == amounts.len() - 1
-                && modified_total_amount_unbonding < unbond_amount
-                && unbond_amounts[i] + Uint128::new(1) <= meta.unbondable
-            {
-                unbond_amounts[i] += Uint128::new(1);
-            }
-            messages.push(adapter::unbond_msg(
-                &full_asset.contract.address.clone(),
-                unbond_amounts[i],
-                meta.contract.clone(),
-            )?);
-            metrics.push(Metric {
-                action: Action::Unbond,
-                context: Context::Unbond,
-                timestamp: env.block.time.seconds(),
-                token: asset.clone(),
-                amount: unbond_amounts[i],
-                user: meta.contract.address.clone(),
-            });
-        }
-        METRICS.append(deps.storage, env.block.time, &mut metrics)?;
-        return Ok(Response::new().add_messages(messages).set_data(to_binary(
-            &adapter::ExecuteAnswer::Unbond {
-                status: ResponseStatus::Success,
-                amount,
-            },
-        )?));
     }
 
-    // if portion total > unbond - tot, we know the portion adapters can cover the rest
-    if unbond_amount - total_amount_unbonding < portion_total {
-        // unbond the tokens slotted for unbonding from the amount adapters
-        for (i, meta) in amounts.clone().iter().enumerate() {
-            if !unbond_amounts[i].is_zero() {
-                messages.push(adapter::unbond_msg(
-                    &full_asset.contract.address.clone(),
-                    unbond_amounts[i],
-                    meta.contract.clone(),
-                )?);
-                metrics.push(Metric {
-                    action: Action::Unbond,
-                    context: Context::Unbond,
-                    timestamp: env.block.time.seconds(),
-                    token: asset.clone(),
-                    amount: unbond_amounts[i],
-                    user: meta.contract.address.clone(),
-                });
-            }
-        }
-        let amount_adapt_tot_unbonding = total_amount_unbonding;
-        /* For each portion adapter, unbond the amount proportional to its portion of the total
-         * balance
-         */
-        for (i, meta) in portions.clone().iter().enumerate() {
-            let unbond_from_portion = (unbond_amount - amount_adapt_tot_unbonding)
-                .multiply_ratio(meta.unbondable, portion_total);
-            unbond_amounts.push(unbond_from_portion);
-            total_amount_unbonding += unbond_from_portion;
-            // Avoid off by 1 error
-            if i == portions.len() - 1
-                && total_amount_unbonding < unbond_amount
-                && unbond_from_portion + Uint128::new(1) <= meta.unbondable
-            {
-                messages.push(adapter::unbond_msg(
-                    &full_asset.contract.address.clone(),
-                    unbond_from_portion + Uint128::new(1),
-                    meta.contract.clone(),
-                )?);
-                metrics.push(Metric {
-                    action: Action::Unbond,
-                    context: Context::Unbond,
-                    timestamp: env.block.time.seconds(),
-                    token: asset.clone(),
-                    amount: unbond_from_portion + Uint128::new(1),
-                    user: meta.contract.address.clone(),
-                });
-            } else if !unbond_from_portion.is_zero() {
-                messages.push(adapter::unbond_msg(
-                    &full_asset.contract.address.clone(),
-                    unbond_from_portion,
-                    meta.contract.clone(),
-                )?);
-                metrics.push(Metric {
-                    action: Action::Unbond,
-                    context: Context::Unbond,
-                    timestamp: env.block.time.seconds(),
-                    token: asset.clone(),
-                    amount: unbond_from_portion,
-                    user: meta.contract.address.clone(),
-                });
-            }
-        }
-        METRICS.append(deps.storage, env.block.time, &mut metrics)?;
-        return Ok(Response::new().add_messages(messages).set_data(to_binary(
-            &adapter::ExecuteAnswer::Unbond {
-                status: ResponseStatus::Success,
-                amount,
-            },
-        )?));
-    } else {
-        // Otherwise we need to unbond everything from the portion adapters and go back to the
-        // amount adapters
-        for meta in portions {
-            unbond_amounts.push(meta.unbondable);
-            if !meta.unbondable.is_zero() {
-                messages.push(adapter::unbond_msg(
-                    &full_asset.contract.address,
-                    meta.unbondable,
-                    meta.contract.clone(),
-                )?);
-                metrics.push(Metric {
-                    action: Action::Unbond,
-                    context: Context::Unbond,
-                    timestamp: env.block.time.seconds(),
-                    token: asset.clone(),
-                    amount: meta.unbondable,
-                    user: meta.contract.address.clone(),
-                });
-            }
-            total_amount_unbonding += meta.unbondable;
-        }
-        // tot_amount_unbonding is equal to unbond_amount, unbonding everything from the portion
-        // adapters covers our requested unbonding, so we push msgs and return
-        if total_amount_unbonding == unbond_amount {
-            for (i, meta) in amounts.clone().iter().enumerate() {
-                if !unbond_amounts[i].is_zero() {
-                    messages.push(adapter::unbond_msg(
-                        &full_asset.contract.address,
-                        unbond_amounts[i].clone(),
-                        meta.contract.clone(),
-                    )?);
-                    metrics.push(Metric {
-                        action: Action::Unbond,
-                        context: Context::Unbond,
-                        timestamp: env.block.time.seconds(),
-                        token: asset.clone(),
-                        amount: unbond_amounts[i].clone(),
-                        user: meta.contract.address.clone(),
-                    });
-                }
-            }
-            METRICS.append(deps.storage, env.block.time, &mut metrics)?;
-            return Ok(Response::new().add_messages(messages).set_data(to_binary(
-                &adapter::ExecuteAnswer::Unbond {
-                    status: ResponseStatus::Success,
-                    amount,
-                },
-            )?));
-        } else {
-            // unbond token amounts proportional to the ratio of the allocation of the adapter and
-            // the sum of the amount allocaitons
-            let mut amount_alloc = Uint128::zero();
-            for meta in amounts.clone() {
-                amount_alloc += meta.amount;
-            }
-            let mut modified_total_amount_unbonding = total_amount_unbonding;
-            for (i, meta) in amounts.iter().enumerate() {
-                unbond_amounts[i] += (unbond_amount - total_amount_unbonding)
-                    .multiply_ratio(meta.amount, amount_alloc);
-
-                modified_total_amount_unbonding += meta.unbondable;
-                // this makes sure that the entire unbond request is fuffiled by the end of this
-                // block
-                if i == amounts.len() - 1
-                    && modified_total_amount_unbonding < unbond_amount
-                    && unbond_amount - modified_total_amount_unbonding
-                        < meta.unbondable - unbond_amounts[i]
-                {
-                    unbond_amounts[i] += unbond_amount - total_amount_unbonding;
-                }
-                if !unbond_amounts[i].is_zero() {
-                    messages.push(adapter::unbond_msg(
-                        &full_asset.contract.address,
-                        unbond_amounts[i],
-                        meta.contract.clone(),
-                    )?);
-                    metrics.push(Metric {
-                        action: Action::Unbond,
-                        context: Context::Unbond,
-                        timestamp: env.block.time.seconds(),
-                        token: asset.clone(),
-                        amount: unbond_amounts[i].clone(),
-                        user: meta.contract.address.clone(),
-                    });
-                }
-            }
-            METRICS.append(deps.storage, env.block.time, &mut metrics)?;
-            return Ok(Response::new().add_messages(messages).set_data(to_binary(
-                &adapter::ExecuteAnswer::Unbond {
-                    status: ResponseStatus::Success,
-                    amount,
-                },
-            )?));
-        }
-    }
+    accounting::append_metrics(&mut deps, env, &asset, &mut metrics)?;
+    Ok(Response::new().add_messages(messages).set_data(to_binary(
+        &adapter::ExecuteAnswer::Unbond {
+            status: ResponseStatus::Success,
+            amount,
+        },
+    )?))
 }
 
 pub fn add_holder(
@@ -1370,6 +1185,7 @@ pub fn add_holder(
     let config = CONFIG.load(deps.storage)?;
     validate_admin(
         &deps.querier,
+        env,
         AdminPermissions::TreasuryManager,
         &info.sender,
         &config.admin_auth,
@@ -1413,6 +1229,7 @@ pub fn remove_holder(
     let config = CONFIG.load(deps.storage)?;
     validate_admin(
         &deps.querier,
+        env,
         AdminPermissions::TreasuryManager,
         &info.sender,
         &config.admin_auth,
@@ -1444,3 +1261,97 @@ pub fn remove_holder(
         })?),
     )
 }
+
+pub fn set_health_policy(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    policy: HealthPolicy,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_admin(
+        &deps.querier,
+        env,
+        AdminPermissions::TreasuryManager,
+        &info.sender,
+        &config.admin_auth,
+    )?;
+
+    if policy.max_balance_drop >= ONE_HUNDRED_PERCENT {
+        return Err(StdError::generic_err(format!(
+            "Max balance drop {} >= 100%",
+            policy.max_balance_drop
+        )));
+    }
+
+    HEALTH_POLICY.save(deps.storage, &policy)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetHealthPolicy {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+/// Returns a degraded allocation to rebalancing, expecting its current balance
+pub fn reset_health(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    asset: Addr,
+    adapter: Addr,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    validate_admin(
+        &deps.querier,
+        env,
+        AdminPermissions::TreasuryManager,
+        &info.sender,
+        &config.admin_auth,
+    )?;
+
+    let allocation = match ALLOCATIONS
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![])
+        .into_iter()
+        .find(|a| a.contract.address == adapter)
+    {
+        Some(a) => a,
+        None => {
+            return Err(StdError::generic_err("Not an allocated adapter"));
+        }
+    };
+
+    let mut healths = HEALTH
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+    let h = match healths.iter().position(|h| h.contract == adapter) {
+        Some(h) if healths[h].health == Health::Degraded => h,
+        _ => {
+            return Err(StdError::generic_err("Allocation is not degraded"));
+        }
+    };
+
+    // the adapter has to be responding again to be trusted with funds
+    let balance = adapter::balance_query(deps.querier, &asset, allocation.contract)?;
+    healths[h].health = Health::Healthy;
+    healths[h].reason = None;
+    healths[h].expected_balance = balance;
+    healths[h].since = env.block.time.seconds();
+    HEALTH.save(deps.storage, asset.clone(), &healths)?;
+
+    METRICS.push(deps.storage, env.block.time, Metric {
+        action: Action::Restore,
+        context: Context::Health,
+        timestamp: env.block.time.seconds(),
+        token: asset,
+        amount: balance,
+        user: adapter,
+    })?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::ResetHealth {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
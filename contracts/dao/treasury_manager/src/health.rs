@@ -0,0 +1,184 @@
+use crate::{execute::ONE_HUNDRED_PERCENT, storage::*};
+use shade_protocol::{
+    c_std::{Addr, Env, QuerierWrapper, StdResult, Storage, Uint128},
+    dao::{
+        adapter,
+        treasury_manager::{
+            Action,
+            AllocationHealth,
+            AllocationMeta,
+            Context,
+            Health,
+            HealthPolicy,
+            Metric,
+        },
+    },
+    utils::asset::Contract,
+};
+
+/// Adapter state used by update
+pub struct AdapterState {
+    pub balance: Uint128,
+    pub unbonding: Uint128,
+    pub unbondable: Uint128,
+    pub claimable: Uint128,
+}
+
+/// Queries everything update needs from an adapter, any failure is returned rather than aborting
+pub fn adapter_state(
+    querier: QuerierWrapper,
+    asset: &Addr,
+    contract: &Contract,
+) -> StdResult<AdapterState> {
+    Ok(AdapterState {
+        balance: adapter::balance_query(querier, asset, contract.clone())?,
+        unbonding: adapter::unbonding_query(querier, asset, contract.clone())?,
+        unbondable: adapter::unbondable_query(querier, asset, contract.clone())?,
+        claimable: adapter::claimable_query(querier, asset, contract.clone())?,
+    })
+}
+
+/// Index of the adapters' health, new adapters start healthy at their current balance
+pub fn position(
+    healths: &mut Vec<AllocationHealth>,
+    adapter: &Addr,
+    balance: Uint128,
+    now: u64,
+) -> usize {
+    match healths.iter().position(|h| h.contract == *adapter) {
+        Some(i) => i,
+        None => {
+            healths.push(AllocationHealth {
+                contract: adapter.clone(),
+                health: Health::Healthy,
+                expected_balance: balance,
+                reason: None,
+                since: now,
+            });
+            healths.len() - 1
+        }
+    }
+}
+
+/// Why a healthy adapter should be degraded, if it should be
+pub fn check(
+    health: &AllocationHealth,
+    state: &AdapterState,
+    policy: &Option<HealthPolicy>,
+) -> Option<String> {
+    if state.unbondable > state.balance {
+        return Some(format!(
+            "Unbondable {} exceeds balance {}",
+            state.unbondable, state.balance
+        ));
+    }
+    if let Some(policy) = policy {
+        let max_drop = health
+            .expected_balance
+            .multiply_ratio(policy.max_balance_drop, ONE_HUNDRED_PERCENT);
+        let drop = health.expected_balance.saturating_sub(state.balance);
+        if drop > max_drop {
+            return Some(format!(
+                "Balance {} dropped {} below expected {}",
+                state.balance, drop, health.expected_balance
+            ));
+        }
+    }
+    None
+}
+
+pub fn degrade(
+    health: &mut AllocationHealth,
+    env: &Env,
+    asset: &Addr,
+    reason: String,
+    metrics: &mut Vec<Metric>,
+) {
+    health.health = Health::Degraded;
+    health.reason = Some(reason);
+    health.since = env.block.time.seconds();
+    metrics.push(Metric {
+        action: Action::Degrade,
+        context: Context::Health,
+        timestamp: env.block.time.seconds(),
+        token: asset.clone(),
+        amount: health.expected_balance,
+        user: health.contract.clone(),
+    });
+}
+
+/// Allocations that can be unbonded from with their adapter state, degraded allocations are
+/// left out & healthy ones failing their queries are degraded the way update does
+pub fn unbondable_allocations(
+    querier: QuerierWrapper,
+    env: &Env,
+    asset: &Addr,
+    allocations: Vec<AllocationMeta>,
+    healths: &mut Vec<AllocationHealth>,
+    metrics: &mut Vec<Metric>,
+) -> Vec<(AllocationMeta, AdapterState)> {
+    let mut unbondable = vec![];
+    for a in allocations {
+        let state = adapter_state(querier, asset, &a.contract);
+        let h = position(
+            healths,
+            &a.contract.address,
+            state.as_ref().map_or(Uint128::zero(), |s| s.balance),
+            env.block.time.seconds(),
+        );
+        if healths[h].health == Health::Degraded {
+            continue;
+        }
+        match state {
+            Ok(state) => unbondable.push((a, state)),
+            Err(e) => degrade(&mut healths[h], env, asset, e.to_string(), metrics),
+        }
+    }
+    unbondable
+}
+
+/// Reduces the expected balance of an adapter for funds it has been asked to release
+pub fn withdrawn(
+    storage: &mut dyn Storage,
+    asset: &Addr,
+    adapter: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    let mut healths = match HEALTH.may_load(storage, asset.clone())? {
+        Some(h) => h,
+        None => return Ok(()),
+    };
+    if let Some(h) = healths.iter_mut().find(|h| h.contract == *adapter) {
+        h.expected_balance = h.expected_balance.saturating_sub(amount);
+        HEALTH.save(storage, asset.clone(), &healths)?;
+    }
+    Ok(())
+}
+
+/// Applies funds sent to adapters to their expected balances, unbonded funds stay in the
+/// adapter's balance until they are claimed & are released by `withdrawn`
+pub fn apply_metrics(storage: &mut dyn Storage, asset: &Addr, metrics: &[Metric]) -> StdResult<()> {
+    let mut healths = match HEALTH.may_load(storage, asset.clone())? {
+        Some(h) => h,
+        None => return Ok(()),
+    };
+    for metric in metrics {
+        let h = match healths.iter_mut().find(|h| h.contract == metric.user) {
+            Some(h) => h,
+            None => continue,
+        };
+        if matches!(metric.action, Action::SendFunds | Action::SendFundsFrom) {
+            h.expected_balance += metric.amount;
+        }
+    }
+    HEALTH.save(storage, asset.clone(), &healths)
+}
+
+pub fn remove(storage: &mut dyn Storage, asset: &Addr, adapter: &Addr) -> StdResult<()> {
+    let mut healths = match HEALTH.may_load(storage, asset.clone())? {
+        Some(h) => h,
+        None => return Ok(()),
+    };
+    healths.retain(|h| h.contract != *adapter);
+    HEALTH.save(storage, asset.clone(), &healths)
+}
@@ -1,4 +1,7 @@
+pub mod accounting;
 pub mod contract;
 pub mod execute;
+pub mod health;
 pub mod query;
 pub mod storage;
+pub mod waterfall;
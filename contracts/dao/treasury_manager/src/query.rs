@@ -1,9 +1,14 @@
-use crate::storage::*;
+use crate::{health, storage::*, waterfall};
 use shade_protocol::{
     c_std::{Addr, Deps, Env, StdError, StdResult, Uint128},
-    dao::{adapter, manager, treasury_manager},
+    dao::{
+        adapter,
+        manager,
+        treasury_manager,
+        treasury_manager::{AllocationHealth, Health},
+    },
     snip20::helpers::{allowance_query, balance_query},
-    utils::{cycle::parse_utc_datetime, storage::plus::period_storage::Period},
+    utils::storage::plus::period_storage::{period_key, Period},
 };
 
 pub fn config(deps: Deps) -> StdResult<treasury_manager::QueryAnswer> {
@@ -19,23 +24,29 @@ pub fn metrics(
     epoch: Option<Uint128>,
     period: Period,
 ) -> StdResult<treasury_manager::QueryAnswer> {
-    if date.is_some() && epoch.is_some() {
-        return Err(StdError::generic_err("cannot pass both epoch and date"));
-    }
-    let key = {
-        if let Some(d) = date {
-            parse_utc_datetime(&d)?.timestamp() as u64
-        } else if let Some(e) = epoch {
-            e.u128() as u64
-        } else {
-            env.block.time.seconds()
-        }
-    };
+    let key = period_key(env.block.time.seconds(), date, epoch)?;
     Ok(treasury_manager::QueryAnswer::Metrics {
         metrics: METRICS.load_period(deps.storage, key, period)?,
     })
 }
 
+pub fn period_report(
+    deps: Deps,
+    env: Env,
+    asset: Addr,
+    date: Option<String>,
+    epoch: Option<Uint128>,
+    period: Period,
+) -> StdResult<treasury_manager::QueryAnswer> {
+    let key = period_key(env.block.time.seconds(), date, epoch)?;
+
+    let totals = METRICS.period_totals(deps.storage, key, period, |m| {
+        (m.token == asset).then(|| (m.action, m.context, m.amount))
+    })?;
+
+    Ok(treasury_manager::QueryAnswer::PeriodReport { totals })
+}
+
 pub fn pending_allowance(
     deps: Deps,
     env: Env,
@@ -88,6 +99,191 @@ pub fn assets(deps: Deps) -> StdResult<treasury_manager::QueryAnswer> {
     })
 }
 
+pub fn pnl(deps: Deps, asset: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+    if ASSETS.may_load(deps.storage, asset.clone())?.is_none() {
+        return Err(StdError::generic_err("Not a registered asset"));
+    }
+
+    let allocations = ALLOCATIONS
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+    let healths = HEALTH
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+
+    let mut adapters = vec![];
+    for account in ACCOUNTING
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![])
+    {
+        // adapters that have been removed hold nothing
+        let bonded = match allocations
+            .iter()
+            .find(|a| a.contract.address == account.adapter)
+        {
+            Some(a) => {
+                // degraded adapters & failing queries are valued at the last known balance
+                let health = healths.iter().find(|h| h.contract == a.contract.address);
+                let last_known = health.map_or(Uint128::zero(), |h| h.expected_balance);
+                match health {
+                    Some(AllocationHealth {
+                        health: Health::Degraded,
+                        ..
+                    }) => last_known,
+                    _ => match health::adapter_state(deps.querier, &asset, &a.contract) {
+                        Ok(state) => state.balance.saturating_sub(state.unbonding),
+                        Err(_) => last_known,
+                    },
+                }
+            }
+            None => Uint128::zero(),
+        };
+
+        adapters.push(treasury_manager::AdapterPnl {
+            unrealized_gains: bonded.saturating_sub(account.cost_basis),
+            unrealized_losses: account.cost_basis.saturating_sub(bonded),
+            adapter: account.adapter,
+            cost_basis: account.cost_basis,
+            bonded,
+            realized_gains: account.realized_gains,
+            realized_losses: account.realized_losses,
+        });
+    }
+
+    Ok(treasury_manager::QueryAnswer::Pnl { adapters })
+}
+
+pub fn allocation_health(deps: Deps, asset: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+    if ASSETS.may_load(deps.storage, asset.clone())?.is_none() {
+        return Err(StdError::generic_err("Not a registered asset"));
+    }
+    Ok(treasury_manager::QueryAnswer::AllocationHealth {
+        policy: HEALTH_POLICY.may_load(deps.storage)?,
+        allocations: HEALTH.may_load(deps.storage, asset)?.unwrap_or(vec![]),
+    })
+}
+
+/// Funds claimable after days, 0 days is available immediately
+fn add_tranche(
+    immediate: &mut Uint128,
+    delayed: &mut Vec<treasury_manager::UnbondTranche>,
+    days: u64,
+    amount: Uint128,
+) {
+    if amount.is_zero() {
+        return;
+    }
+    if days == 0 {
+        *immediate += amount;
+    } else if let Some(t) = delayed.iter_mut().find(|t| t.days == days) {
+        t.amount += amount;
+    } else {
+        delayed.push(treasury_manager::UnbondTranche { days, amount });
+    }
+}
+
+pub fn unbond_estimate(
+    deps: Deps,
+    env: Env,
+    asset: Addr,
+    amount: Uint128,
+) -> StdResult<treasury_manager::QueryAnswer> {
+    let full_asset = match ASSETS.may_load(deps.storage, asset.clone())? {
+        Some(a) => a,
+        None => {
+            return Err(StdError::generic_err("Not a registered asset"));
+        }
+    };
+    let allocations = ALLOCATIONS
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+
+    // like unbond, degraded & failing allocations are left out
+    let mut healths = HEALTH
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or(vec![]);
+    let allocations = health::unbondable_allocations(
+        deps.querier,
+        &env,
+        &asset,
+        allocations,
+        &mut healths,
+        &mut vec![],
+    );
+
+    let mut remaining = amount;
+    let mut immediate = Uint128::zero();
+    let mut delayed = vec![];
+
+    // unbondings the manager started on its own cover requests first,
+    // they are assumed to arrive with the slowest adapter currently unbonding
+    let mut unbonding_tot = Uint128::zero();
+    let mut latency = 0;
+    for (a, state) in allocations.iter() {
+        if !state.unbonding.is_zero() {
+            latency = latency.max(a.unbond_latency);
+        }
+        unbonding_tot += state.unbonding;
+    }
+    let independent = UNBONDINGS
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or_default();
+    if independent <= unbonding_tot {
+        let covered = independent.min(remaining);
+        add_tranche(&mut immediate, &mut delayed, latency, covered);
+        remaining -= covered;
+    }
+
+    // reserves not already owed to holders are sent immediately
+    let mut holder_unbondings = Uint128::zero();
+    for h in HOLDERS.load(deps.storage)? {
+        if let Some(u) = HOLDING
+            .load(deps.storage, h)?
+            .unbondings
+            .iter()
+            .find(|u| u.token == asset)
+        {
+            holder_unbondings += u.amount;
+        }
+    }
+    let reserves = balance_query(
+        &deps.querier,
+        env.contract.address,
+        VIEWING_KEY.load(deps.storage)?,
+        &full_asset.contract,
+    )?
+    .saturating_sub(holder_unbondings);
+    let covered = reserves.min(remaining);
+    immediate += covered;
+    remaining -= covered;
+
+    let mut alloc_meta = vec![];
+    for (a, state) in allocations {
+        alloc_meta.push(treasury_manager::AllocationTempData {
+            balance: state.balance,
+            unbondable: state.unbondable,
+            contract: a.contract,
+            alloc_type: a.alloc_type,
+            amount: a.amount,
+            tolerance: a.tolerance,
+            unbonding: Uint128::zero(),
+            unbond_priority: a.unbond_priority,
+            unbond_latency: a.unbond_latency,
+        });
+    }
+    for (meta, unbond) in waterfall::waterfall(alloc_meta, remaining) {
+        add_tranche(&mut immediate, &mut delayed, meta.unbond_latency, unbond);
+        remaining = remaining.saturating_sub(unbond);
+    }
+    delayed.sort_by_key(|t| t.days);
+
+    Ok(treasury_manager::QueryAnswer::UnbondEstimate {
+        immediate,
+        delayed,
+        unavailable: remaining,
+    })
+}
+
 pub fn allocations(deps: Deps, asset: Addr) -> StdResult<treasury_manager::QueryAnswer> {
     Ok(treasury_manager::QueryAnswer::Allocations {
         allocations: match ALLOCATIONS.may_load(deps.storage, asset)? {
@@ -1,6 +1,14 @@
 use shade_protocol::{
     c_std::{Addr, Uint128},
-    dao::treasury_manager::{AllocationMeta, Config, Holding, Metric},
+    dao::treasury_manager::{
+        AdapterAccounting,
+        AllocationHealth,
+        AllocationMeta,
+        Config,
+        HealthPolicy,
+        Holding,
+        Metric,
+    },
     secret_storage_plus::{Item, Map},
     snip20::helpers::Snip20Asset,
     utils::storage::plus::period_storage::PeriodStorage,
@@ -16,6 +24,11 @@ pub const ALLOCATIONS: Map<Addr, Vec<AllocationMeta>> = Map::new("allocations");
 pub const HOLDERS: Item<Vec<Addr>> = Item::new("holders");
 pub const HOLDING: Map<Addr, Holding> = Map::new("holding");
 pub const UNBONDINGS: Map<Addr, Uint128> = Map::new("unbondings");
+// { asset: [adapter accounting] }
+pub const ACCOUNTING: Map<Addr, Vec<AdapterAccounting>> = Map::new("accounting");
+// { asset: [allocation health] }
+pub const HEALTH: Map<Addr, Vec<AllocationHealth>> = Map::new("health");
+pub const HEALTH_POLICY: Item<HealthPolicy> = Item::new("health_policy");
 
 pub const METRICS: PeriodStorage<Metric> =
     PeriodStorage::new("metrics-all", "metrics-recent", "metrics-timed");
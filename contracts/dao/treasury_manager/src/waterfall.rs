@@ -0,0 +1,147 @@
+use itertools::{Either, Itertools};
+use shade_protocol::{
+    c_std::Uint128,
+    dao::treasury_manager::{AllocationTempData, AllocationType},
+};
+
+/// Splits an unbond across allocations sharing a priority
+/// - extra tokens held by amount adapters are unbonded first
+/// - then portion adapters proportional to their unbondable balance
+/// - then amount adapters proportional to their allocation
+fn split(
+    tier: Vec<AllocationTempData>,
+    unbond_amount: Uint128,
+) -> Vec<(AllocationTempData, Uint128)> {
+    let mut unbonds = vec![];
+
+    let (amounts, portions): (Vec<AllocationTempData>, Vec<AllocationTempData>) =
+        tier.into_iter().partition_map(|a| match a.alloc_type {
+            AllocationType::Amount => Either::Left(a),
+            AllocationType::Portion => Either::Right(a),
+        });
+    let portion_total: Uint128 = portions.iter().map(|p| p.balance).sum();
+
+    let mut total_amount_unbonding = Uint128::zero();
+    let mut unbond_amounts = vec![];
+
+    // unbond the extra tokens from the amount adapters
+    for meta in amounts.iter() {
+        if meta.unbondable > meta.amount {
+            total_amount_unbonding += meta.unbondable - meta.amount;
+            unbond_amounts.push(meta.unbondable - meta.amount);
+        } else {
+            unbond_amounts.push(Uint128::zero())
+        }
+    }
+
+    // the extra tokens from the amount adapters covers the unbond request
+    if unbond_amount == total_amount_unbonding {
+        return amounts.into_iter().zip(unbond_amounts).collect();
+    } else if unbond_amount < total_amount_unbonding {
+        // if the extra tokens are greater than the unbond request, unbond proportionally to the
+        // extra tokens available
+        let mut modified_total_amount_unbonding = Uint128::zero();
+        for (i, meta) in amounts.iter().enumerate() {
+            unbond_amounts[i] =
+                unbond_amount.multiply_ratio(unbond_amounts[i], total_amount_unbonding);
+            modified_total_amount_unbonding += unbond_amounts[i];
+            // avoid off by one error
+            if i == amounts.len() - 1
+                && modified_total_amount_unbonding < unbond_amount
+                && unbond_amounts[i] + Uint128::new(1) <= meta.unbondable
+            {
+                unbond_amounts[i] += Uint128::new(1);
+            }
+        }
+        return amounts.into_iter().zip(unbond_amounts).collect();
+    }
+
+    // if portion total > unbond - tot, we know the portion adapters can cover the rest
+    if unbond_amount - total_amount_unbonding < portion_total {
+        let amount_adapt_tot_unbonding = total_amount_unbonding;
+        /* For each portion adapter, unbond the amount proportional to its portion of the total
+         * balance
+         */
+        for (i, meta) in portions.iter().enumerate() {
+            let mut unbond_from_portion = (unbond_amount - amount_adapt_tot_unbonding)
+                .multiply_ratio(meta.unbondable, portion_total);
+            total_amount_unbonding += unbond_from_portion;
+            // Avoid off by 1 error
+            if i == portions.len() - 1
+                && total_amount_unbonding < unbond_amount
+                && unbond_from_portion + Uint128::new(1) <= meta.unbondable
+            {
+                unbond_from_portion += Uint128::new(1);
+            }
+            unbonds.push((meta.clone(), unbond_from_portion));
+        }
+        unbonds.extend(amounts.into_iter().zip(unbond_amounts));
+        return unbonds;
+    }
+
+    // Otherwise we need to unbond everything from the portion adapters and go back to the
+    // amount adapters
+    for meta in portions {
+        total_amount_unbonding += meta.unbondable;
+        let unbondable = meta.unbondable;
+        unbonds.push((meta, unbondable));
+    }
+    // unbonding everything from the portion adapters covers the request
+    if total_amount_unbonding != unbond_amount {
+        // unbond token amounts proportional to the ratio of the allocation of the adapter and
+        // the sum of the amount allocaitons
+        let amount_alloc: Uint128 = amounts.iter().map(|a| a.amount).sum();
+        let mut modified_total_amount_unbonding = total_amount_unbonding;
+        for (i, meta) in amounts.iter().enumerate() {
+            unbond_amounts[i] +=
+                (unbond_amount - total_amount_unbonding).multiply_ratio(meta.amount, amount_alloc);
+
+            modified_total_amount_unbonding += meta.unbondable;
+            // this makes sure that the entire unbond request is fuffiled
+            if i == amounts.len() - 1
+                && modified_total_amount_unbonding < unbond_amount
+                && unbond_amount - modified_total_amount_unbonding
+                    < meta.unbondable - unbond_amounts[i]
+            {
+                unbond_amounts[i] += unbond_amount - total_amount_unbonding;
+            }
+        }
+    }
+    unbonds.extend(amounts.into_iter().zip(unbond_amounts));
+    unbonds
+}
+
+/// Fills an unbond from allocations in priority order,
+/// each priority is drained completely before the next is touched
+pub fn waterfall(
+    mut allocations: Vec<AllocationTempData>,
+    unbond_amount: Uint128,
+) -> Vec<(AllocationTempData, Uint128)> {
+    allocations.sort_by_key(|a| a.unbond_priority);
+
+    let mut unbonds = vec![];
+    let mut remaining = unbond_amount;
+    for (_, tier) in &allocations.into_iter().group_by(|a| a.unbond_priority) {
+        if remaining.is_zero() {
+            break;
+        }
+        let tier: Vec<AllocationTempData> = tier.collect();
+        let available: Uint128 = tier.iter().map(|a| a.unbondable).sum();
+
+        if remaining >= available {
+            remaining -= available;
+            unbonds.extend(tier.into_iter().map(|a| {
+                let unbondable = a.unbondable;
+                (a, unbondable)
+            }));
+        } else {
+            unbonds.extend(split(tier, remaining));
+            remaining = Uint128::zero();
+        }
+    }
+
+    unbonds
+        .into_iter()
+        .filter(|(_, amount)| !amount.is_zero())
+        .collect()
+}
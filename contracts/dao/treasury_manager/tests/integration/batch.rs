@@ -69,6 +69,7 @@ fn batch_balance_test(balances: Vec<Uint128>) {
                 enable_mint: Some(false),
                 enable_burn: Some(false),
                 enable_transfer: Some(true),
+                max_supply: None,
             }),
             query_auth: None,
         }
@@ -94,6 +95,8 @@ fn batch_balance_test(balances: Vec<Uint128>) {
             amount: bal,
             msg: None,
             memo: None,
+            decoys: None,
+            entropy: None,
             padding: None,
         }
         .test_exec(&token, &mut app, admin.clone(), &[])
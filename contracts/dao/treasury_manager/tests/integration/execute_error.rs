@@ -70,6 +70,8 @@ pub fn execute_error() {
             Uint128::new(1),
             Uint128::new(10u128.pow(18u32)),
             0,
+            0,
+            0,
         )
         .is_ok()
     );
@@ -85,6 +87,8 @@ pub fn execute_error() {
             Uint128::new(10u128.pow(18u32)),
             Uint128::new(1),
             0,
+            0,
+            0,
         )
         .is_ok()
     );
@@ -0,0 +1,414 @@
+use shade_multi_test::interfaces::{
+    dao::{self, init_dao, mock_adapter_sub_tokens},
+    treasury_manager,
+    utils::{DeployedContracts, SupportedContracts},
+};
+use shade_protocol::{
+    c_std::Uint128,
+    contract_interfaces::dao::{
+        treasury::AllowanceType,
+        treasury_manager::{AllocationType, Health},
+    },
+    multi_test::App,
+    utils::cycle::Cycle,
+};
+
+#[test]
+pub fn circuit_breaker() {
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1000),
+        "SSCRT",
+        vec![AllowanceType::Amount],
+        vec![Cycle::Constant],
+        vec![Uint128::new(1000)],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Amount, AllocationType::Amount]],
+        vec![vec![Uint128::new(300), Uint128::new(300)]],
+        vec![vec![Uint128::zero(), Uint128::zero()]],
+        true,
+        true,
+    )
+    .unwrap();
+
+    // 10% drop tolerated
+    treasury_manager::set_health_policy_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        Uint128::new(10u128.pow(17)),
+        false,
+    )
+    .unwrap();
+    assert!(
+        treasury_manager::set_health_policy_exec(
+            &mut app,
+            "admin",
+            &contracts,
+            SupportedContracts::TreasuryManager(0),
+            Uint128::new(10u128.pow(18)),
+            false,
+        )
+        .is_err()
+    );
+
+    let healths = treasury_manager::allocation_health_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+    )
+    .unwrap();
+    assert_eq!(healths.len(), 2);
+    for h in healths {
+        assert_eq!(h.health, Health::Healthy);
+        assert_eq!(h.expected_balance, Uint128::new(300));
+    }
+
+    // Adapter 0 loses a third of its funds
+    mock_adapter_sub_tokens(
+        &mut app,
+        "admin",
+        &contracts,
+        Uint128::new(100),
+        SupportedContracts::MockAdapter(0),
+    )
+    .unwrap();
+    treasury_manager::update_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+    )
+    .unwrap();
+
+    let adapter_0 = contracts
+        .get(&SupportedContracts::MockAdapter(0))
+        .unwrap()
+        .address
+        .clone();
+    let healths = treasury_manager::allocation_health_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+    )
+    .unwrap();
+    for h in healths {
+        if h.contract == adapter_0 {
+            assert_eq!(h.health, Health::Degraded);
+            assert!(h.reason.is_some());
+        } else {
+            assert_eq!(h.health, Health::Healthy);
+        }
+    }
+
+    // Degraded allocation is not refilled
+    treasury_manager::update_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+    )
+    .unwrap();
+    assert_eq!(
+        dao::balance_query(
+            &app,
+            &contracts,
+            "SSCRT",
+            SupportedContracts::MockAdapter(0)
+        )
+        .unwrap(),
+        Uint128::new(200)
+    );
+    assert_eq!(
+        dao::balance_query(
+            &app,
+            &contracts,
+            "SSCRT",
+            SupportedContracts::MockAdapter(1)
+        )
+        .unwrap(),
+        Uint128::new(300)
+    );
+
+    // Emergency unbond pulls everything out
+    treasury_manager::set_health_policy_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        Uint128::new(10u128.pow(17)),
+        true,
+    )
+    .unwrap();
+    treasury_manager::update_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+    )
+    .unwrap();
+    assert_eq!(
+        dao::balance_query(
+            &app,
+            &contracts,
+            "SSCRT",
+            SupportedContracts::MockAdapter(0)
+        )
+        .unwrap(),
+        Uint128::zero()
+    );
+
+    // Only degraded allocations can be reset
+    assert!(
+        treasury_manager::reset_health_exec(
+            &mut app,
+            "admin",
+            &contracts,
+            SupportedContracts::TreasuryManager(0),
+            "SSCRT",
+            SupportedContracts::MockAdapter(1),
+        )
+        .is_err()
+    );
+    treasury_manager::reset_health_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+        SupportedContracts::MockAdapter(0),
+    )
+    .unwrap();
+    treasury_manager::set_health_policy_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        Uint128::new(10u128.pow(17)),
+        false,
+    )
+    .unwrap();
+    treasury_manager::update_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+    )
+    .unwrap();
+    assert_eq!(
+        dao::balance_query(
+            &app,
+            &contracts,
+            "SSCRT",
+            SupportedContracts::MockAdapter(0)
+        )
+        .unwrap(),
+        Uint128::new(300)
+    );
+}
+
+#[test]
+pub fn unbond_then_claim() {
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1000),
+        "SSCRT",
+        vec![AllowanceType::Amount],
+        vec![Cycle::Constant],
+        vec![Uint128::new(1000)],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Amount, AllocationType::Amount]],
+        vec![vec![Uint128::new(300), Uint128::new(300)]],
+        vec![vec![Uint128::zero(), Uint128::zero()]],
+        true,
+        true,
+    )
+    .unwrap();
+
+    treasury_manager::set_health_policy_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        Uint128::new(10u128.pow(17)),
+        false,
+    )
+    .unwrap();
+
+    // expected balances should follow the adapters' balances
+    let assert_expected = |app: &App| {
+        let healths = treasury_manager::allocation_health_query(
+            app,
+            &contracts,
+            SupportedContracts::TreasuryManager(0),
+            "SSCRT",
+        )
+        .unwrap();
+        for i in 0..2 {
+            let adapter = SupportedContracts::MockAdapter(i);
+            let address = contracts.get(&adapter).unwrap().address.clone();
+            let h = healths.iter().find(|h| h.contract == address).unwrap();
+            assert_eq!(h.health, Health::Healthy);
+            assert_eq!(
+                h.expected_balance,
+                dao::balance_query(app, &contracts, "SSCRT", adapter).unwrap()
+            );
+        }
+    };
+
+    // Unbonding funds stay in the adapter until claimed
+    treasury_manager::unbond_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+        Uint128::new(300),
+    )
+    .unwrap();
+    assert_expected(&app);
+
+    for i in 0..2 {
+        dao::mock_adapter_complete_unbonding(
+            &mut app,
+            "admin",
+            &contracts,
+            SupportedContracts::MockAdapter(i),
+        )
+        .unwrap();
+    }
+    treasury_manager::claim_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+    )
+    .unwrap();
+    assert_expected(&app);
+}
+
+#[test]
+pub fn degraded_unbond() {
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1000),
+        "SSCRT",
+        vec![AllowanceType::Amount],
+        vec![Cycle::Constant],
+        vec![Uint128::new(1000)],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Amount, AllocationType::Amount]],
+        vec![vec![Uint128::new(300), Uint128::new(300)]],
+        vec![vec![Uint128::zero(), Uint128::zero()]],
+        true,
+        true,
+    )
+    .unwrap();
+
+    treasury_manager::set_health_policy_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        Uint128::new(10u128.pow(17)),
+        false,
+    )
+    .unwrap();
+
+    // Adapter 0 loses a third of its funds & is degraded
+    mock_adapter_sub_tokens(
+        &mut app,
+        "admin",
+        &contracts,
+        Uint128::new(100),
+        SupportedContracts::MockAdapter(0),
+    )
+    .unwrap();
+    treasury_manager::update_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+    )
+    .unwrap();
+
+    let adapter_0 = contracts
+        .get(&SupportedContracts::MockAdapter(0))
+        .unwrap()
+        .address
+        .clone();
+    let pnl = treasury_manager::pnl_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+    )
+    .unwrap();
+    let pnl_0 = pnl.iter().find(|p| p.adapter == adapter_0).unwrap();
+    assert_eq!(pnl_0.bonded, Uint128::new(200));
+    assert_eq!(pnl_0.unrealized_losses, Uint128::new(100));
+
+    // Only the healthy adapter is counted on to cover unbonds
+    let (immediate, delayed, unavailable) = treasury_manager::unbond_estimate_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+        Uint128::new(500),
+    )
+    .unwrap();
+    assert_eq!(immediate, Uint128::new(300));
+    assert!(delayed.is_empty());
+    assert_eq!(unavailable, Uint128::new(200));
+
+    treasury_manager::unbond_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+        Uint128::new(300),
+    )
+    .unwrap();
+    assert_eq!(
+        dao::balance_query(
+            &app,
+            &contracts,
+            "SSCRT",
+            SupportedContracts::MockAdapter(1)
+        )
+        .unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        dao::balance_query(
+            &app,
+            &contracts,
+            "SSCRT",
+            SupportedContracts::MockAdapter(0)
+        )
+        .unwrap(),
+        Uint128::new(200)
+    );
+}
@@ -88,6 +88,8 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, holder.clone(), &[])
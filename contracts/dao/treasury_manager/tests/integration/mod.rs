@@ -1,9 +1,12 @@
 pub mod batch;
 pub mod config;
 pub mod execute_error;
+pub mod health;
 pub mod holder_integration;
 pub mod multiple_holders;
+pub mod pnl;
 pub mod query;
 pub mod scrt_staking_integration;
 pub mod tm_unbond;
 pub mod tolerance;
+pub mod unbond_priority;
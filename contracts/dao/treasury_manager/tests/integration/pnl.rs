@@ -0,0 +1,192 @@
+use shade_multi_test::interfaces::{
+    dao::{init_dao, mock_adapter_sub_tokens},
+    snip20,
+    treasury_manager,
+    utils::{DeployedContracts, SupportedContracts},
+};
+use shade_protocol::{
+    c_std::Uint128,
+    contract_interfaces::dao::{
+        treasury::AllowanceType,
+        treasury_manager::{Action, AllocationType, Context},
+    },
+    multi_test::App,
+    utils::{cycle::Cycle, storage::plus::period_storage::Period},
+};
+
+#[test]
+pub fn pnl() {
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1000),
+        "SSCRT",
+        vec![AllowanceType::Amount],
+        vec![Cycle::Constant],
+        vec![Uint128::new(1000)],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Amount]],
+        vec![vec![Uint128::new(500)]],
+        vec![vec![Uint128::zero()]],
+        true,
+        true,
+    )
+    .unwrap();
+
+    let adapter = contracts
+        .get(&SupportedContracts::MockAdapter(0))
+        .unwrap()
+        .address
+        .clone();
+
+    let pnl = treasury_manager::pnl_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+    )
+    .unwrap();
+    assert_eq!(pnl.len(), 1);
+    assert_eq!(pnl[0].adapter, adapter);
+    assert_eq!(pnl[0].cost_basis, Uint128::new(500));
+    assert_eq!(pnl[0].bonded, Uint128::new(500));
+    assert_eq!(pnl[0].unrealized_gains, Uint128::zero());
+    assert_eq!(pnl[0].unrealized_losses, Uint128::zero());
+
+    let totals = treasury_manager::period_report_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+        None,
+        None,
+        Period::Day,
+    )
+    .unwrap();
+    assert!(totals.iter().any(|t| t.action == Action::SendFunds
+        && t.context == Context::Update
+        && t.amount == Uint128::new(500)));
+
+    // Adapter loses 100
+    mock_adapter_sub_tokens(
+        &mut app,
+        "admin",
+        &contracts,
+        Uint128::new(100),
+        SupportedContracts::MockAdapter(0),
+    )
+    .unwrap();
+    let pnl = treasury_manager::pnl_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+    )
+    .unwrap();
+    assert_eq!(pnl[0].bonded, Uint128::new(400));
+    assert_eq!(pnl[0].unrealized_losses, Uint128::new(100));
+
+    // Adapter gains 300
+    snip20::send_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        adapter.to_string(),
+        Uint128::new(300),
+        None,
+    )
+    .unwrap();
+    let pnl = treasury_manager::pnl_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+    )
+    .unwrap();
+    assert_eq!(pnl[0].bonded, Uint128::new(700));
+    assert_eq!(pnl[0].cost_basis, Uint128::new(500));
+    assert_eq!(pnl[0].unrealized_gains, Uint128::new(200));
+    assert_eq!(pnl[0].unrealized_losses, Uint128::zero());
+    assert_eq!(pnl[0].realized_gains, Uint128::zero());
+}
+
+#[test]
+pub fn pnl_unbond() {
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    // allowance matches the allocation so the manager holds no reserves
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1000),
+        "SSCRT",
+        vec![AllowanceType::Amount],
+        vec![Cycle::Constant],
+        vec![Uint128::new(500)],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Amount]],
+        vec![vec![Uint128::new(500)]],
+        vec![vec![Uint128::zero()]],
+        true,
+        true,
+    )
+    .unwrap();
+
+    let adapter = contracts
+        .get(&SupportedContracts::MockAdapter(0))
+        .unwrap()
+        .address
+        .clone();
+
+    // Adapter gains 200
+    snip20::send_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        adapter.to_string(),
+        Uint128::new(200),
+        None,
+    )
+    .unwrap();
+
+    // 350 of the 700 bonded releases half of the 500 basis
+    treasury_manager::unbond_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+        Uint128::new(350),
+    )
+    .unwrap();
+    let pnl = treasury_manager::pnl_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+    )
+    .unwrap();
+    assert_eq!(pnl[0].cost_basis, Uint128::new(250));
+    assert_eq!(pnl[0].realized_gains, Uint128::new(100));
+    assert_eq!(pnl[0].realized_losses, Uint128::zero());
+
+    let totals = treasury_manager::period_report_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+        None,
+        None,
+        Period::Day,
+    )
+    .unwrap();
+    assert!(totals.iter().any(|t| t.action == Action::Unbond
+        && t.context == Context::Unbond
+        && t.amount == Uint128::new(350)));
+}
@@ -64,6 +64,7 @@ fn single_holder_scrt_staking_adapter(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
@@ -140,6 +141,8 @@ fn single_holder_scrt_staking_adapter(
             alloc_type,
             amount: alloc_amount,
             tolerance: Uint128::zero(),
+            unbond_priority: 0,
+            unbond_latency: 21,
         },
     }
     .test_exec(&manager, &mut app, admin.clone(), &[])
@@ -170,6 +173,8 @@ fn single_holder_scrt_staking_adapter(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, holder.clone(), &[])
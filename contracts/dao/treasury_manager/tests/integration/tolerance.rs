@@ -55,6 +55,7 @@ fn underfunded_tolerance(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
         query_auth: None,
     }
@@ -114,6 +115,8 @@ fn underfunded_tolerance(
             amount: allocation,
             // 100% (adapter balance will 2x before unbond)
             tolerance,
+            unbond_priority: 0,
+            unbond_latency: 0,
         },
     }
     .test_exec(&manager, &mut app, admin.clone(), &[])
@@ -126,6 +129,8 @@ fn underfunded_tolerance(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -158,6 +163,8 @@ fn underfunded_tolerance(
         amount: added,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -273,6 +280,7 @@ fn overfunded_tolerance(
             enable_mint: Some(false),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
         query_auth: None,
     }
@@ -332,6 +340,8 @@ fn overfunded_tolerance(
             amount: allocation,
             // 100% (adapter balance will 2x before unbond)
             tolerance,
+            unbond_priority: 0,
+            unbond_latency: 0,
         },
     }
     .test_exec(&manager, &mut app, admin.clone(), &[])
@@ -344,6 +354,8 @@ fn overfunded_tolerance(
         amount: deposit,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token, &mut app, admin.clone(), &[])
@@ -379,6 +391,8 @@ fn overfunded_tolerance(
             amount: reduced,
             // 100% (adapter balance will 2x before unbond)
             tolerance,
+            unbond_priority: 0,
+            unbond_latency: 0,
         },
     }
     .test_exec(&manager, &mut app, admin.clone(), &[])
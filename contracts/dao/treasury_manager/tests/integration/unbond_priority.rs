@@ -0,0 +1,109 @@
+use shade_multi_test::interfaces::{
+    dao::{self, init_dao},
+    treasury_manager,
+    utils::{DeployedContracts, SupportedContracts},
+};
+use shade_protocol::{
+    c_std::Uint128,
+    contract_interfaces::dao::{
+        treasury::AllowanceType,
+        treasury_manager::{AllocationType, UnbondTranche},
+    },
+    multi_test::App,
+    utils::cycle::Cycle,
+};
+
+#[test]
+pub fn unbond_priority() {
+    let mut app = App::default();
+    let mut contracts = DeployedContracts::new();
+    init_dao(
+        &mut app,
+        "admin",
+        &mut contracts,
+        Uint128::new(1000),
+        "SSCRT",
+        vec![AllowanceType::Amount],
+        vec![Cycle::Constant],
+        vec![Uint128::new(1000)],
+        vec![Uint128::zero()],
+        vec![vec![AllocationType::Amount, AllocationType::Amount]],
+        vec![vec![Uint128::new(300), Uint128::new(300)]],
+        vec![vec![Uint128::zero(), Uint128::zero()]],
+        true,
+        true,
+    )
+    .unwrap();
+
+    // Adapter 0 is slow to unbond & should only be used once adapter 1 is drained
+    treasury_manager::allocate_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        Some("0".to_string()),
+        &SupportedContracts::MockAdapter(0),
+        AllocationType::Amount,
+        Uint128::new(300),
+        Uint128::zero(),
+        1,
+        21,
+        0,
+    )
+    .unwrap();
+
+    let (immediate, delayed, unavailable) = treasury_manager::unbond_estimate_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+        Uint128::new(500),
+    )
+    .unwrap();
+    assert_eq!(immediate, Uint128::new(300));
+    assert_eq!(delayed, vec![UnbondTranche {
+        days: 21,
+        amount: Uint128::new(200),
+    }]);
+    assert_eq!(unavailable, Uint128::zero());
+
+    let (_, _, unavailable) = treasury_manager::unbond_estimate_query(
+        &app,
+        &contracts,
+        SupportedContracts::TreasuryManager(0),
+        "SSCRT",
+        Uint128::new(700),
+    )
+    .unwrap();
+    assert_eq!(unavailable, Uint128::new(100));
+
+    treasury_manager::unbond_exec(
+        &mut app,
+        "admin",
+        &contracts,
+        "SSCRT",
+        SupportedContracts::TreasuryManager(0),
+        Uint128::new(400),
+    )
+    .unwrap();
+    assert_eq!(
+        dao::balance_query(
+            &app,
+            &contracts,
+            "SSCRT",
+            SupportedContracts::MockAdapter(1)
+        )
+        .unwrap(),
+        Uint128::zero()
+    );
+    assert_eq!(
+        dao::balance_query(
+            &app,
+            &contracts,
+            "SSCRT",
+            SupportedContracts::MockAdapter(0)
+        )
+        .unwrap(),
+        Uint128::new(200)
+    );
+}
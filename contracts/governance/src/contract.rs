@@ -11,12 +11,15 @@ use crate::{
         migration::{try_migrate, try_migrate_data, try_receive_migration_data},
         profile::{try_add_profile, try_set_profile},
         proposal::{
+            proposal_reply,
             try_cancel,
             try_claim_funding,
             try_receive_funding,
             try_receive_vote,
+            try_retry,
             try_trigger,
             try_update,
+            try_veto_queued,
         },
         try_set_config,
         try_set_runtime_state,
@@ -48,7 +51,7 @@ use shade_protocol::{
         QueryMsg,
         MSG_VARIABLE,
     },
-    governance::{errors::Error, AuthQuery, QueryData, RuntimeState},
+    governance::{errors::Error, AuthQuery, QueryData, RuntimeState, VotingPower},
     query_auth::helpers::{authenticate_permit, authenticate_vk, PermitAuthentication},
     snip20::helpers::register_receive,
     utils::{
@@ -97,6 +100,7 @@ pub fn instantiate(
         query: msg.query_auth,
         treasury: msg.treasury,
         vote_token: msg.vote_token.clone(),
+        voting_power: msg.voting_power.unwrap_or(VotingPower::Live),
         funding_token: msg.funding_token.clone(),
         migrated_from,
         migrated_to: None,
@@ -205,6 +209,8 @@ pub fn instantiate(
 pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
     match msg {
         ExecuteMsg::Trigger { .. } // Will be deprecated
+        | ExecuteMsg::Retry { .. }
+        | ExecuteMsg::VetoQueued { .. } // Gets halted
         | ExecuteMsg::Cancel { .. } // Will also be deprecated
         | ExecuteMsg::Update { .. } // Gets halted 
         | ExecuteMsg::Receive { .. } // Gets halted
@@ -226,6 +232,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 treasury,
                 vote_token,
                 funding_token,
+                voting_power,
                 ..
             } => try_set_config(
                 deps,
@@ -235,6 +242,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 treasury,
                 vote_token,
                 funding_token,
+                voting_power,
             ),
 
             ExecuteMsg::SetRuntimeState { state, .. } => {
@@ -243,6 +251,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
 
             // Proposals
             ExecuteMsg::Trigger { proposal, .. } => try_trigger(deps, env, info, proposal),
+            ExecuteMsg::Retry { proposal, .. } => try_retry(deps, env, info, proposal),
+            ExecuteMsg::VetoQueued { proposal, .. } => {
+                try_veto_queued(deps, env, info, proposal)
+            }
             ExecuteMsg::Cancel { proposal, .. } => try_cancel(deps, env, info, proposal),
             ExecuteMsg::Update { proposal, .. } => try_update(deps, env, info, proposal),
             ExecuteMsg::Receive {
@@ -372,13 +384,19 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
 }
 
 #[shd_entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     pad_query_result(
         match msg {
             QueryMsg::TotalProposals {} => to_binary(&query::total_proposals(deps)?),
 
             QueryMsg::Proposals { start, end } => to_binary(&query::proposals(deps, start, end)?),
 
+            QueryMsg::FilteredProposals {
+                filter,
+                cursor,
+                limit,
+            } => to_binary(&query::filtered_proposals(deps, filter, cursor, limit)?),
+
             QueryMsg::TotalAssemblies {} => to_binary(&query::total_assemblies(deps)?),
 
             QueryMsg::Assemblies { start, end } => to_binary(&query::assemblies(deps, start, end)?),
@@ -402,11 +420,11 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             QueryMsg::WithVK { user, key, query } => {
                 // Query VK info
                 let authenticator = Config::load(deps.storage)?.query;
-                if !authenticate_vk(user.clone(), key, &deps.querier, &authenticator)? {
+                if !authenticate_vk(user.clone(), key, &deps.querier, &env, &authenticator)? {
                     return Err(Error::bad_vk(vec![]));
                 }
 
-                auth_queries(deps, query, user)
+                auth_queries(deps, &env, query, user)
             }
 
             QueryMsg::WithPermit { permit, query } => {
@@ -419,14 +437,14 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     return Err(Error::bad_pkey(vec![]));
                 }
 
-                auth_queries(deps, query, res.sender)
+                auth_queries(deps, &env, query, res.sender)
             }
         },
         RESPONSE_BLOCK_SIZE,
     )
 }
 
-pub fn auth_queries(deps: Deps, msg: AuthQuery, user: Addr) -> StdResult<Binary> {
+pub fn auth_queries(deps: Deps, env: &Env, msg: AuthQuery, user: Addr) -> StdResult<Binary> {
     to_binary(&match msg {
         AuthQuery::Proposals { pagination } => query::user_proposals(deps, user, pagination)?,
         AuthQuery::AssemblyVotes { pagination } => {
@@ -434,11 +452,14 @@ pub fn auth_queries(deps: Deps, msg: AuthQuery, user: Addr) -> StdResult<Binary>
         }
         AuthQuery::Funding { pagination } => query::user_funding(deps, user, pagination)?,
         AuthQuery::Votes { pagination } => query::user_votes(deps, user, pagination)?,
+        AuthQuery::Delegations { pagination } => {
+            query::user_delegations(deps, env, user, pagination)?
+        }
+        AuthQuery::DelegatedPower {} => query::user_delegated_power(deps, env, user)?,
     })
 }
 
 const MIGRATION_REPLY: u64 = 0;
-// const PROPOSAL_REPLY: u64 = 1;
 const ADDRESS_ATTRIBUTE: &str = "instantiated-address";
 const CODE_HASH_ATTRIBUTE: &str = "instantiated-code-hash";
 #[shd_entry_point]
@@ -474,8 +495,8 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
             });
             config.save(deps.storage)?;
         }
-        // TODO: on receiving a response, subtract 1 and update that proposals status to failed
-        _ => return Err(Error::wrong_reply(vec![&msg.id.to_string()])),
+        // Every other ID belongs to a triggered proposal msg
+        id => return proposal_reply(deps, id, msg.result),
     }
 
     Ok(Response::new())
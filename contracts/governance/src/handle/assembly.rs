@@ -20,7 +20,6 @@ use shade_protocol::{
         stored_id::{UserID, ID},
         vote::Vote,
         ExecuteAnswer,
-        MSG_VARIABLE,
     },
     governance::errors::Error,
     utils::generic_response::ResponseStatus,
@@ -87,10 +86,6 @@ pub fn try_assembly_proposal(
     // Get assembly
     let assembly_data = authorize_assembly(deps.storage, &info, assembly_id)?;
 
-    // Get profile
-    // Check if assembly is enabled
-    let profile = Profile::data(deps.storage, assembly_data.profile)?;
-
     let status: Status;
 
     // Check if assembly voting
@@ -116,12 +111,9 @@ pub fn try_assembly_proposal(
             end: env.block.time.seconds() + vote_settings.deadline,
         }
     }
-    // Else push directly to passed
+    // Else push directly to passed, or queue it if timelocked
     else {
-        status = Status::Passed {
-            start: env.block.time.seconds(),
-            end: env.block.time.seconds() + profile.cancel_deadline,
-        }
+        status = Status::approved(deps.storage, assembly_data.profile, &env.block.time)?;
     }
 
     let processed_msgs: Option<Vec<ProposalMsg>>;
@@ -142,9 +134,10 @@ pub fn try_assembly_proposal(
                 }
             }
 
+            // Arguments are validated against their placeholder types
             let vars: Vec<String> = from_binary(&msg.msg)?;
             let binary_msg =
-                Binary::from(assembly_msg.msg.create_msg(vars, MSG_VARIABLE)?.as_bytes());
+                Binary::from(assembly_msg.msg.create_typed_msg(deps.api, vars)?.as_bytes());
 
             new_msgs.push(ProposalMsg {
                 target: msg.target,
@@ -163,6 +156,7 @@ pub fn try_assembly_proposal(
         title,
         metadata,
         msgs: processed_msgs,
+        msg_outcomes: None,
         assembly: assembly_id,
         assembly_vote_tally: None,
         public_vote_tally: None,
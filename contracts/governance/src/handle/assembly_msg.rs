@@ -4,7 +4,6 @@ use shade_protocol::{
         assembly::AssemblyMsg,
         stored_id::ID,
         ExecuteAnswer,
-        MSG_VARIABLE,
     },
     governance::errors::Error,
     utils::{flexible_msg::FlexibleMsg, generic_response::ResponseStatus},
@@ -33,7 +32,7 @@ pub fn try_add_assembly_msg(
     AssemblyMsg {
         name,
         assemblies,
-        msg: FlexibleMsg::new(msg, MSG_VARIABLE),
+        msg: FlexibleMsg::new_typed(msg)?,
     }
     .save(deps.storage, id)?;
 
@@ -63,7 +62,7 @@ pub fn try_set_assembly_msg(
     }
 
     if let Some(msg) = msg {
-        assembly_msg.msg = FlexibleMsg::new(msg, MSG_VARIABLE);
+        assembly_msg.msg = FlexibleMsg::new_typed(msg)?;
     }
 
     if let Some(assemblies) = assemblies {
@@ -0,0 +1,89 @@
+use crate::handle::proposal::staked_at;
+use shade_protocol::{
+    c_std::{to_binary, Addr, DepsMut, Env, Response, StdResult},
+    contract_interfaces::governance::{
+        vote::{Delegation, DelegationMsg, MAX_DELEGATORS},
+        Config,
+        ExecuteAnswer,
+        VotingPower,
+    },
+    governance::errors::Error,
+    utils::{
+        generic_response::ResponseStatus,
+        storage::plus::{ItemStorage, MapStorage},
+    },
+};
+
+/// Handles a delegation received through the vote token, the delegated power
+/// is the delegator's stake when each proposal started
+pub fn try_receive_delegation(
+    deps: DepsMut,
+    env: &Env,
+    token: Addr,
+    sender: Addr,
+    msg: DelegationMsg,
+) -> StdResult<Response> {
+    // Remove the previous delegation
+    let old_delegation = Delegation::may_load(deps.storage, (token.clone(), sender.clone()))?;
+    if let Some(old_delegation) = &old_delegation {
+        let mut delegators = Delegation::delegators(deps.storage, &token, &old_delegation.to)?;
+        delegators.retain(|delegator| delegator != &sender);
+        Delegation::save_delegators(deps.storage, &token, &old_delegation.to, &delegators)?;
+        Delegation::save_auth(deps.storage, &token, &sender, None)?;
+        Delegation::remove(deps.storage, (token.clone(), sender.clone()));
+    }
+
+    let answer = match msg {
+        DelegationMsg::Delegate { to, auth } => {
+            if to == sender {
+                return Err(Error::self_delegation(vec![]));
+            }
+
+            // Exposed balances can't be looked up again once tokens move,
+            // so only snapshotted power is delegated
+            let staking = match Config::load(deps.storage)?.voting_power {
+                VotingPower::Live => return Err(Error::live_delegation(vec![])),
+                VotingPower::Snapshot { staking } => staking,
+            };
+            let auth = match auth {
+                None => return Err(Error::snapshot_auth(vec![])),
+                Some(auth) => auth,
+            };
+            staked_at(
+                deps.as_ref(),
+                &staking,
+                auth.clone(),
+                &sender,
+                env.block.time.seconds(),
+            )?;
+            Delegation::save_auth(deps.storage, &token, &sender, Some(&auth))?;
+
+            let mut delegators = Delegation::delegators(deps.storage, &token, &to)?;
+            if delegators.len() >= MAX_DELEGATORS {
+                return Err(Error::too_many_delegators(vec![
+                    to.as_str(),
+                    &MAX_DELEGATORS.to_string(),
+                ]));
+            }
+            delegators.push(sender.clone());
+            Delegation::save_delegators(deps.storage, &token, &to, &delegators)?;
+
+            Delegation { to }.save(deps.storage, (token, sender))?;
+
+            ExecuteAnswer::Delegate {
+                status: ResponseStatus::Success,
+            }
+        }
+        DelegationMsg::Undelegate {} => {
+            if old_delegation.is_none() {
+                return Err(Error::not_delegating(vec![]));
+            }
+
+            ExecuteAnswer::Undelegate {
+                status: ResponseStatus::Success,
+            }
+        }
+    };
+
+    Ok(Response::new().set_data(to_binary(&answer)?))
+}
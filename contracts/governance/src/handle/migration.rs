@@ -51,6 +51,7 @@ pub fn try_migrate(
                 assemblies: None,
                 funding_token: config.funding_token,
                 vote_token: config.vote_token,
+                voting_power: Some(config.voting_power),
                 migrator: Some(MigrationInit {
                     source: Contract {
                         address: env.contract.address,
@@ -1,6 +1,6 @@
 use shade_protocol::{
     c_std::{to_binary, Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage, SubMsg},
-    contract_interfaces::governance::{Config, ExecuteAnswer, RuntimeState},
+    contract_interfaces::governance::{Config, ExecuteAnswer, RuntimeState, VotingPower},
     governance::{
         assembly::{Assembly, AssemblyData},
         errors::Error,
@@ -13,6 +13,7 @@ use shade_protocol::{
 pub mod assembly;
 pub mod assembly_msg;
 pub mod contract;
+pub mod delegation;
 pub mod migration;
 pub mod profile;
 pub mod proposal;
@@ -77,6 +78,7 @@ pub fn try_set_config(
     treasury: Option<Addr>,
     vote_token: Option<Contract>,
     funding_token: Option<Contract>,
+    voting_power: Option<VotingPower>,
 ) -> StdResult<Response> {
     let mut messages = vec![];
     let mut config = Config::load(deps.storage)?;
@@ -100,6 +102,10 @@ pub fn try_set_config(
         )?));
     }
 
+    if let Some(voting_power) = voting_power {
+        config.voting_power = voting_power;
+    }
+
     if let Some(treasury) = treasury {
         config.treasury = treasury;
     }
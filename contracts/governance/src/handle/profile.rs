@@ -63,6 +63,12 @@ pub fn try_set_profile(
         profile.token = Some(token.update_profile(&profile.token)?)
     }
 
+    if new_profile.disable_timelock {
+        profile.timelock = None;
+    } else if let Some(timelock) = new_profile.timelock {
+        profile.timelock = Some(timelock.update_profile(&profile.timelock)?)
+    }
+
     if let Some(cancel_deadline) = new_profile.cancel_deadline {
         profile.cancel_deadline = cancel_deadline;
     }
@@ -1,99 +1,272 @@
-use crate::handle::assembly_state_valid;
+use crate::handle::{assembly_state_valid, authorize_assembly, delegation::try_receive_delegation};
 use shade_protocol::{
     c_std::{
         from_binary,
         to_binary,
         Addr,
         Binary,
+        Deps,
         DepsMut,
         Env,
         MessageInfo,
         Response,
         StdResult,
+        Storage,
         SubMsg,
+        SubMsgResult,
         Uint128,
         WasmMsg,
     },
     contract_interfaces::{
+        basic_staking,
         governance::{
             assembly::Assembly,
             contract::AllowedContract,
-            profile::{Count, Profile, VoteProfile},
-            proposal::{Funding, Proposal, Status},
+            profile::{Count, Profile, TimelockProfile, VoteProfile, Weighting},
+            proposal::{DelegatedVoter, Funding, MsgOutcome, Proposal, Status},
             stored_id::UserID,
-            vote::{ReceiveBalanceMsg, TalliedVotes, Vote},
+            vote::{Delegation, DelegationMsg, ReceiveBalanceMsg, TalliedVotes, Vote},
             Config,
             ExecuteAnswer,
+            VotingPower,
         },
         staking::snip20_staking,
     },
     governance::errors::Error,
     snip20::helpers::send_msg,
-    utils::{asset::Contract, generic_response::ResponseStatus, storage::plus::ItemStorage, Query},
+    utils::{
+        asset::Contract,
+        generic_response::ResponseStatus,
+        storage::plus::{ItemStorage, MapStorage},
+        Query,
+    },
 };
 
+/// Reply IDs are offset by one so that 0 is kept for migrations
+pub fn proposal_reply_id(proposal: u32, msg_index: u32) -> u64 {
+    (((proposal as u64) << 32) | msg_index as u64) + 1
+}
+
+fn proposal_msgs(storage: &dyn Storage, proposal: u32, retry: bool) -> StdResult<Vec<SubMsg>> {
+    let mut messages = vec![];
+
+    if let Some(prop_msgs) = Proposal::msg(storage, proposal)? {
+        for (i, prop_msg) in prop_msgs.iter().enumerate() {
+            // Only failed or unexecuted msgs get retried
+            if retry {
+                if let MsgOutcome::Success = Proposal::msg_outcome(storage, proposal, i as u32)? {
+                    continue;
+                }
+            }
+
+            let contract = AllowedContract::data(storage, prop_msg.target)?.contract;
+            let msg = WasmMsg::Execute {
+                contract_addr: contract.address.into(),
+                code_hash: contract.code_hash,
+                msg: prop_msg.msg.clone(),
+                funds: prop_msg.send.clone(),
+            };
+            messages.push(SubMsg::reply_always(
+                msg,
+                proposal_reply_id(proposal, i as u32),
+            ));
+        }
+    }
+
+    Ok(messages)
+}
+
+/// Moves a queued proposal to passed once its timelock ends
+fn release_timelock(storage: &dyn Storage, proposal: u32, end: u64) -> StdResult<Status> {
+    let profile = Assembly::data(storage, Proposal::assembly(storage, proposal)?)?.profile;
+    Ok(Status::Passed {
+        start: end,
+        end: end + Profile::data(storage, profile)?.cancel_deadline,
+    })
+}
+
 pub fn try_trigger(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     proposal: u32,
 ) -> StdResult<Response> {
-    let mut messages = vec![];
+    let mut history = Proposal::status_history(deps.storage, proposal)?;
+    let mut status = Proposal::status(deps.storage, proposal)?;
+
+    // Queued proposals can be triggered once their timelock ends
+    if let Status::Queued { end, .. } = status {
+        if env.block.time.seconds() < end {
+            return Err(Error::timelocked(vec![&end.to_string()]));
+        }
+        history.push(status);
+        status = release_timelock(deps.storage, proposal, end)?;
+    }
 
-    let status = Proposal::status(deps.storage, proposal)?;
     if let Status::Passed { .. } = status {
-        let mut history = Proposal::status_history(deps.storage, proposal)?;
         history.push(status);
         Proposal::save_status_history(deps.storage, proposal, history)?;
+        // Replies will set the status to failed if any msg errors
         Proposal::save_status(deps.storage, proposal, Status::Success)?;
-
-        // Trigger the msg
-        let proposal_msg = Proposal::msg(deps.storage, proposal)?;
-        if let Some(prop_msgs) = proposal_msg {
-            for (_i, prop_msg) in prop_msgs.iter().enumerate() {
-                let contract = AllowedContract::data(deps.storage, prop_msg.target)?.contract;
-                let msg = WasmMsg::Execute {
-                    contract_addr: contract.address.into(),
-                    code_hash: contract.code_hash,
-                    msg: prop_msg.msg.clone(),
-                    funds: prop_msg.send.clone(),
-                };
-                // TODO: set to reply on error where ID is propID + 1
-                // TODO: set proposal status to success
-                messages.push(SubMsg::new(msg));
-            }
-        }
     } else {
         return Err(Error::not_passed(vec![]));
     }
 
     Ok(Response::new()
-        .add_submessages(messages)
+        .add_submessages(proposal_msgs(deps.storage, proposal, false)?)
         .set_data(to_binary(&ExecuteAnswer::Trigger {
             status: ResponseStatus::Success,
         })?))
 }
 
-pub fn try_cancel(
+pub fn try_retry(
     deps: DepsMut,
     env: Env,
     _info: MessageInfo,
     proposal: u32,
 ) -> StdResult<Response> {
-    // Check if passed, and check if current time > cancel time
     let status = Proposal::status(deps.storage, proposal)?;
-    if let Status::Passed { start: _, end } = status {
-        if env.block.time.seconds() < end {
-            return Err(Error::cannot_cancel(vec![&end.to_string()]));
-        }
+    if let Status::Failed { .. } = status {
         let mut history = Proposal::status_history(deps.storage, proposal)?;
+
+        // Retrying is only possible before the proposal can be canceled
+        if let Some(end) = Status::cancel_deadline(&history) {
+            if env.block.time.seconds() >= end {
+                return Err(Error::cannot_retry(vec![&end.to_string()]));
+            }
+        }
+
         history.push(status);
         Proposal::save_status_history(deps.storage, proposal, history)?;
-        Proposal::save_status(deps.storage, proposal, Status::Canceled)?;
+        Proposal::save_status(deps.storage, proposal, Status::Success)?;
     } else {
-        return Err(Error::cannot_cancel(vec![&(-1).to_string()]));
+        return Err(Error::not_failed(vec![]));
     }
 
+    Ok(Response::new()
+        .add_submessages(proposal_msgs(deps.storage, proposal, true)?)
+        .set_data(to_binary(&ExecuteAnswer::Retry {
+            status: ResponseStatus::Success,
+        })?))
+}
+
+/// Records the outcome of a triggered proposal msg
+pub fn proposal_reply(deps: DepsMut, id: u64, result: SubMsgResult) -> StdResult<Response> {
+    let id = id - 1;
+    let proposal = (id >> 32) as u32;
+    let msg_index = id as u32;
+
+    match result {
+        SubMsgResult::Ok(_) => {
+            Proposal::save_msg_outcome(deps.storage, proposal, msg_index, MsgOutcome::Success)?;
+        }
+        SubMsgResult::Err(error) => {
+            Proposal::save_msg_outcome(deps.storage, proposal, msg_index, MsgOutcome::Failed {
+                error: error.clone(),
+            })?;
+
+            // Only the first failure is kept as the proposal status
+            if let Status::Success = Proposal::status(deps.storage, proposal)? {
+                Proposal::save_status(deps.storage, proposal, Status::Failed {
+                    msg_index,
+                    error,
+                })?;
+            }
+        }
+    }
+
+    Ok(Response::new())
+}
+
+pub fn try_veto_queued(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal: u32,
+) -> StdResult<Response> {
+    // Guardians can only veto during the timelock
+    let status = Proposal::status(deps.storage, proposal)?;
+    match status {
+        Status::Queued { end, .. } if env.block.time.seconds() < end => {}
+        _ => return Err(Error::not_queued(vec![])),
+    }
+
+    let assembly = Proposal::assembly(deps.storage, proposal)?;
+    let profile = Assembly::data(deps.storage, assembly)?.profile;
+    let (guardian, threshold) = match Profile::timelock(deps.storage, profile)? {
+        Some(TimelockProfile {
+            guardian: Some(guardian),
+            guardian_threshold,
+            ..
+        }) => (guardian, guardian_threshold),
+        _ => return Err(Error::no_guardian(vec![&profile.to_string()])),
+    };
+
+    let members = authorize_assembly(deps.storage, &info, guardian)?.members;
+
+    // A single guardian member cannot stop a proposal unless the profile allows it
+    let mut vetoes = Proposal::guardian_vetoes(deps.storage, proposal)?;
+    if vetoes.contains(&info.sender) {
+        return Err(Error::guardian_already_vetoed(vec![info.sender.as_str()]));
+    }
+    vetoes.push(info.sender);
+    Proposal::save_guardian_vetoes(deps.storage, proposal, &vetoes)?;
+
+    let required = threshold
+        .map(|t| t as usize)
+        .unwrap_or(members.len() / 2 + 1)
+        .clamp(1, members.len().max(1));
+    if vetoes.len() < required {
+        return Ok(
+            Response::new().set_data(to_binary(&ExecuteAnswer::VetoQueued {
+                status: ResponseStatus::Success,
+            })?),
+        );
+    }
+
+    let mut history = Proposal::status_history(deps.storage, proposal)?;
+    history.push(status);
+    Proposal::save_status_history(deps.storage, proposal, history)?;
+    Proposal::save_status(deps.storage, proposal, Status::GuardianVetoed {
+        assembly: guardian,
+    })?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::VetoQueued {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+pub fn try_cancel(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    proposal: u32,
+) -> StdResult<Response> {
+    // Check if passed, and check if current time > cancel time
+    let mut status = Proposal::status(deps.storage, proposal)?;
+    let mut history = Proposal::status_history(deps.storage, proposal)?;
+
+    if let Status::Queued { end, .. } = status {
+        history.push(status);
+        status = release_timelock(deps.storage, proposal, end)?;
+    }
+
+    let end = match status {
+        Status::Passed { end, .. } => end,
+        // Failed proposals keep the deadline from when they passed
+        Status::Failed { .. } => Status::cancel_deadline(&history).unwrap_or(0),
+        _ => return Err(Error::cannot_cancel(vec![&(-1).to_string()])),
+    };
+
+    if env.block.time.seconds() < end {
+        return Err(Error::cannot_cancel(vec![&end.to_string()]));
+    }
+    history.push(status);
+    Proposal::save_status_history(deps.storage, proposal, history)?;
+    Proposal::save_status(deps.storage, proposal, Status::Canceled)?;
+
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::Cancel {
         status: ResponseStatus::Success,
     })?))
@@ -197,11 +370,7 @@ pub fn try_update(
                         end: env.block.time.seconds() + setting.deadline,
                     }
                 } else {
-                    vote_conclusion = Status::Passed {
-                        start: env.block.time.seconds(),
-                        end: env.block.time.seconds()
-                            + Profile::data(deps.storage, profile)?.cancel_deadline,
-                    }
+                    vote_conclusion = Status::approved(deps.storage, profile, &env.block.time)?
                 }
             }
 
@@ -213,25 +382,17 @@ pub fn try_update(
             if let Some(setting) = Profile::funding(deps.storage, profile)? {
                 // Check if deadline or funding limit reached
                 if amount >= setting.required {
-                    new_status = Status::Passed {
-                        start: env.block.time.seconds(),
-                        end: env.block.time.seconds()
-                            + Profile::data(deps.storage, profile)?.cancel_deadline,
-                    }
+                    new_status = Status::approved(deps.storage, profile, &env.block.time)?
                 } else if end > env.block.time.seconds() {
                     return Err(Error::cannot_update(vec!["Funding", &end.to_string()]));
                 } else {
                     new_status = Status::Expired;
                 }
             } else {
-                new_status = Status::Passed {
-                    start: env.block.time.seconds(),
-                    end: env.block.time.seconds()
-                        + Profile::data(deps.storage, profile)?.cancel_deadline,
-                }
+                new_status = Status::approved(deps.storage, profile, &env.block.time)?
             }
 
-            if let Status::Passed { .. } = new_status {
+            if let Status::Passed { .. } | Status::Queued { .. } = new_status {
                 if let Some(setting) = Profile::public_voting(deps.storage, profile)? {
                     new_status = Status::Voting {
                         start: env.block.time.seconds(),
@@ -240,7 +401,7 @@ pub fn try_update(
                 }
             }
         }
-        Status::Voting { start: _, end } => {
+        Status::Voting { start, end } => {
             if end > env.block.time.seconds() {
                 return Err(Error::cannot_update(vec!["Voting", &end.to_string()]));
             }
@@ -248,13 +409,30 @@ pub fn try_update(
             let config = Config::load(deps.storage)?;
             let votes = Proposal::public_votes(deps.storage, proposal)?;
 
-            let query: snip20_staking::QueryAnswer = snip20_staking::QueryMsg::TotalStaked {}
-                .query(&deps.querier, &config.vote_token.unwrap())?;
-
             // Get total staking power
-            let total_power = match query {
-                snip20_staking::QueryAnswer::TotalStaked { tokens, .. } => tokens.into(),
-                _ => return Err(Error::unexpected_query_response(vec![])),
+            let total_power = match config.voting_power {
+                VotingPower::Live => {
+                    let query: snip20_staking::QueryAnswer =
+                        snip20_staking::QueryMsg::TotalStaked {}
+                            .query(&deps.querier, &config.vote_token.unwrap())?;
+
+                    match query {
+                        snip20_staking::QueryAnswer::TotalStaked { tokens, .. } => tokens.into(),
+                        _ => return Err(Error::unexpected_query_response(vec![])),
+                    }
+                }
+                VotingPower::Snapshot { staking } => {
+                    let query: basic_staking::QueryAnswer =
+                        basic_staking::QueryMsg::TotalStakedAt {
+                            time: Uint128::new(start as u128),
+                        }
+                        .query(&deps.querier, &staking)?;
+
+                    match query {
+                        basic_staking::QueryAnswer::TotalStakedAt { amount } => amount,
+                        _ => return Err(Error::unexpected_query_response(vec![])),
+                    }
+                }
             };
 
             let mut vote_conclusion: Status;
@@ -295,15 +473,18 @@ pub fn try_update(
                     }
                 }
             } else if let Status::Success = vote_conclusion {
-                vote_conclusion = Status::Passed {
-                    start: env.block.time.seconds(),
-                    end: env.block.time.seconds()
-                        + Profile::data(deps.storage, profile)?.cancel_deadline,
-                }
+                vote_conclusion = Status::approved(deps.storage, profile, &env.block.time)?
             }
 
             new_status = vote_conclusion;
         }
+        Status::Queued { end, .. } => {
+            if end > env.block.time.seconds() {
+                return Err(Error::cannot_update(vec!["Queued", &end.to_string()]));
+            }
+
+            new_status = release_timelock(deps.storage, proposal, end)?;
+        }
         _ => return Err(Error::state_update(vec![])),
     }
 
@@ -476,6 +657,9 @@ pub fn try_claim_funding(
         })?))
 }
 
+/// Lists the delegators that were left out of a vote because their auth failed
+const FAILED_DELEGATORS_ATTRIBUTE: &str = "failed-delegators";
+
 pub fn try_receive_vote(
     deps: DepsMut,
     env: Env,
@@ -485,20 +669,27 @@ pub fn try_receive_vote(
     balance: Uint128,
     _memo: Option<String>,
 ) -> StdResult<Response> {
-    if let Some(token) = Config::load(deps.storage)?.vote_token {
-        if info.sender != token.address {
-            return Err(Error::sender_funding(vec![]));
-        }
-    } else {
-        return Err(Error::missing_funding_token(vec![]));
+    let config = Config::load(deps.storage)?;
+    let token = match config.vote_token.clone() {
+        None => return Err(Error::missing_funding_token(vec![])),
+        Some(token) => token.address,
+    };
+    if info.sender != token {
+        return Err(Error::sender_funding(vec![]));
     }
 
     let vote: Vote;
     let proposal: u32;
+    let auth: Option<basic_staking::Auth>;
     if let Some(msg) = msg {
+        if let Ok(delegation) = from_binary::<DelegationMsg>(&msg) {
+            return try_receive_delegation(deps, &env, token, sender, delegation);
+        }
+
         let decoded_msg: ReceiveBalanceMsg = from_binary(&msg)?;
         vote = decoded_msg.vote;
         proposal = decoded_msg.proposal;
+        auth = decoded_msg.auth;
 
         // Verify that total does not exceed balance
         let total_votes = vote.yes.checked_add(
@@ -514,28 +705,185 @@ pub fn try_receive_vote(
     }
 
     // Check if proposal in assembly voting
-    if let Status::Voting { end, .. } = Proposal::status(deps.storage, proposal)? {
-        if end <= env.block.time.seconds() {
-            return Err(Error::voting_time(vec![&end.to_string()]));
+    let start = match Proposal::status(deps.storage, proposal)? {
+        Status::Voting { start, end } => {
+            if end <= env.block.time.seconds() {
+                return Err(Error::voting_time(vec![&end.to_string()]));
+            }
+            start
+        }
+        _ => return Err(Error::voting_not_state(vec![])),
+    };
+
+    // Voting power is fixed to the stake held when voting started
+    if let VotingPower::Snapshot { staking } = &config.voting_power {
+        let auth = match auth.clone() {
+            None => return Err(Error::snapshot_auth(vec![])),
+            Some(auth) => auth,
+        };
+
+        if vote.total_count()? > staked_at(deps.as_ref(), staking, auth, &sender, start)? {
+            return Err(Error::voting_snapshot(vec![]));
         }
-    } else {
-        return Err(Error::voting_not_state(vec![]));
     }
 
+    // Weigh the vote with the profile's settings
+    let assembly = Proposal::assembly(deps.storage, proposal)?;
+    let profile = Assembly::data(deps.storage, assembly)?.profile;
+    let weighting = match Profile::public_voting(deps.storage, profile)? {
+        None => Weighting::Linear,
+        Some(settings) => settings.weighting,
+    };
+    let lockup = match &weighting {
+        Weighting::Conviction { staking, .. } => {
+            let auth = match auth {
+                None => return Err(Error::conviction_auth(vec![])),
+                Some(auth) => auth,
+            };
+            conviction_lockup(deps.as_ref(), &env, staking, &sender, auth)?
+        }
+        _ => 0,
+    };
+    let power = weighting.weigh(vote.total_count()?, lockup)?;
+
     let mut tally = Proposal::public_votes(deps.storage, proposal)?;
 
-    // Check if user voted
+    // Check if user voted, removing the old vote with its delegated power
+    let old_voters = Proposal::delegated_voters(deps.storage, proposal, &sender)?;
     if let Some(old_vote) = Proposal::public_vote(deps.storage, proposal, &sender)? {
-        tally = tally.checked_sub(&old_vote)?;
+        let old_power = match Proposal::public_vote_power(deps.storage, proposal, &sender)? {
+            None => old_vote.total_count()?,
+            Some(power) => power,
+        };
+        tally = tally.checked_sub(&old_vote.scale(old_power)?)?;
+        tally = tally.checked_sub(&old_vote.scale(DelegatedVoter::total_power(&old_voters)?)?)?;
+    }
+    for voter in old_voters.iter() {
+        Proposal::save_vote_delegate(deps.storage, proposal, &voter.voter, None)?;
+    }
+
+    // Voting directly overrides the delegate's vote
+    if let Some(delegate) = Proposal::vote_delegate(deps.storage, proposal, &sender)? {
+        if let Some(delegate_vote) = Proposal::public_vote(deps.storage, proposal, &delegate)? {
+            let mut voters = Proposal::delegated_voters(deps.storage, proposal, &delegate)?;
+            tally = tally
+                .checked_sub(&delegate_vote.scale(DelegatedVoter::total_power(&voters)?)?)?;
+            voters.retain(|voter| voter.voter != sender);
+            tally = tally
+                .checked_add(&delegate_vote.scale(DelegatedVoter::total_power(&voters)?)?)?;
+            Proposal::save_delegated_voters(deps.storage, proposal, &delegate, &voters)?;
+        }
+        Proposal::save_vote_delegate(deps.storage, proposal, &sender, None)?;
+    }
+
+    // Count the delegators that have not voted themselves, delegations are only
+    // accepted with snapshotted power
+    let mut voters = vec![];
+    let mut failed_delegators = vec![];
+    if let VotingPower::Snapshot { staking } = &config.voting_power {
+        for delegator in Delegation::delegators(deps.storage, &token, &sender)? {
+            if Proposal::public_vote(deps.storage, proposal, &delegator)?.is_some()
+                || Proposal::vote_delegate(deps.storage, proposal, &delegator)?.is_some()
+            {
+                continue;
+            }
+
+            let auth = match Delegation::auth(deps.storage, &token, &delegator)? {
+                None => continue,
+                Some(auth) => auth,
+            };
+            // Delegators whose auth stopped working are left out until they delegate again
+            let delegated = match staked_at(deps.as_ref(), staking, auth, &delegator, start) {
+                Ok(staked) => staked,
+                Err(_) => {
+                    failed_delegators.push(delegator.to_string());
+                    continue;
+                }
+            };
+
+            // Delegators lockups are unknown so they are weighed without them
+            let power = weighting.weigh(delegated, 0)?;
+            Proposal::save_vote_delegate(deps.storage, proposal, &delegator, Some(&sender))?;
+            voters.push(DelegatedVoter {
+                voter: delegator,
+                power,
+            });
+        }
     }
+    let delegated_vote = vote.scale(DelegatedVoter::total_power(&voters)?)?;
+    Proposal::save_delegated_voters(deps.storage, proposal, &sender, &voters)?;
 
     Proposal::save_public_vote(deps.storage, proposal, &sender, &vote)?;
-    Proposal::save_public_votes(deps.storage, proposal, &tally.checked_add(&vote)?)?;
+    Proposal::save_public_vote_power(deps.storage, proposal, &sender, power)?;
+    Proposal::save_public_votes(
+        deps.storage,
+        proposal,
+        &tally
+            .checked_add(&vote.scale(power)?)?
+            .checked_add(&delegated_vote)?,
+    )?;
     UserID::add_vote(deps.storage, sender.clone(), proposal)?;
 
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::ReceiveBalance {
-            status: ResponseStatus::Success,
-        })?),
-    )
+    let mut response = Response::new().set_data(to_binary(&ExecuteAnswer::ReceiveBalance {
+        status: ResponseStatus::Success,
+    })?);
+    if !failed_delegators.is_empty() {
+        response = response.add_attribute(FAILED_DELEGATORS_ATTRIBUTE, failed_delegators.join(","));
+    }
+
+    Ok(response)
+}
+
+/// Stake held by the user at the given time, the auth must belong to them
+pub fn staked_at(
+    deps: Deps,
+    staking: &Contract,
+    auth: basic_staking::Auth,
+    user: &Addr,
+    time: u64,
+) -> StdResult<Uint128> {
+    let query: basic_staking::QueryAnswer = basic_staking::QueryMsg::StakedAt {
+        auth,
+        time: Uint128::new(time as u128),
+    }
+    .query(&deps.querier, staking)?;
+
+    match query {
+        basic_staking::QueryAnswer::StakedAt { user: owner, amount } => {
+            if &owner != user {
+                return Err(Error::snapshot_user(vec![user.as_str()]));
+            }
+            Ok(amount)
+        }
+        _ => Err(Error::unexpected_query_response(vec![])),
+    }
+}
+
+/// Remaining seconds the voter's unbondings are locked for
+fn conviction_lockup(
+    deps: Deps,
+    env: &Env,
+    staking: &Contract,
+    voter: &Addr,
+    auth: basic_staking::Auth,
+) -> StdResult<u64> {
+    let now = Uint128::new(env.block.time.seconds() as u128);
+
+    // Verify that the auth belongs to the voter
+    staked_at(deps, staking, auth.clone(), voter, env.block.time.seconds())?;
+
+    let query: basic_staking::QueryAnswer =
+        basic_staking::QueryMsg::Unbonding { auth, ids: None }.query(&deps.querier, staking)?;
+
+    let lockup = match query {
+        basic_staking::QueryAnswer::Unbonding { unbondings } => unbondings
+            .iter()
+            .filter(|unbonding| unbonding.complete > now)
+            .map(|unbonding| unbonding.complete - now)
+            .max()
+            .unwrap_or_default(),
+        _ => return Err(Error::unexpected_query_response(vec![])),
+    };
+
+    Ok(lockup.u128() as u64)
 }
@@ -1,19 +1,26 @@
+use crate::handle::proposal::staked_at;
 use shade_protocol::{
-    c_std::{Addr, Deps, StdResult},
+    c_std::{Addr, Deps, Env, StdResult, Uint128},
     contract_interfaces::governance::{
         assembly::{Assembly, AssemblyMsg},
         contract::AllowedContract,
         profile::Profile,
-        proposal::Proposal,
+        proposal::{Proposal, ProposalFilter, StatusKind},
         stored_id::ID,
+        vote::{Delegation, Delegator},
         Config,
         QueryAnswer,
+        VotingPower,
     },
     governance::{errors::Error, stored_id::UserID, Pagination, ResponseWithID},
-    utils::storage::plus::ItemStorage,
+    utils::storage::plus::{ItemStorage, MapStorage},
 };
 use std::cmp::min;
 
+// Max amount of index entries read by a filtered proposal query
+const MAX_PROPOSAL_SCAN: u32 = 500;
+const MAX_PROPOSAL_LIMIT: u32 = 100;
+
 pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
     Ok(QueryAnswer::Config {
         config: Config::load(deps.storage)?,
@@ -41,6 +48,123 @@ pub fn proposals(deps: Deps, start: u32, end: u32) -> StdResult<QueryAnswer> {
     Ok(QueryAnswer::Proposals { props: items })
 }
 
+enum ProposalIndex {
+    Proposer(Addr),
+    Assembly(u16),
+    Status(StatusKind),
+    All,
+}
+
+fn matches_filter(deps: Deps, filter: &ProposalFilter, id: u32) -> StdResult<bool> {
+    if let Some(status) = &filter.status {
+        if &Proposal::status(deps.storage, id)?.kind() != status {
+            return Ok(false);
+        }
+    }
+    if let Some(assembly) = filter.assembly {
+        if Proposal::assembly(deps.storage, id)? != assembly {
+            return Ok(false);
+        }
+    }
+    if let Some(proposer) = &filter.proposer {
+        if &Proposal::description(deps.storage, id)?.proposer != proposer {
+            return Ok(false);
+        }
+    }
+    if filter.from.is_some() || filter.to.is_some() {
+        let created = Proposal::created(deps.storage, id)?;
+        if filter.from.map_or(false, |from| created < from)
+            || filter.to.map_or(false, |to| created > to)
+        {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+pub fn filtered_proposals(
+    deps: Deps,
+    filter: ProposalFilter,
+    cursor: Option<u32>,
+    limit: u32,
+) -> StdResult<QueryAnswer> {
+    let limit = min(limit, MAX_PROPOSAL_LIMIT) as usize;
+
+    // Scan the narrowest index available
+    let mut start = 0;
+    // Unindexed proposals are scanned by ID before the assembly and status indices
+    let mut legacy = 0;
+    let (index, total) = if let Some(proposer) = &filter.proposer {
+        let total = match UserID::proposal(deps.storage, proposer.clone(), 0) {
+            Ok(_) => UserID::total_proposals(deps.storage, proposer.clone())? + 1,
+            Err(_) => 0,
+        };
+        (ProposalIndex::Proposer(proposer.clone()), total)
+    } else if let Some(assembly) = filter.assembly {
+        legacy = Proposal::first_indexed(deps.storage)?;
+        let total = Proposal::total_assembly_proposals(deps.storage, assembly)?;
+        (ProposalIndex::Assembly(assembly), legacy + total)
+    } else if let Some(status) = &filter.status {
+        legacy = Proposal::first_indexed(deps.storage)?;
+        let total = Proposal::total_status_proposals(deps.storage, status)?;
+        (ProposalIndex::Status(status.clone()), legacy + total)
+    } else {
+        // IDs are chronological so the time range can be narrowed directly
+        if let Some(from) = filter.from {
+            start = Proposal::first_created_after(deps.storage, from)?;
+        }
+        let total = match filter.to {
+            Some(to) => Proposal::first_created_after(deps.storage, to.saturating_add(1))?,
+            None => ID::proposal(deps.storage).map(|id| id + 1).unwrap_or(0),
+        };
+        (ProposalIndex::All, total)
+    };
+
+    let mut position = cursor.unwrap_or(start);
+    let end = min(total, position.saturating_add(MAX_PROPOSAL_SCAN));
+    let mut props = vec![];
+
+    while position < end && props.len() < limit {
+        let id = if position < legacy {
+            Some(position)
+        } else {
+            let index_position = position - legacy;
+            match &index {
+                ProposalIndex::Proposer(proposer) => {
+                    Some(UserID::proposal(deps.storage, proposer.clone(), index_position)?)
+                }
+                ProposalIndex::Assembly(assembly) => Some(Proposal::assembly_proposal(
+                    deps.storage,
+                    *assembly,
+                    index_position,
+                )?),
+                // Unindexed proposals that changed status were already scanned
+                ProposalIndex::Status(status) => {
+                    Proposal::status_proposal(deps.storage, status, index_position)?
+                        .filter(|id| *id >= legacy)
+                }
+                ProposalIndex::All => Some(position),
+            }
+        };
+        position += 1;
+
+        if let Some(id) = id {
+            if matches_filter(deps, &filter, id)? {
+                props.push(Proposal::summary(deps.storage, id)?);
+            }
+        }
+    }
+
+    Ok(QueryAnswer::ProposalSummaries {
+        props,
+        cursor: if position < total {
+            Some(position)
+        } else {
+            None
+        },
+    })
+}
+
 pub fn total_profiles(deps: Deps) -> StdResult<QueryAnswer> {
     Ok(QueryAnswer::Total {
         total: ID::profile(deps.storage)?.checked_add(1).unwrap() as u32,
@@ -227,3 +351,80 @@ pub fn user_votes(deps: Deps, user: Addr, pagination: Pagination) -> StdResult<Q
 
     Ok(QueryAnswer::UserVotes { votes, total })
 }
+
+/// Stake currently held by the delegator, None when their auth stopped working
+fn delegator_power(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    token: &Addr,
+    delegator: &Addr,
+) -> StdResult<Option<Uint128>> {
+    let staking = match &config.voting_power {
+        VotingPower::Live => return Ok(None),
+        VotingPower::Snapshot { staking } => staking,
+    };
+
+    Ok(match Delegation::auth(deps.storage, token, delegator)? {
+        None => None,
+        Some(auth) => staked_at(deps, staking, auth, delegator, env.block.time.seconds()).ok(),
+    })
+}
+
+pub fn user_delegations(
+    deps: Deps,
+    env: &Env,
+    user: Addr,
+    pagination: Pagination,
+) -> StdResult<QueryAnswer> {
+    let config = Config::load(deps.storage)?;
+    let token = match &config.vote_token {
+        None => {
+            return Ok(QueryAnswer::UserDelegations {
+                delegation: None,
+                delegators: vec![],
+                total: 0,
+            });
+        }
+        Some(token) => token.address.clone(),
+    };
+
+    let all_delegators = Delegation::delegators(deps.storage, &token, &user)?;
+    let total = all_delegators.len() as u32;
+
+    let start = pagination
+        .amount
+        .checked_mul(pagination.page as u32)
+        .unwrap();
+    let mut delegators = vec![];
+
+    for address in all_delegators
+        .into_iter()
+        .skip(start as usize)
+        .take(pagination.amount as usize)
+    {
+        delegators.push(Delegator {
+            power: delegator_power(deps, env, &config, &token, &address)?,
+            address,
+        });
+    }
+
+    Ok(QueryAnswer::UserDelegations {
+        delegation: Delegation::may_load(deps.storage, (token, user))?,
+        delegators,
+        total,
+    })
+}
+
+pub fn user_delegated_power(deps: Deps, env: &Env, user: Addr) -> StdResult<QueryAnswer> {
+    let config = Config::load(deps.storage)?;
+    let mut power = Uint128::zero();
+    if let Some(token) = &config.vote_token {
+        for delegator in Delegation::delegators(deps.storage, &token.address, &user)? {
+            let staked = delegator_power(deps, env, &config, &token.address, &delegator)?;
+            power = power.checked_add(staked.unwrap_or_default())?;
+        }
+    }
+
+    Ok(QueryAnswer::UserDelegatedPower { power })
+}
@@ -1,5 +1,10 @@
-use crate::tests::{admin_only_governance, get_assembly_msgs};
-use shade_protocol::{c_std::Addr, contract_interfaces::governance, utils::ExecuteCallback};
+use crate::tests::{admin_only_governance, get_assembly_msgs, get_proposals};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo},
+    contract_interfaces::{governance, governance::proposal::ProposalMsg},
+    multi_test::App,
+    utils::ExecuteCallback,
+};
 
 #[test]
 fn add_assembly_msg() {
@@ -100,3 +105,67 @@ fn unauthorised_set_assembly_msg() {
         .is_err()
     );
 }
+
+#[test]
+fn add_invalid_typed_assembly_msg() {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    assert!(
+        governance::ExecuteMsg::AddAssemblyMsg {
+            name: "Some Assembly name".to_string(),
+            msg: "{\"a\":{~float}}".to_string(),
+            assemblies: vec![1],
+            padding: None,
+        }
+        .test_exec(&gov, &mut chain, gov.address.clone(), &[])
+        .is_err()
+    );
+}
+
+fn typed_proposal(chain: &mut App, gov: &ContractInfo, name: &str) -> bool {
+    governance::ExecuteMsg::AssemblyProposal {
+        assembly: 1,
+        title: "Title".to_string(),
+        metadata: "Proposal metadata".to_string(),
+        msgs: Some(vec![ProposalMsg {
+            target: 0,
+            assembly_msg: 1,
+            msg: to_binary(&vec![name.to_string()]).unwrap(),
+            send: vec![],
+        }]),
+        padding: None,
+    }
+    .test_exec(gov, chain, Addr::unchecked("admin"), &[])
+    .is_ok()
+}
+
+#[test]
+fn typed_assembly_msg_proposal() {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    governance::ExecuteMsg::AddAssemblyMsg {
+        name: "Rename assembly".to_string(),
+        msg: "{\"set_assembly\":{\"id\":1,\"name\":{~string}}}".to_string(),
+        assemblies: vec![1],
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, gov.address.clone(), &[])
+    .unwrap();
+
+    // Arguments are escaped so they cannot inject extra fields
+    assert!(typed_proposal(
+        &mut chain,
+        &gov,
+        "name\",\"members\":[\"attacker\"],\"x\":\""
+    ));
+
+    let prop = get_proposals(&mut chain, &gov, 0, 1).unwrap()[0].clone();
+    let msg = prop.msgs.unwrap()[0].msg.clone();
+    assert_eq!(
+        String::from_utf8(msg.0).unwrap(),
+        concat!(
+            "{\"set_assembly\":{\"id\":1,",
+            "\"name\":\"name\\\",\\\"members\\\":[\\\"attacker\\\"],\\\"x\\\":\\\"\"}}"
+        )
+    );
+}
@@ -45,6 +45,7 @@ fn migrate() {
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
             padding: None,
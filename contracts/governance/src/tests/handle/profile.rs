@@ -25,6 +25,7 @@ fn add_profile() {
             assembly: None,
             funding: None,
             token: None,
+            timelock: None,
             cancel_deadline: 0,
         },
         padding: None,
@@ -54,6 +55,7 @@ fn unauthorised_add_profile() {
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
             padding: None,
@@ -86,6 +88,8 @@ fn set_profile() {
             funding: None,
             disable_token: false,
             token: None,
+            disable_timelock: false,
+            timelock: None,
             cancel_deadline: None,
         },
         padding: None,
@@ -125,6 +129,8 @@ fn unauthorised_set_profile() {
                 funding: None,
                 disable_token: false,
                 token: None,
+                disable_timelock: false,
+                timelock: None,
                 cancel_deadline: None,
             },
             padding: None,
@@ -161,11 +167,14 @@ fn set_profile_disable_assembly() {
                 veto_threshold: Some(Count::LiteralCount {
                     count: Uint128::zero(),
                 }),
+                weighting: None,
             }),
             disable_funding: false,
             funding: None,
             disable_token: false,
             token: None,
+            disable_timelock: false,
+            timelock: None,
             cancel_deadline: None,
         },
         padding: None,
@@ -192,6 +201,8 @@ fn set_profile_disable_assembly() {
             funding: None,
             disable_token: false,
             token: None,
+            disable_timelock: false,
+            timelock: None,
             cancel_deadline: None,
         },
         padding: None,
@@ -233,11 +244,14 @@ fn set_profile_set_incomplete_assembly() {
                     veto_threshold: Some(Count::LiteralCount {
                         count: Uint128::zero(),
                     }),
+                    weighting: None,
                 }),
                 disable_funding: false,
                 funding: None,
                 disable_token: false,
                 token: None,
+                disable_timelock: false,
+                timelock: None,
                 cancel_deadline: None,
             },
             padding: None,
@@ -278,7 +292,10 @@ fn set_profile_disable_token() {
                 veto_threshold: Some(Count::LiteralCount {
                     count: Uint128::zero(),
                 }),
+                weighting: None,
             }),
+            disable_timelock: false,
+            timelock: None,
             cancel_deadline: None,
         },
         padding: None,
@@ -305,6 +322,8 @@ fn set_profile_disable_token() {
             funding: None,
             disable_token: true,
             token: None,
+            disable_timelock: false,
+            timelock: None,
             cancel_deadline: None,
         },
         padding: None,
@@ -350,7 +369,10 @@ fn set_profile_set_incomplete_token() {
                     veto_threshold: Some(Count::LiteralCount {
                         count: Uint128::zero(),
                     }),
+                    weighting: None,
                 }),
+                disable_timelock: false,
+                timelock: None,
                 cancel_deadline: None,
             },
             padding: None,
@@ -386,6 +408,8 @@ fn set_profile_disable_funding() {
             }),
             disable_token: false,
             token: None,
+            disable_timelock: false,
+            timelock: None,
             cancel_deadline: None,
         },
         padding: None,
@@ -412,6 +436,8 @@ fn set_profile_disable_funding() {
             funding: None,
             disable_token: false,
             token: None,
+            disable_timelock: false,
+            timelock: None,
             cancel_deadline: None,
         },
         padding: None,
@@ -456,6 +482,8 @@ fn set_profile_set_incomplete_fuding() {
                 }),
                 disable_token: false,
                 token: None,
+                disable_timelock: false,
+                timelock: None,
                 cancel_deadline: None,
             },
             padding: None,
@@ -5,7 +5,7 @@ use shade_protocol::{
     contract_interfaces::{
         governance,
         governance::{
-            profile::{Count, Profile, VoteProfile},
+            profile::{Count, Profile, VoteProfile, Weighting},
             proposal::Status,
             vote::Vote,
             InstantiateMsg,
@@ -22,6 +22,8 @@ pub fn init_assembly_governance_with_proposal() -> StdResult<(App, ContractInfo)
 
     query_auth::ExecuteMsg::SetViewingKey {
         key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&auth, &mut chain, Addr::unchecked("alpha"), &[])
@@ -29,6 +31,8 @@ pub fn init_assembly_governance_with_proposal() -> StdResult<(App, ContractInfo)
 
     query_auth::ExecuteMsg::SetViewingKey {
         key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&auth, &mut chain, Addr::unchecked("beta"), &[])
@@ -36,6 +40,8 @@ pub fn init_assembly_governance_with_proposal() -> StdResult<(App, ContractInfo)
 
     query_auth::ExecuteMsg::SetViewingKey {
         key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&auth, &mut chain, Addr::unchecked("charlie"), &[])
@@ -49,6 +55,7 @@ pub fn init_assembly_governance_with_proposal() -> StdResult<(App, ContractInfo)
         },
         funding_token: None,
         vote_token: None,
+        voting_power: None,
         assemblies: Some(AssemblyInit {
             admin_members: vec![
                 Addr::unchecked("alpha"),
@@ -69,9 +76,11 @@ pub fn init_assembly_governance_with_proposal() -> StdResult<(App, ContractInfo)
                     veto_threshold: Count::LiteralCount {
                         count: Uint128::new(3),
                     },
+                    weighting: Weighting::Linear,
                 }),
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
             public_profile: Profile {
@@ -80,6 +89,7 @@ pub fn init_assembly_governance_with_proposal() -> StdResult<(App, ContractInfo)
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
         }),
@@ -751,6 +761,7 @@ fn vote_count_percentage() {
         },
         funding_token: None,
         vote_token: None,
+        voting_power: None,
         assemblies: Some(AssemblyInit {
             admin_members: vec![
                 Addr::unchecked("alpha"),
@@ -765,9 +776,11 @@ fn vote_count_percentage() {
                     threshold: Count::Percentage { percent: 6500 },
                     yes_threshold: Count::Percentage { percent: 6500 },
                     veto_threshold: Count::Percentage { percent: 6500 },
+                    weighting: Weighting::Linear,
                 }),
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
             public_profile: Profile {
@@ -776,6 +789,7 @@ fn vote_count_percentage() {
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
         }),
@@ -0,0 +1,367 @@
+use crate::tests::{
+    get_config,
+    get_proposals,
+    handle::proposal::{
+        snapshot::init_snapshot_governance_with_stakers,
+        voting::{init_voting_governance_with_proposal, vote},
+    },
+};
+use shade_protocol::{
+    basic_staking,
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        governance,
+        governance::{
+            vote::{DelegationMsg, Delegator, ReceiveBalanceMsg, Vote, MAX_DELEGATORS},
+            AuthQuery,
+            Pagination,
+        },
+    },
+    multi_test::{App, AppResponse},
+    query_auth,
+    utils::{ExecuteCallback, Query},
+    AnyResult,
+};
+
+fn auth(user: &str) -> Option<basic_staking::Auth> {
+    Some(basic_staking::Auth::ViewingKey {
+        key: "password".to_string(),
+        address: user.to_string(),
+    })
+}
+
+fn delegate_msg(to: &str, delegator: &str) -> DelegationMsg {
+    DelegationMsg::Delegate {
+        to: Addr::unchecked(to),
+        auth: auth(delegator),
+    }
+}
+
+fn delegate(
+    gov: &ContractInfo,
+    chain: &mut App,
+    token: &str,
+    delegator: &str,
+    msg: DelegationMsg,
+) -> AnyResult<AppResponse> {
+    governance::ExecuteMsg::ReceiveBalance {
+        sender: Addr::unchecked(delegator),
+        msg: Some(to_binary(&msg).unwrap()),
+        balance: Uint128::new(10_000_000),
+        memo: None,
+    }
+    .test_exec(gov, chain, Addr::unchecked(token), &[])
+}
+
+fn vote_msg(voter: &str, yes: u128, no: u128) -> ReceiveBalanceMsg {
+    ReceiveBalanceMsg {
+        vote: Vote {
+            yes: Uint128::new(yes),
+            no: Uint128::new(no),
+            no_with_veto: Uint128::zero(),
+            abstain: Uint128::zero(),
+        },
+        proposal: 0,
+        auth: auth(voter),
+    }
+}
+
+fn snapshot_vote(
+    gov: &ContractInfo,
+    chain: &mut App,
+    token: &str,
+    voter: &str,
+    yes: u128,
+    no: u128,
+) -> AnyResult<AppResponse> {
+    vote(
+        gov,
+        chain,
+        token,
+        voter,
+        vote_msg(voter, yes, no),
+        Uint128::new(10_000_000),
+    )
+}
+
+fn tally(chain: &mut App, gov: &ContractInfo, yes: u128, no: u128) {
+    let prop = get_proposals(chain, gov, 0, 2).unwrap()[0].clone();
+    assert_eq!(
+        prop.public_vote_tally,
+        Some(Vote {
+            yes: Uint128::new(yes),
+            no: Uint128::new(no),
+            no_with_veto: Uint128::zero(),
+            abstain: Uint128::zero(),
+        })
+    );
+}
+
+fn delegations(chain: &App, gov: &ContractInfo, user: &str) -> (Option<Addr>, Vec<Delegator>) {
+    let query: governance::QueryAnswer = governance::QueryMsg::WithVK {
+        user: Addr::unchecked(user),
+        key: "password".to_string(),
+        query: AuthQuery::Delegations {
+            pagination: Pagination { page: 0, amount: 10 },
+        },
+    }
+    .test_query(gov, chain)
+    .unwrap();
+
+    match query {
+        governance::QueryAnswer::UserDelegations {
+            delegation,
+            delegators,
+            ..
+        } => (delegation.map(|delegation| delegation.to), delegators),
+        _ => panic!("Returned wrong enum"),
+    }
+}
+
+fn delegated_power(chain: &App, gov: &ContractInfo, user: &str) -> Uint128 {
+    let query: governance::QueryAnswer = governance::QueryMsg::WithVK {
+        user: Addr::unchecked(user),
+        key: "password".to_string(),
+        query: AuthQuery::DelegatedPower {},
+    }
+    .test_query(gov, chain)
+    .unwrap();
+
+    match query {
+        governance::QueryAnswer::UserDelegatedPower { power } => power,
+        _ => panic!("Returned wrong enum"),
+    }
+}
+
+#[test]
+fn delegate_and_undelegate() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_stakers(&["charlie"]).unwrap();
+    let token = token.address.as_str();
+
+    delegate(
+        &gov,
+        &mut chain,
+        token,
+        "charlie",
+        delegate_msg("alpha", "charlie"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        delegations(&chain, &gov, "charlie").0,
+        Some(Addr::unchecked("alpha"))
+    );
+    assert_eq!(delegations(&chain, &gov, "alpha").1, vec![Delegator {
+        address: Addr::unchecked("charlie"),
+        power: Some(Uint128::new(10_000_000)),
+    }]);
+    assert_eq!(
+        delegated_power(&chain, &gov, "alpha"),
+        Uint128::new(10_000_000)
+    );
+
+    delegate(
+        &gov,
+        &mut chain,
+        token,
+        "charlie",
+        DelegationMsg::Undelegate {},
+    )
+    .unwrap();
+
+    assert_eq!(delegations(&chain, &gov, "charlie").0, None);
+    assert_eq!(delegated_power(&chain, &gov, "alpha"), Uint128::zero());
+
+    // Nothing left to undelegate
+    assert!(
+        delegate(
+            &gov,
+            &mut chain,
+            token,
+            "charlie",
+            DelegationMsg::Undelegate {},
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn live_delegation() {
+    let (mut chain, gov, stkd_tkn, _auth) = init_voting_governance_with_proposal().unwrap();
+
+    // Exposed balances would go stale once the tokens move
+    assert!(
+        delegate(
+            &gov,
+            &mut chain,
+            &stkd_tkn,
+            "beta",
+            DelegationMsg::Delegate {
+                to: Addr::unchecked("alpha"),
+                auth: None,
+            },
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn self_delegation() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_stakers(&["charlie"]).unwrap();
+
+    assert!(
+        delegate(
+            &gov,
+            &mut chain,
+            token.address.as_str(),
+            "charlie",
+            delegate_msg("charlie", "charlie"),
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn delegate_from_wrong_token() {
+    let (mut chain, gov, _, _) = init_snapshot_governance_with_stakers(&["charlie"]).unwrap();
+
+    assert!(
+        delegate(
+            &gov,
+            &mut chain,
+            "not_token",
+            "charlie",
+            delegate_msg("alpha", "charlie"),
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn delegated_vote() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_stakers(&["charlie"]).unwrap();
+    let token = token.address.as_str();
+
+    delegate(
+        &gov,
+        &mut chain,
+        token,
+        "charlie",
+        delegate_msg("alpha", "charlie"),
+    )
+    .unwrap();
+
+    snapshot_vote(&gov, &mut chain, token, "alpha", 7_500_000, 2_500_000).unwrap();
+
+    // Delegated power follows the delegate's vote
+    tally(&mut chain, &gov, 15_000_000, 5_000_000);
+
+    // Changing the vote moves the delegated power with it
+    snapshot_vote(&gov, &mut chain, token, "alpha", 0, 10_000_000).unwrap();
+    tally(&mut chain, &gov, 0, 20_000_000);
+}
+
+#[test]
+fn delegator_overrides_vote() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_stakers(&["charlie"]).unwrap();
+    let token = token.address.as_str();
+
+    delegate(
+        &gov,
+        &mut chain,
+        token,
+        "charlie",
+        delegate_msg("alpha", "charlie"),
+    )
+    .unwrap();
+
+    snapshot_vote(&gov, &mut chain, token, "alpha", 10_000_000, 0).unwrap();
+    tally(&mut chain, &gov, 20_000_000, 0);
+
+    snapshot_vote(&gov, &mut chain, token, "charlie", 0, 10_000_000).unwrap();
+    tally(&mut chain, &gov, 10_000_000, 10_000_000);
+
+    // Revoting does not count the delegator that already voted
+    snapshot_vote(&gov, &mut chain, token, "alpha", 5_000_000, 0).unwrap();
+    tally(&mut chain, &gov, 5_000_000, 10_000_000);
+}
+
+#[test]
+fn delegator_changed_key() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_stakers(&["charlie"]).unwrap();
+    let token = token.address.as_str();
+
+    delegate(
+        &gov,
+        &mut chain,
+        token,
+        "charlie",
+        delegate_msg("alpha", "charlie"),
+    )
+    .unwrap();
+
+    let query_auth = get_config(&mut chain, &gov).unwrap().query;
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "new_password".to_string(),
+        expires_at: None,
+        contracts: None,
+        padding: None,
+    }
+    .test_exec(
+        &ContractInfo {
+            address: query_auth.address,
+            code_hash: query_auth.code_hash,
+        },
+        &mut chain,
+        Addr::unchecked("charlie"),
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(delegations(&chain, &gov, "alpha").1, vec![Delegator {
+        address: Addr::unchecked("charlie"),
+        power: None,
+    }]);
+
+    // The delegator is left out and reported instead of counting as zero
+    let res = snapshot_vote(&gov, &mut chain, token, "alpha", 10_000_000, 0).unwrap();
+    assert!(res.events.iter().any(|event| {
+        event
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "failed-delegators" && attr.value == "charlie")
+    }));
+    tally(&mut chain, &gov, 10_000_000, 0);
+}
+
+#[test]
+fn max_delegators() {
+    let delegators: Vec<String> = (0..=MAX_DELEGATORS)
+        .map(|i| format!("delegator{}", i))
+        .collect();
+    let stakers: Vec<&str> = delegators.iter().map(|delegator| delegator.as_str()).collect();
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_stakers(&stakers).unwrap();
+    let token = token.address.as_str();
+
+    for delegator in &stakers[..MAX_DELEGATORS] {
+        delegate(
+            &gov,
+            &mut chain,
+            token,
+            delegator,
+            delegate_msg("alpha", delegator),
+        )
+        .unwrap();
+    }
+
+    assert!(
+        delegate(
+            &gov,
+            &mut chain,
+            token,
+            stakers[MAX_DELEGATORS],
+            delegate_msg("alpha", stakers[MAX_DELEGATORS]),
+        )
+        .is_err()
+    );
+}
@@ -79,6 +79,7 @@ pub fn init_funding_governance_with_proposal()
                     veto_deposit_loss: Default::default(),
                 }),
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
             public_profile: Profile {
@@ -87,6 +88,7 @@ pub fn init_funding_governance_with_proposal()
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
         }),
@@ -95,6 +97,7 @@ pub fn init_funding_governance_with_proposal()
             code_hash: snip20.code_hash.clone(),
         }),
         vote_token: None,
+        voting_power: None,
         migrator: None,
     }
     .test_init(
@@ -125,6 +128,8 @@ pub fn init_funding_governance_with_proposal()
 
     query_auth::ExecuteMsg::SetViewingKey {
         key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&auth, &mut chain, Addr::unchecked("alpha"), &[])
@@ -168,11 +173,14 @@ fn assembly_to_funding_transition() {
                 veto_threshold: Some(Count::LiteralCount {
                     count: Uint128::new(1),
                 }),
+                weighting: None,
             }),
             disable_funding: false,
             funding: None,
             disable_token: false,
             token: None,
+            disable_timelock: false,
+            timelock: None,
             cancel_deadline: None,
         },
         padding: None,
@@ -293,6 +301,7 @@ fn fake_funding_token() {
             code_hash: other.code_hash,
         }),
         vote_token: None,
+        voting_power: None,
         padding: None,
     }
     .test_exec(
@@ -311,6 +320,8 @@ fn fake_funding_token() {
             amount: Uint128::new(100),
             msg: None,
             memo: None,
+            decoys: None,
+            entropy: None,
             padding: None
         }
         .test_exec(
@@ -334,6 +345,8 @@ fn funding_proposal_without_msg() {
             amount: Uint128::new(100),
             msg: None,
             memo: None,
+            decoys: None,
+            entropy: None,
             padding: None
         }
         .test_exec(
@@ -356,6 +369,8 @@ fn funding_proposal() {
         amount: Uint128::new(100),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
@@ -373,6 +388,8 @@ fn funding_proposal() {
         amount: Uint128::new(100),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
@@ -404,6 +421,8 @@ fn funding_proposal_after_deadline() {
             amount: Uint128::new(100),
             msg: Some(to_binary(&0).unwrap()),
             memo: None,
+            decoys: None,
+            entropy: None,
             padding: None
         }
         .test_exec(
@@ -439,6 +458,8 @@ fn update_when_fully_funded() {
         amount: Uint128::new(1000),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
@@ -456,6 +477,8 @@ fn update_when_fully_funded() {
         amount: Uint128::new(1000),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
@@ -491,6 +514,8 @@ fn update_after_failed_funding() {
         amount: Uint128::new(1000),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
@@ -528,6 +553,8 @@ fn claim_when_not_finished() {
         amount: Uint128::new(1000),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
@@ -561,6 +588,8 @@ fn claim_after_failing() {
         amount: Uint128::new(1000),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
@@ -615,6 +644,8 @@ fn claim_after_passing() {
         amount: Uint128::new(2000),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
@@ -713,6 +744,7 @@ fn init_funding_governance_with_proposal_with_privacy()
                     veto_deposit_loss: Default::default(),
                 }),
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
             public_profile: Profile {
@@ -721,6 +753,7 @@ fn init_funding_governance_with_proposal_with_privacy()
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
         }),
@@ -729,6 +762,7 @@ fn init_funding_governance_with_proposal_with_privacy()
             code_hash: snip20.code_hash.clone(),
         }),
         vote_token: None,
+        voting_power: None,
         migrator: None,
     }
     .test_init(
@@ -764,6 +798,8 @@ fn funding_privacy() {
         amount: Uint128::new(2000),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
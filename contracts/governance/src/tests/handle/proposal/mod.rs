@@ -1,6 +1,10 @@
 pub mod assembly_voting;
+pub mod delegation;
 pub mod funding;
+pub mod snapshot;
+pub mod timelock;
 pub mod voting;
+pub mod weighting;
 
 use crate::tests::{
     admin_only_governance,
@@ -14,7 +18,10 @@ use shade_protocol::{
     c_std::{to_binary, Addr, ContractInfo, StdResult},
     contract_interfaces::{
         governance,
-        governance::proposal::{ProposalMsg, Status},
+        governance::{
+            profile::UpdateProfile,
+            proposal::{MsgOutcome, ProposalMsg, Status},
+        },
     },
     multi_test::App,
     query_auth,
@@ -51,6 +58,8 @@ pub fn init_funding_token(
             for balance in balances {
                 query_auth::ExecuteMsg::SetViewingKey {
                     key: "password".to_string(),
+                    expires_at: None,
+                    contracts: None,
                     padding: None,
                 }
                 .test_exec(&auth, chain, Addr::unchecked(balance.address), &[])
@@ -269,8 +278,187 @@ fn msg_proposal_invalid_msg() {
     )
     .unwrap();
 
+    // Msg failures are recorded instead of reverting the trigger
+    governance::ExecuteMsg::Trigger {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    match prop.status {
+        Status::Failed { msg_index, .. } => assert_eq!(msg_index, 0),
+        _ => assert!(false),
+    };
+    match prop.msg_outcomes.unwrap()[0] {
+        MsgOutcome::Failed { .. } => assert!(true),
+        _ => assert!(false),
+    };
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(100000));
+
+    governance::ExecuteMsg::Cancel {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    assert_eq!(prop.status, Status::Canceled);
+}
+
+fn set_admin_cancel_deadline(chain: &mut App, gov: &ContractInfo, cancel_deadline: u64) {
+    governance::ExecuteMsg::SetProfile {
+        id: 1,
+        profile: UpdateProfile {
+            name: None,
+            enabled: None,
+            disable_assembly: false,
+            assembly: None,
+            disable_funding: false,
+            funding: None,
+            disable_token: false,
+            token: None,
+            disable_timelock: false,
+            timelock: None,
+            cancel_deadline: Some(cancel_deadline),
+        },
+        padding: None,
+    }
+    .test_exec(gov, chain, gov.address.clone(), &[])
+    .unwrap();
+}
+
+fn add_assembly(chain: &mut App, gov: &ContractInfo) {
+    governance::ExecuteMsg::AddAssembly {
+        name: "Other assembly".to_string(),
+        metadata: "some data".to_string(),
+        members: vec![],
+        profile: 1,
+        padding: None,
+    }
+    .test_exec(gov, chain, gov.address.clone(), &[])
+    .unwrap();
+}
+
+fn partially_failing_proposal(chain: &mut App, gov: &ContractInfo) {
+    gov_msg_proposal(chain, gov, "admin", vec![
+        ProposalMsg {
+            target: 0,
+            assembly_msg: 0,
+            msg: to_binary(&vec![
+                serde_json::to_string(&governance::ExecuteMsg::SetAssembly {
+                    id: 1,
+                    name: Some("Random name".to_string()),
+                    metadata: None,
+                    members: None,
+                    profile: None,
+                    padding: None,
+                })
+                .unwrap(),
+            ])
+            .unwrap(),
+            send: vec![],
+        },
+        ProposalMsg {
+            target: 0,
+            assembly_msg: 0,
+            msg: to_binary(&vec![
+                serde_json::to_string(&governance::ExecuteMsg::SetAssembly {
+                    id: 2,
+                    name: Some("Random name".to_string()),
+                    metadata: None,
+                    members: None,
+                    profile: None,
+                    padding: None,
+                })
+                .unwrap(),
+            ])
+            .unwrap(),
+            send: vec![],
+        },
+    ])
+    .unwrap();
+
+    governance::ExecuteMsg::Trigger {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(gov, chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+}
+
+#[test]
+fn retry_failed_proposal() {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    set_admin_cancel_deadline(&mut chain, &gov, 100);
+    partially_failing_proposal(&mut chain, &gov);
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    match prop.status {
+        Status::Failed { msg_index, .. } => assert_eq!(msg_index, 1),
+        _ => assert!(false),
+    };
+    let outcomes = prop.msg_outcomes.unwrap();
+    assert_eq!(outcomes[0], MsgOutcome::Success);
+    match outcomes[1] {
+        MsgOutcome::Failed { .. } => assert!(true),
+        _ => assert!(false),
+    };
+
+    // Retrying without fixing the cause fails again
+    governance::ExecuteMsg::Retry {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    match prop.status {
+        Status::Failed { msg_index, .. } => assert_eq!(msg_index, 1),
+        _ => assert!(false),
+    };
+
+    add_assembly(&mut chain, &gov);
+
+    governance::ExecuteMsg::Retry {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    assert_eq!(prop.status, Status::Success);
+    assert_eq!(prop.msg_outcomes.unwrap(), vec![
+        MsgOutcome::Success,
+        MsgOutcome::Success
+    ]);
+
+    let assembly = get_assemblies(&mut chain, &gov, 2, 3).unwrap()[0].clone();
+    assert_eq!(assembly.name, "Random name".to_string());
+}
+
+#[test]
+fn retry_after_cancel_deadline() {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    set_admin_cancel_deadline(&mut chain, &gov, 100);
+    partially_failing_proposal(&mut chain, &gov);
+    add_assembly(&mut chain, &gov);
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(100));
+
     assert!(
-        governance::ExecuteMsg::Trigger {
+        governance::ExecuteMsg::Retry {
             proposal: 0,
             padding: None
         }
@@ -278,8 +466,6 @@ fn msg_proposal_invalid_msg() {
         .is_err()
     );
 
-    chain.update_block(|block| block.time = block.time.plus_seconds(100000));
-
     governance::ExecuteMsg::Cancel {
         proposal: 0,
         padding: None,
@@ -291,3 +477,32 @@ fn msg_proposal_invalid_msg() {
 
     assert_eq!(prop.status, Status::Canceled);
 }
+
+#[test]
+fn retry_not_failed() {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    gov_generic_proposal(
+        &mut chain,
+        &gov,
+        "admin",
+        governance::ExecuteMsg::SetAssembly {
+            id: 1,
+            name: Some("Random name".to_string()),
+            metadata: None,
+            members: None,
+            profile: None,
+            padding: None,
+        },
+    )
+    .unwrap();
+
+    assert!(
+        governance::ExecuteMsg::Retry {
+            proposal: 0,
+            padding: None
+        }
+        .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+        .is_err()
+    );
+}
@@ -0,0 +1,369 @@
+use crate::tests::{get_proposals, handle::proposal::init_funding_token, init_chain};
+use shade_multi_test::multi::{
+    admin::Admin,
+    basic_staking::BasicStaking,
+    governance::Governance,
+};
+use shade_protocol::{
+    admin,
+    basic_staking,
+    c_std::{to_binary, Addr, ContractInfo, StdResult, Uint128},
+    contract_interfaces::{
+        governance,
+        governance::{
+            profile::{Count, Profile, VoteProfile, Weighting},
+            vote::{DelegationMsg, ReceiveBalanceMsg, Vote},
+            InstantiateMsg,
+            VotingPower,
+        },
+        snip20,
+    },
+    governance::AssemblyInit,
+    multi_test::{App, AppResponse},
+    utils::{asset::Contract, ExecuteCallback, InstantiateCallback, MultiTestable},
+    AnyResult,
+};
+
+fn stake(chain: &mut App, token: &ContractInfo, staking: &ContractInfo, user: &str) {
+    snip20::ExecuteMsg::Send {
+        recipient: staking.address.to_string(),
+        recipient_code_hash: None,
+        amount: Uint128::new(10_000_000),
+        msg: Some(
+            to_binary(&basic_staking::Action::Stake {
+                compound: None,
+                airdrop_task: None,
+            })
+            .unwrap(),
+        ),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None,
+    }
+    .test_exec(token, chain, Addr::unchecked(user), &[])
+    .unwrap();
+}
+
+pub fn init_snapshot_governance_with_proposal(
+) -> StdResult<(App, ContractInfo, ContractInfo, ContractInfo)> {
+    init_snapshot_governance_with_stakers(&[])
+}
+
+/// The extra stakers hold stake when voting starts, same as alpha
+pub fn init_snapshot_governance_with_stakers(
+    stakers: &[&str],
+) -> StdResult<(App, ContractInfo, ContractInfo, ContractInfo)> {
+    let (mut chain, auth) = init_chain();
+
+    let admin = admin::InstantiateMsg {
+        super_admin: Some("admin".to_string()),
+    }
+    .test_init(
+        Admin::default(),
+        &mut chain,
+        Addr::unchecked("admin"),
+        "staking_admin_auth",
+        &[],
+    )
+    .unwrap();
+
+    let token = init_funding_token(
+        &mut chain,
+        Some(
+            ["alpha", "beta"]
+                .iter()
+                .chain(stakers)
+                .map(|user| snip20::InitialBalance {
+                    address: user.to_string(),
+                    amount: Uint128::new(10_000_000),
+                })
+                .collect(),
+        ),
+        Some(&auth),
+    )
+    .unwrap();
+
+    let staking = basic_staking::InstantiateMsg {
+        admin_auth: admin.into(),
+        query_auth: auth.clone().into(),
+        airdrop: None,
+        stake_token: token.clone().into(),
+        unbond_period: Uint128::new(100),
+        max_user_pools: Uint128::one(),
+        viewing_key: "password".to_string(),
+    }
+    .test_init(
+        BasicStaking::default(),
+        &mut chain,
+        Addr::unchecked("admin"),
+        "basic_staking",
+        &[],
+    )
+    .unwrap();
+
+    // Beta has no stake when voting starts
+    for user in ["alpha"].iter().chain(stakers) {
+        stake(&mut chain, &token, &staking, user);
+    }
+
+    let gov = InstantiateMsg {
+        treasury: Addr::unchecked("treasury"),
+        query_auth: Contract {
+            address: auth.address.clone(),
+            code_hash: auth.code_hash.clone(),
+        },
+        assemblies: Some(AssemblyInit {
+            admin_members: vec![Addr::unchecked("alpha"), Addr::unchecked("beta")],
+            admin_profile: Profile {
+                name: "admin".to_string(),
+                enabled: true,
+                assembly: None,
+                funding: None,
+                token: Some(VoteProfile {
+                    deadline: 10000,
+                    threshold: Count::LiteralCount {
+                        count: Uint128::new(5_000_000),
+                    },
+                    yes_threshold: Count::LiteralCount {
+                        count: Uint128::new(5_000_000),
+                    },
+                    veto_threshold: Count::LiteralCount {
+                        count: Uint128::new(5_000_000),
+                    },
+                    weighting: Weighting::Linear,
+                }),
+                timelock: None,
+                cancel_deadline: 0,
+            },
+            public_profile: Profile {
+                name: "public".to_string(),
+                enabled: false,
+                assembly: None,
+                funding: None,
+                token: None,
+                timelock: None,
+                cancel_deadline: 0,
+            },
+        }),
+        funding_token: None,
+        vote_token: Some(Contract {
+            address: token.address.clone(),
+            code_hash: token.code_hash.clone(),
+        }),
+        voting_power: Some(VotingPower::Snapshot {
+            staking: Contract {
+                address: staking.address.clone(),
+                code_hash: staking.code_hash.clone(),
+            },
+        }),
+        migrator: None,
+    }
+    .test_init(
+        Governance::default(),
+        &mut chain,
+        Addr::unchecked("admin"),
+        "governance",
+        &[],
+    )
+    .unwrap();
+
+    governance::ExecuteMsg::AssemblyProposal {
+        assembly: 1,
+        title: "Title".to_string(),
+        metadata: "Text only proposal".to_string(),
+        msgs: None,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
+    .unwrap();
+
+    // Beta stakes after the snapshot was taken
+    chain.update_block(|block| block.time = block.time.plus_seconds(10));
+    stake(&mut chain, &token, &staking, "beta");
+
+    Ok((chain, gov, token, staking))
+}
+
+fn yes_vote(amount: u128, auth: Option<&str>) -> ReceiveBalanceMsg {
+    ReceiveBalanceMsg {
+        vote: Vote {
+            yes: Uint128::new(amount),
+            no: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
+            abstain: Uint128::zero(),
+        },
+        proposal: 0,
+        auth: auth.map(|user| basic_staking::Auth::ViewingKey {
+            key: "password".to_string(),
+            address: user.to_string(),
+        }),
+    }
+}
+
+fn vote(
+    chain: &mut App,
+    gov: &ContractInfo,
+    token: &ContractInfo,
+    voter: &str,
+    vote: ReceiveBalanceMsg,
+) -> AnyResult<AppResponse> {
+    governance::ExecuteMsg::ReceiveBalance {
+        sender: Addr::unchecked(voter),
+        msg: Some(to_binary(&vote).unwrap()),
+        balance: Uint128::new(10_000_000),
+        memo: None,
+    }
+    .test_exec(gov, chain, token.address.clone(), &[])
+}
+
+#[test]
+fn snapshot_vote() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_proposal().unwrap();
+
+    assert!(
+        vote(
+            &mut chain,
+            &gov,
+            &token,
+            "alpha",
+            yes_vote(10_000_000, Some("alpha"))
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn snapshot_vote_missing_auth() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_proposal().unwrap();
+
+    assert!(
+        vote(
+            &mut chain,
+            &gov,
+            &token,
+            "alpha",
+            yes_vote(10_000_000, None)
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn snapshot_vote_wrong_auth() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_proposal().unwrap();
+
+    assert!(
+        vote(
+            &mut chain,
+            &gov,
+            &token,
+            "beta",
+            yes_vote(10_000_000, Some("alpha"))
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn snapshot_vote_staked_after_start() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_proposal().unwrap();
+
+    // Beta holds stake now but did not when voting started
+    assert!(
+        vote(
+            &mut chain,
+            &gov,
+            &token,
+            "beta",
+            yes_vote(1, Some("beta"))
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn snapshot_vote_after_unbond() {
+    let (mut chain, gov, token, staking) = init_snapshot_governance_with_proposal().unwrap();
+
+    basic_staking::ExecuteMsg::Unbond {
+        amount: Uint128::new(10_000_000),
+        compound: None,
+        padding: None,
+    }
+    .test_exec(&staking, &mut chain, Addr::unchecked("alpha"), &[])
+    .unwrap();
+
+    // Power stays fixed to the snapshot
+    assert!(
+        vote(
+            &mut chain,
+            &gov,
+            &token,
+            "alpha",
+            yes_vote(10_000_000, Some("alpha"))
+        )
+        .is_ok()
+    );
+}
+
+fn delegate(
+    chain: &mut App,
+    gov: &ContractInfo,
+    token: &ContractInfo,
+    delegator: &str,
+    auth: Option<&str>,
+) -> AnyResult<AppResponse> {
+    governance::ExecuteMsg::ReceiveBalance {
+        sender: Addr::unchecked(delegator),
+        msg: Some(
+            to_binary(&DelegationMsg::Delegate {
+                to: Addr::unchecked("alpha"),
+                auth: auth.map(|user| basic_staking::Auth::ViewingKey {
+                    key: "password".to_string(),
+                    address: user.to_string(),
+                }),
+            })
+            .unwrap(),
+        ),
+        balance: Uint128::new(10_000_000),
+        memo: None,
+    }
+    .test_exec(gov, chain, token.address.clone(), &[])
+}
+
+#[test]
+fn snapshot_delegation_missing_auth() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_proposal().unwrap();
+
+    assert!(delegate(&mut chain, &gov, &token, "beta", None).is_err());
+    assert!(delegate(&mut chain, &gov, &token, "beta", Some("alpha")).is_err());
+}
+
+#[test]
+fn snapshot_delegated_vote() {
+    let (mut chain, gov, token, _) = init_snapshot_governance_with_proposal().unwrap();
+
+    // Beta exposes a balance but held no stake when voting started
+    delegate(&mut chain, &gov, &token, "beta", Some("beta")).unwrap();
+
+    vote(
+        &mut chain,
+        &gov,
+        &token,
+        "alpha",
+        yes_vote(10_000_000, Some("alpha")),
+    )
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    assert_eq!(
+        prop.public_vote_tally,
+        Some(Vote {
+            yes: Uint128::new(10_000_000),
+            no: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
+            abstain: Uint128::zero(),
+        })
+    );
+}
@@ -0,0 +1,259 @@
+use crate::tests::{admin_only_governance, get_assemblies, get_proposals, gov_generic_proposal};
+use shade_protocol::{
+    c_std::{Addr, ContractInfo},
+    contract_interfaces::{
+        governance,
+        governance::{
+            profile::{UpdateProfile, UpdateTimelockProfile},
+            proposal::Status,
+        },
+    },
+    multi_test::App,
+    utils::ExecuteCallback,
+};
+
+fn set_timelock(
+    chain: &mut App,
+    gov: &ContractInfo,
+    guardian: Option<u16>,
+    guardian_threshold: Option<u16>,
+) {
+    governance::ExecuteMsg::SetProfile {
+        id: 1,
+        profile: UpdateProfile {
+            name: None,
+            enabled: None,
+            disable_assembly: false,
+            assembly: None,
+            disable_funding: false,
+            funding: None,
+            disable_token: false,
+            token: None,
+            disable_timelock: false,
+            timelock: Some(UpdateTimelockProfile {
+                delay: Some(100),
+                guardian,
+                guardian_threshold,
+            }),
+            cancel_deadline: None,
+        },
+        padding: None,
+    }
+    .test_exec(gov, chain, gov.address.clone(), &[])
+    .unwrap();
+}
+
+fn init_timelocked_governance(guardian: bool) -> (App, ContractInfo) {
+    init_guarded_governance(guardian, vec![Addr::unchecked("guardian")], None)
+}
+
+fn init_guarded_governance(
+    guardian: bool,
+    members: Vec<Addr>,
+    guardian_threshold: Option<u16>,
+) -> (App, ContractInfo) {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    // Guardian assembly will be ID 2
+    governance::ExecuteMsg::AddAssembly {
+        name: "Guardian".to_string(),
+        metadata: "Vetoes queued proposals".to_string(),
+        members,
+        profile: 1,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, gov.address.clone(), &[])
+    .unwrap();
+
+    set_timelock(
+        &mut chain,
+        &gov,
+        if guardian { Some(2) } else { None },
+        guardian_threshold,
+    );
+
+    gov_generic_proposal(
+        &mut chain,
+        &gov,
+        "admin",
+        governance::ExecuteMsg::SetAssembly {
+            id: 1,
+            name: Some("Random name".to_string()),
+            metadata: None,
+            members: None,
+            profile: None,
+            padding: None,
+        },
+    )
+    .unwrap();
+
+    (chain, gov)
+}
+
+fn trigger(chain: &mut App, gov: &ContractInfo) -> bool {
+    governance::ExecuteMsg::Trigger {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(gov, chain, Addr::unchecked("admin"), &[])
+    .is_ok()
+}
+
+fn veto(chain: &mut App, gov: &ContractInfo, sender: &str) -> bool {
+    governance::ExecuteMsg::VetoQueued {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(gov, chain, Addr::unchecked(sender), &[])
+    .is_ok()
+}
+
+#[test]
+fn timelocked_proposal() {
+    let (mut chain, gov) = init_timelocked_governance(true);
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    match prop.status {
+        Status::Queued { start, end } => assert_eq!(end - start, 100),
+        _ => assert!(false),
+    };
+
+    assert!(!trigger(&mut chain, &gov));
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    assert!(trigger(&mut chain, &gov));
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    assert_eq!(prop.status, Status::Success);
+
+    let assembly = get_assemblies(&mut chain, &gov, 1, 2).unwrap()[0].clone();
+
+    assert_eq!(assembly.name, "Random name".to_string());
+}
+
+#[test]
+fn update_timelocked_proposal() {
+    let (mut chain, gov) = init_timelocked_governance(true);
+
+    assert!(
+        governance::ExecuteMsg::Update {
+            proposal: 0,
+            padding: None
+        }
+        .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+        .is_err()
+    );
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    governance::ExecuteMsg::Update {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    match prop.status {
+        Status::Passed { .. } => assert!(true),
+        _ => assert!(false),
+    };
+}
+
+#[test]
+fn guardian_veto() {
+    let (mut chain, gov) = init_timelocked_governance(true);
+
+    assert!(veto(&mut chain, &gov, "guardian"));
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    assert_eq!(prop.status, Status::GuardianVetoed { assembly: 2 });
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    assert!(!trigger(&mut chain, &gov));
+}
+
+#[test]
+fn unauthorised_guardian_veto() {
+    let (mut chain, gov) = init_timelocked_governance(true);
+
+    assert!(!veto(&mut chain, &gov, "admin"));
+}
+
+#[test]
+fn guardian_veto_after_timelock() {
+    let (mut chain, gov) = init_timelocked_governance(true);
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(100));
+
+    assert!(!veto(&mut chain, &gov, "guardian"));
+}
+
+#[test]
+fn guardian_veto_without_guardian() {
+    let (mut chain, gov) = init_timelocked_governance(false);
+
+    assert!(!veto(&mut chain, &gov, "guardian"));
+}
+
+#[test]
+fn guardian_veto_requires_majority() {
+    let (mut chain, gov) = init_guarded_governance(
+        true,
+        vec![
+            Addr::unchecked("guardian"),
+            Addr::unchecked("guardian2"),
+            Addr::unchecked("guardian3"),
+        ],
+        None,
+    );
+
+    assert!(veto(&mut chain, &gov, "guardian"));
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    match prop.status {
+        Status::Queued { .. } => assert!(true),
+        _ => assert!(false),
+    };
+
+    // The same member cannot veto twice
+    assert!(!veto(&mut chain, &gov, "guardian"));
+
+    assert!(veto(&mut chain, &gov, "guardian2"));
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    assert_eq!(prop.status, Status::GuardianVetoed { assembly: 2 });
+}
+
+#[test]
+fn guardian_veto_threshold() {
+    let (mut chain, gov) = init_guarded_governance(
+        true,
+        vec![
+            Addr::unchecked("guardian"),
+            Addr::unchecked("guardian2"),
+            Addr::unchecked("guardian3"),
+        ],
+        Some(3),
+    );
+
+    assert!(veto(&mut chain, &gov, "guardian"));
+    assert!(veto(&mut chain, &gov, "guardian2"));
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    match prop.status {
+        Status::Queued { .. } => assert!(true),
+        _ => assert!(false),
+    };
+
+    assert!(veto(&mut chain, &gov, "guardian3"));
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    assert_eq!(prop.status, Status::GuardianVetoed { assembly: 2 });
+}
@@ -5,7 +5,7 @@ use shade_protocol::{
     contract_interfaces::{
         governance,
         governance::{
-            profile::{Count, Profile, VoteProfile},
+            profile::{Count, Profile, VoteProfile, Weighting},
             proposal::Status,
             vote::Vote,
             InstantiateMsg,
@@ -68,6 +68,8 @@ pub fn init_voting_governance_with_proposal() -> StdResult<(App, ContractInfo, S
     // Register governance
     query_auth::ExecuteMsg::SetViewingKey {
         key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&auth, &mut chain, Addr::unchecked("alpha"), &[])
@@ -75,6 +77,8 @@ pub fn init_voting_governance_with_proposal() -> StdResult<(App, ContractInfo, S
 
     query_auth::ExecuteMsg::SetViewingKey {
         key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&auth, &mut chain, Addr::unchecked("beta"), &[])
@@ -82,6 +86,8 @@ pub fn init_voting_governance_with_proposal() -> StdResult<(App, ContractInfo, S
 
     query_auth::ExecuteMsg::SetViewingKey {
         key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&auth, &mut chain, Addr::unchecked("charlie"), &[])
@@ -115,7 +121,9 @@ pub fn init_voting_governance_with_proposal() -> StdResult<(App, ContractInfo, S
                     veto_threshold: Count::LiteralCount {
                         count: Uint128::new(15_000_000),
                     },
+                    weighting: Weighting::Linear,
                 }),
+                timelock: None,
                 cancel_deadline: 0,
             },
             public_profile: Profile {
@@ -124,6 +132,7 @@ pub fn init_voting_governance_with_proposal() -> StdResult<(App, ContractInfo, S
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
         }),
@@ -132,6 +141,7 @@ pub fn init_voting_governance_with_proposal() -> StdResult<(App, ContractInfo, S
             address: stkd_tkn.address.clone(),
             code_hash: stkd_tkn.code_hash.clone(),
         }),
+        voting_power: None,
         migrator: None,
     }
     .test_init(
@@ -239,7 +249,8 @@ fn invalid_vote() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -266,7 +277,8 @@ fn vote_after_deadline() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -291,7 +303,8 @@ fn vote_yes() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -333,7 +346,8 @@ fn vote_abstain() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::new(1_000_000)
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -375,7 +389,8 @@ fn vote_no() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -417,7 +432,8 @@ fn vote_veto() {
                     no_with_veto: Uint128::new(1_000_000),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -460,7 +476,8 @@ fn vote_passed() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -479,7 +496,8 @@ fn vote_passed() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -526,7 +544,8 @@ fn vote_abstained() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::new(10_000_000)
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -545,7 +564,8 @@ fn vote_abstained() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::new(10_000_000)
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -586,7 +606,8 @@ fn vote_rejected() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -605,7 +626,8 @@ fn vote_rejected() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -646,7 +668,8 @@ fn vote_vetoed() {
                     no_with_veto: Uint128::new(10_000_000),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -665,7 +688,8 @@ fn vote_vetoed() {
                     no_with_veto: Uint128::new(10_000_000),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -706,7 +730,8 @@ fn vote_no_quorum() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -725,7 +750,8 @@ fn vote_no_quorum() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -766,7 +792,8 @@ fn vote_total() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -785,7 +812,8 @@ fn vote_total() {
                     no_with_veto: Uint128::new(10_000),
                     abstain: Uint128::zero()
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -804,7 +832,8 @@ fn vote_total() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::new(10_000),
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -846,7 +875,8 @@ fn update_vote() {
                     no_with_veto: Uint128::new(22_000),
                     abstain: Uint128::zero(),
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -878,7 +908,8 @@ fn update_vote() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero(),
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -916,7 +947,8 @@ fn vote_count() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero(),
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -936,7 +968,8 @@ fn vote_count() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero(),
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -977,7 +1010,8 @@ fn vote_count_percentage() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero(),
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -997,7 +1031,8 @@ fn vote_count_percentage() {
                     no_with_veto: Uint128::zero(),
                     abstain: Uint128::zero(),
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
@@ -0,0 +1,196 @@
+use crate::tests::{
+    get_proposals,
+    handle::proposal::{
+        snapshot::init_snapshot_governance_with_proposal,
+        voting::{init_voting_governance_with_proposal, vote},
+    },
+};
+use shade_protocol::{
+    basic_staking,
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::governance::{
+        self,
+        profile::{Count, UpdateProfile, UpdateVoteProfile, Weighting},
+        vote::{ReceiveBalanceMsg, Vote},
+    },
+    multi_test::{App, AppResponse},
+    utils::{asset::Contract, ExecuteCallback},
+    AnyResult,
+};
+
+fn set_weighting(chain: &mut App, gov: &ContractInfo, weighting: Weighting) {
+    set_token_profile(chain, gov, None, weighting).unwrap();
+}
+
+fn set_token_profile(
+    chain: &mut App,
+    gov: &ContractInfo,
+    threshold: Option<Count>,
+    weighting: Weighting,
+) -> AnyResult<AppResponse> {
+    governance::ExecuteMsg::SetProfile {
+        id: 1,
+        profile: UpdateProfile {
+            name: None,
+            enabled: None,
+            disable_assembly: false,
+            assembly: None,
+            disable_funding: false,
+            funding: None,
+            disable_token: false,
+            token: Some(UpdateVoteProfile {
+                deadline: None,
+                threshold,
+                yes_threshold: None,
+                veto_threshold: None,
+                weighting: Some(weighting),
+            }),
+            disable_timelock: false,
+            timelock: None,
+            cancel_deadline: None,
+        },
+        padding: None,
+    }
+    .test_exec(gov, chain, gov.address.clone(), &[])
+}
+
+fn yes_vote(amount: u128, auth: Option<&str>) -> ReceiveBalanceMsg {
+    ReceiveBalanceMsg {
+        vote: Vote {
+            yes: Uint128::new(amount),
+            no: Uint128::zero(),
+            no_with_veto: Uint128::zero(),
+            abstain: Uint128::zero(),
+        },
+        proposal: 0,
+        auth: auth.map(|user| basic_staking::Auth::ViewingKey {
+            key: "password".to_string(),
+            address: user.to_string(),
+        }),
+    }
+}
+
+fn yes_tally(chain: &mut App, gov: &ContractInfo) -> Uint128 {
+    get_proposals(chain, gov, 0, 2).unwrap()[0]
+        .clone()
+        .public_vote_tally
+        .unwrap()
+        .yes
+}
+
+#[test]
+fn quadratic_vote() {
+    let (mut chain, gov, stkd_tkn, _auth) = init_voting_governance_with_proposal().unwrap();
+
+    set_weighting(&mut chain, &gov, Weighting::Quadratic);
+
+    vote(
+        &gov,
+        &mut chain,
+        &stkd_tkn,
+        "alpha",
+        yes_vote(16_000_000, None),
+        Uint128::new(20_000_000),
+    )
+    .unwrap();
+
+    assert_eq!(yes_tally(&mut chain, &gov), Uint128::new(4_000));
+
+    // Revoting replaces the weighted vote
+    vote(
+        &gov,
+        &mut chain,
+        &stkd_tkn,
+        "alpha",
+        yes_vote(9_000_000, None),
+        Uint128::new(20_000_000),
+    )
+    .unwrap();
+
+    assert_eq!(yes_tally(&mut chain, &gov), Uint128::new(3_000));
+}
+
+#[test]
+fn conviction_vote() {
+    let (mut chain, gov, token, staking) = init_snapshot_governance_with_proposal().unwrap();
+
+    set_weighting(&mut chain, &gov, Weighting::Conviction {
+        staking: Contract {
+            address: staking.address.clone(),
+            code_hash: staking.code_hash.clone(),
+        },
+        period: 100,
+    });
+
+    // Unbonding period is 100 so the remaining lockup doubles the power
+    basic_staking::ExecuteMsg::Unbond {
+        amount: Uint128::new(5_000_000),
+        compound: None,
+        padding: None,
+    }
+    .test_exec(&staking, &mut chain, Addr::unchecked("alpha"), &[])
+    .unwrap();
+
+    governance::ExecuteMsg::ReceiveBalance {
+        sender: Addr::unchecked("alpha"),
+        msg: Some(to_binary(&yes_vote(5_000_000, Some("alpha"))).unwrap()),
+        balance: Uint128::new(10_000_000),
+        memo: None,
+    }
+    .test_exec(&gov, &mut chain, token.address.clone(), &[])
+    .unwrap();
+
+    assert_eq!(yes_tally(&mut chain, &gov), Uint128::new(10_000_000));
+}
+
+#[test]
+fn conviction_vote_without_unbonding() {
+    let (mut chain, gov, token, staking) = init_snapshot_governance_with_proposal().unwrap();
+
+    set_weighting(&mut chain, &gov, Weighting::Conviction {
+        staking: Contract {
+            address: staking.address.clone(),
+            code_hash: staking.code_hash.clone(),
+        },
+        period: 100,
+    });
+
+    governance::ExecuteMsg::ReceiveBalance {
+        sender: Addr::unchecked("alpha"),
+        msg: Some(to_binary(&yes_vote(5_000_000, Some("alpha"))).unwrap()),
+        balance: Uint128::new(10_000_000),
+        memo: None,
+    }
+    .test_exec(&gov, &mut chain, token.address.clone(), &[])
+    .unwrap();
+
+    assert_eq!(yes_tally(&mut chain, &gov), Uint128::new(5_000_000));
+}
+
+#[test]
+fn percentage_threshold_requires_linear_weighting() {
+    let (mut chain, gov, _, _auth) = init_voting_governance_with_proposal().unwrap();
+
+    assert!(
+        set_token_profile(
+            &mut chain,
+            &gov,
+            Some(Count::Percentage { percent: 5000 }),
+            Weighting::Quadratic,
+        )
+        .is_err()
+    );
+
+    assert!(
+        set_token_profile(
+            &mut chain,
+            &gov,
+            Some(Count::Percentage { percent: 5000 }),
+            Weighting::Linear,
+        )
+        .is_ok()
+    );
+
+    // Switching the weighting of a percentage profile is rejected too
+    assert!(set_token_profile(&mut chain, &gov, None, Weighting::Quadratic).is_err());
+}
@@ -5,7 +5,7 @@ use shade_protocol::{
     c_std::{to_binary, Addr, ContractInfo, StdResult, Uint128},
     governance,
     governance::{
-        profile::{Count, FundProfile, Profile, VoteProfile},
+        profile::{Count, FundProfile, Profile, VoteProfile, Weighting},
         vote::Vote,
         AssemblyInit,
         InstantiateMsg,
@@ -72,6 +72,7 @@ pub fn init_gov() -> StdResult<(App, ContractInfo, ContractInfo, u64)> {
                             veto_threshold: Count::LiteralCount {
                                 count: Uint128::new(1),
                             },
+                            weighting: Weighting::Linear,
                         }),
                         funding: Some(FundProfile {
                             deadline: 1000,
@@ -80,6 +81,7 @@ pub fn init_gov() -> StdResult<(App, ContractInfo, ContractInfo, u64)> {
                             veto_deposit_loss: Default::default(),
                         }),
                         token: None,
+                        timelock: None,
                         cancel_deadline: 0,
                     },
                     public_profile: Profile {
@@ -88,6 +90,7 @@ pub fn init_gov() -> StdResult<(App, ContractInfo, ContractInfo, u64)> {
                         assembly: None,
                         funding: None,
                         token: None,
+                        timelock: None,
                         cancel_deadline: 0,
                     },
                 }),
@@ -96,6 +99,7 @@ pub fn init_gov() -> StdResult<(App, ContractInfo, ContractInfo, u64)> {
                     code_hash: snip20.code_hash.clone(),
                 }),
                 vote_token: None,
+                voting_power: None,
                 migrator: None,
             },
             &vec![],
@@ -152,6 +156,8 @@ fn fund_proposal(
         amount: Uint128::new(100),
         msg: Some(to_binary(&proposal).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(
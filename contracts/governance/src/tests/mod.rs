@@ -65,6 +65,7 @@ pub fn admin_only_governance() -> StdResult<(App, ContractInfo)> {
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
             public_profile: Profile {
@@ -73,11 +74,13 @@ pub fn admin_only_governance() -> StdResult<(App, ContractInfo)> {
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
         }),
         funding_token: None,
         vote_token: None,
+        voting_power: None,
         migrator: None,
     }
     .test_init(
@@ -5,8 +5,17 @@ use crate::tests::{
     get_config,
     get_contract,
     get_profiles,
+    gov_generic_proposal,
+};
+use shade_protocol::{
+    c_std::{Addr, ContractInfo},
+    contract_interfaces::{
+        governance,
+        governance::proposal::{ProposalFilter, ProposalSummary, StatusKind},
+    },
+    multi_test::App,
+    utils::{ExecuteCallback, Query},
 };
-use shade_protocol::{contract_interfaces::governance, utils::Query};
 
 #[test]
 fn query_total_assembly_msg() {
@@ -178,3 +187,197 @@ fn query_config() {
 
     get_config(&mut chain, &gov).unwrap();
 }
+
+fn init_proposals() -> (App, ContractInfo) {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    for i in 0..3 {
+        chain.update_block(|block| block.time = block.time.plus_seconds(100));
+
+        gov_generic_proposal(
+            &mut chain,
+            &gov,
+            "admin",
+            governance::ExecuteMsg::SetAssembly {
+                id: 1,
+                name: Some(format!("Name {}", i)),
+                metadata: None,
+                members: None,
+                profile: None,
+                padding: None,
+            },
+        )
+        .unwrap();
+    }
+
+    governance::ExecuteMsg::Trigger {
+        proposal: 1,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    (chain, gov)
+}
+
+fn filter() -> ProposalFilter {
+    ProposalFilter {
+        status: None,
+        assembly: None,
+        proposer: None,
+        from: None,
+        to: None,
+    }
+}
+
+fn filtered_proposals(
+    chain: &App,
+    gov: &ContractInfo,
+    filter: ProposalFilter,
+    cursor: Option<u32>,
+    limit: u32,
+) -> (Vec<u32>, Option<u32>) {
+    let query: governance::QueryAnswer = governance::QueryMsg::FilteredProposals {
+        filter,
+        cursor,
+        limit,
+    }
+    .test_query(gov, chain)
+    .unwrap();
+
+    match query {
+        governance::QueryAnswer::ProposalSummaries { props, cursor } => (
+            props.iter().map(|prop: &ProposalSummary| prop.id).collect(),
+            cursor,
+        ),
+        _ => panic!("Returned wrong enum"),
+    }
+}
+
+#[test]
+fn query_filtered_proposals_status() {
+    let (chain, gov) = init_proposals();
+
+    let (props, _) = filtered_proposals(
+        &chain,
+        &gov,
+        ProposalFilter {
+            status: Some(StatusKind::Passed),
+            ..filter()
+        },
+        None,
+        10,
+    );
+    assert_eq!(props, vec![0, 2]);
+
+    let (props, _) = filtered_proposals(
+        &chain,
+        &gov,
+        ProposalFilter {
+            status: Some(StatusKind::Success),
+            ..filter()
+        },
+        None,
+        10,
+    );
+    assert_eq!(props, vec![1]);
+}
+
+#[test]
+fn query_filtered_proposals_assembly_and_proposer() {
+    let (chain, gov) = init_proposals();
+
+    let (props, _) = filtered_proposals(
+        &chain,
+        &gov,
+        ProposalFilter {
+            assembly: Some(1),
+            ..filter()
+        },
+        None,
+        10,
+    );
+    assert_eq!(props, vec![0, 1, 2]);
+
+    let (props, _) = filtered_proposals(
+        &chain,
+        &gov,
+        ProposalFilter {
+            assembly: Some(0),
+            ..filter()
+        },
+        None,
+        10,
+    );
+    assert!(props.is_empty());
+
+    let (props, _) = filtered_proposals(
+        &chain,
+        &gov,
+        ProposalFilter {
+            proposer: Some(Addr::unchecked("admin")),
+            status: Some(StatusKind::Passed),
+            ..filter()
+        },
+        None,
+        10,
+    );
+    assert_eq!(props, vec![0, 2]);
+
+    let (props, _) = filtered_proposals(
+        &chain,
+        &gov,
+        ProposalFilter {
+            proposer: Some(Addr::unchecked("random")),
+            ..filter()
+        },
+        None,
+        10,
+    );
+    assert!(props.is_empty());
+}
+
+#[test]
+fn query_filtered_proposals_time_range() {
+    let (chain, gov) = init_proposals();
+
+    let created = chain.block_info().time.seconds();
+
+    let (props, _) = filtered_proposals(
+        &chain,
+        &gov,
+        ProposalFilter {
+            from: Some(created - 100),
+            to: Some(created - 100),
+            ..filter()
+        },
+        None,
+        10,
+    );
+    assert_eq!(props, vec![1]);
+
+    let (props, _) = filtered_proposals(
+        &chain,
+        &gov,
+        ProposalFilter {
+            from: Some(created - 100),
+            ..filter()
+        },
+        None,
+        10,
+    );
+    assert_eq!(props, vec![1, 2]);
+}
+
+#[test]
+fn query_filtered_proposals_pagination() {
+    let (chain, gov) = init_proposals();
+
+    let (props, cursor) = filtered_proposals(&chain, &gov, filter(), None, 2);
+    assert_eq!(props, vec![0, 1]);
+    assert_eq!(cursor, Some(2));
+
+    let (props, cursor) = filtered_proposals(&chain, &gov, filter(), cursor, 2);
+    assert_eq!(props, vec![2]);
+    assert_eq!(cursor, None);
+}
@@ -24,6 +24,8 @@ fn proposals() {
 
     query_auth::ExecuteMsg::SetViewingKey {
         key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&auth, &mut chain, Addr::unchecked("admin"), &[])
@@ -43,6 +45,7 @@ fn proposals() {
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
             public_profile: Profile {
@@ -51,11 +54,13 @@ fn proposals() {
                 assembly: None,
                 funding: None,
                 token: None,
+                timelock: None,
                 cancel_deadline: 0,
             },
         }),
         funding_token: None,
         vote_token: None,
+        voting_power: None,
         migrator: None,
     };
 
@@ -194,6 +199,8 @@ fn funding() {
         amount: Uint128::new(100),
         msg: Some(to_binary(&0).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&snip20, &mut chain, Addr::unchecked("alpha"), &[])
@@ -238,7 +245,8 @@ fn votes() {
                     no_with_veto: Default::default(),
                     abstain: Default::default(),
                 },
-                proposal: 0
+                proposal: 0,
+                auth: None
             },
             Uint128::new(20_000_000)
         )
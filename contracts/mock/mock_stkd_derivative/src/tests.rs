@@ -358,6 +358,8 @@ fn test() {
         amount: Uint128::new(998),
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }.test_exec(&other_snip, &mut chain, admin.clone(), &[]).unwrap();
 
@@ -437,6 +439,8 @@ fn test() {
             to: None,
         }).unwrap()),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }.test_exec(&other_snip, &mut chain, user.clone(), &[]).unwrap();
 
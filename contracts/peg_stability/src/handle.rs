@@ -16,7 +16,7 @@ use shade_protocol::{
 
 pub fn try_update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     admin_auth: Option<Contract>,
     snip20: Option<Contract>,
@@ -29,6 +29,7 @@ pub fn try_update_config(
     let mut config = Config::load(deps.storage)?;
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::StabilityAdmin,
         info.sender.to_string(),
         &config.admin_auth,
@@ -69,7 +70,7 @@ pub fn try_update_config(
 
 pub fn try_set_pairs(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     pairs: Vec<ArbPair>,
 ) -> StdResult<Response> {
@@ -77,6 +78,7 @@ pub fn try_set_pairs(
     let mut config = Config::load(deps.storage)?;
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::StabilityAdmin,
         info.sender.to_string(),
         &config.admin_auth,
@@ -130,6 +132,7 @@ pub fn try_append_pairs(
     //Admin-only
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::StabilityAdmin,
         info.sender.to_string(),
         &config.admin_auth,
@@ -163,7 +166,7 @@ pub fn try_append_pairs(
 
 pub fn try_remove_pair(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     pair_address: String,
 ) -> StdResult<Response> {
@@ -171,6 +174,7 @@ pub fn try_remove_pair(
     let mut config = Config::load(deps.storage)?;
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::StabilityAdmin,
         info.sender.to_string(),
         &config.admin_auth,
@@ -56,7 +56,9 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         },
         // No VK interactions
         ContractStatus::DisableVK => match msg {
-            ExecuteMsg::CreateViewingKey { .. } | ExecuteMsg::SetViewingKey { .. } => {
+            ExecuteMsg::CreateViewingKey { .. }
+            | ExecuteMsg::SetViewingKey { .. }
+            | ExecuteMsg::RevokeViewingKey { .. } => {
                 return Err(StdError::generic_err("unauthorized"));
             }
             _ => {}
@@ -65,7 +67,9 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ContractStatus::DisableAll => match msg {
             ExecuteMsg::CreateViewingKey { .. }
             | ExecuteMsg::SetViewingKey { .. }
-            | ExecuteMsg::BlockPermitKey { .. } => {
+            | ExecuteMsg::RevokeViewingKey { .. }
+            | ExecuteMsg::BlockPermitKey { .. }
+            | ExecuteMsg::RotateAll { .. } => {
                 return Err(StdError::generic_err("unauthorized"));
             }
             _ => {}
@@ -78,36 +82,58 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             ExecuteMsg::SetRunState { state, .. } => {
                 handle::try_set_run_state(deps, env, info, state)
             }
-            ExecuteMsg::SetViewingKey { key, .. } => {
-                handle::try_set_viewing_key(deps, env, info, key)
-            }
-            ExecuteMsg::CreateViewingKey { entropy, .. } => {
-                handle::try_create_viewing_key(deps, env, info, entropy)
+            ExecuteMsg::SetViewingKey {
+                key,
+                expires_at,
+                contracts,
+                ..
+            } => handle::try_set_viewing_key(deps, env, info, key, expires_at, contracts),
+            ExecuteMsg::CreateViewingKey {
+                entropy,
+                expires_at,
+                contracts,
+                ..
+            } => handle::try_create_viewing_key(deps, env, info, entropy, expires_at, contracts),
+            ExecuteMsg::RevokeViewingKey { key, .. } => {
+                handle::try_revoke_viewing_key(deps, env, info, key)
             }
             ExecuteMsg::BlockPermitKey { key, .. } => {
                 handle::try_block_permit_key(deps, env, info, key)
             }
+            ExecuteMsg::RotateAll { .. } => handle::try_rotate_all(deps, env, info),
         },
         RESPONSE_BLOCK_SIZE,
     )
 }
 
 #[shd_entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     let status = ContractStatus::load(deps.storage)?;
     match status {
         // Do nothing
         ContractStatus::Default => {}
         // No permit interactions
         ContractStatus::DisablePermit => {
-            if let QueryMsg::ValidatePermit { .. } = msg {
-                return Err(StdError::generic_err("unauthorized"));
+            match &msg {
+                QueryMsg::ValidatePermit { .. } => {
+                    return Err(StdError::generic_err("unauthorized"));
+                }
+                QueryMsg::BatchValidate { permits, .. } if !permits.is_empty() => {
+                    return Err(StdError::generic_err("unauthorized"));
+                }
+                _ => {}
             }
         }
         // No VK interactions
         ContractStatus::DisableVK => {
-            if let QueryMsg::ValidateViewingKey { .. } = msg {
-                return Err(StdError::generic_err("unauthorized"));
+            match &msg {
+                QueryMsg::ValidateViewingKey { .. } => {
+                    return Err(StdError::generic_err("unauthorized"));
+                }
+                QueryMsg::BatchValidate { viewing_keys, .. } if !viewing_keys.is_empty() => {
+                    return Err(StdError::generic_err("unauthorized"));
+                }
+                _ => {}
             }
         }
         // Nothing
@@ -122,8 +148,18 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     pad_query_result(
         to_binary(&match msg {
             QueryMsg::Config { .. } => query::config(deps)?,
-            QueryMsg::ValidateViewingKey { user, key } => query::validate_vk(deps, user, key)?,
+            QueryMsg::ValidateViewingKey {
+                user,
+                key,
+                contract,
+            } => query::validate_vk(deps, env, user, key, contract)?,
             QueryMsg::ValidatePermit { permit } => query::validate_permit(deps, permit)?,
+            QueryMsg::BatchValidate {
+                viewing_keys,
+                permits,
+                contract,
+            } => query::batch_validate(deps, env, viewing_keys, permits, contract)?,
+            QueryMsg::KeyEpoch { user } => query::key_epoch(deps, user)?,
         }),
         RESPONSE_BLOCK_SIZE,
     )
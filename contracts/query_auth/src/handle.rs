@@ -1,8 +1,16 @@
 use shade_protocol::{
     admin::helpers::{validate_admin, AdminPermissions},
-    c_std::{to_binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult},
+    c_std::{to_binary, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult},
     contract_interfaces::query_auth::{
-        auth::{HashedKey, Key, PermitKey},
+        auth::{
+            HashedKey,
+            Key,
+            KeyEpoch,
+            PermitKey,
+            RestrictedKey,
+            RestrictedKeys,
+            MAX_RESTRICTED_KEYS,
+        },
         Admin,
         ContractStatus,
         ExecuteAnswer,
@@ -17,11 +25,12 @@ use shade_protocol::{
 
 use shade_protocol::utils::asset::Contract;
 
-fn user_authorized(deps: &Deps, _env: Env, info: &MessageInfo) -> StdResult<()> {
+fn user_authorized(deps: &Deps, env: Env, info: &MessageInfo) -> StdResult<()> {
     let contract = Admin::load(deps.storage)?.0;
 
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::QueryAuthAdmin,
         info.sender.clone(),
         &contract,
@@ -54,32 +63,102 @@ pub fn try_set_run_state(
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::SetRunState { status: Success })?))
 }
 
+/// Unrestricted keys replace the main key, others are added to the user's restricted keys.
+fn save_key(
+    deps: DepsMut,
+    env: &Env,
+    user: Addr,
+    key: &Key,
+    expires_at: Option<u64>,
+    contracts: Option<Vec<String>>,
+) -> StdResult<()> {
+    if expires_at.is_none() && contracts.is_none() {
+        return HashedKey(key.hash()).save(deps.storage, user);
+    }
+
+    let now = env.block.time.seconds();
+    if let Some(expires_at) = expires_at {
+        if expires_at <= now {
+            return Err(StdError::generic_err("Viewing key must expire in the future"));
+        }
+    }
+    let contracts = contracts
+        .map(|contracts| {
+            contracts
+                .iter()
+                .map(|contract| deps.api.addr_validate(contract))
+                .collect::<StdResult<Vec<Addr>>>()
+        })
+        .transpose()?;
+
+    let mut keys = RestrictedKeys::may_load(deps.storage, user.clone())?
+        .unwrap_or(RestrictedKeys(vec![]))
+        .0;
+    keys.retain(|restricted| !restricted.is_expired(now));
+    if keys.len() >= MAX_RESTRICTED_KEYS {
+        return Err(StdError::generic_err(format!(
+            "Cannot have more than {} restricted viewing keys",
+            MAX_RESTRICTED_KEYS
+        )));
+    }
+    keys.push(RestrictedKey {
+        hash: key.hash(),
+        expires_at,
+        contracts,
+    });
+    RestrictedKeys(keys).save(deps.storage, user)
+}
+
 pub fn try_create_viewing_key(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     entropy: String,
+    expires_at: Option<u64>,
+    contracts: Option<Vec<String>>,
 ) -> StdResult<Response> {
     let seed = RngSeed::load(deps.storage)?.0;
 
     let key = Key::generate(&info, &env, seed.as_slice(), &entropy.as_ref());
 
-    HashedKey(key.hash()).save(deps.storage, info.sender)?;
+    save_key(deps, &env, info.sender, &key, expires_at, contracts)?;
 
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::CreateViewingKey { key: key.0 })?))
 }
 
 pub fn try_set_viewing_key(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     key: String,
+    expires_at: Option<u64>,
+    contracts: Option<Vec<String>>,
 ) -> StdResult<Response> {
-    HashedKey(Key(key).hash()).save(deps.storage, info.sender)?;
+    save_key(deps, &env, info.sender, &Key(key), expires_at, contracts)?;
 
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::SetViewingKey { status: Success })?))
 }
 
+pub fn try_revoke_viewing_key(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    key: String,
+) -> StdResult<Response> {
+    if let Some(RestrictedKeys(mut keys)) =
+        RestrictedKeys::may_load(deps.storage, info.sender.clone())?
+    {
+        keys.retain(|restricted| !Key(key.clone()).compare(&restricted.hash));
+        RestrictedKeys(keys).save(deps.storage, info.sender)?;
+    }
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::RevokeViewingKey {
+            status: Success,
+        })?),
+    )
+}
+
 pub fn try_block_permit_key(
     deps: DepsMut,
     _env: Env,
@@ -93,3 +172,8 @@ pub fn try_block_permit_key(
         })?),
     )
 }
+
+pub fn try_rotate_all(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Response> {
+    let epoch = KeyEpoch::rotate(deps.storage, info.sender)?;
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RotateAll { epoch })?))
+}
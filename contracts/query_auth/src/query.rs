@@ -1,11 +1,14 @@
 use shade_protocol::{
-    c_std::{Addr, Deps, StdResult},
+    c_std::{Addr, Deps, Env, StdError, StdResult},
     contract_interfaces::query_auth::{
-        auth::{Key, PermitKey},
+        auth::{Key, KeyEpoch, PermitKey},
         Admin,
         ContractStatus,
+        PermitValidation,
         QueryAnswer,
         QueryPermit,
+        ViewingKeyCheck,
+        MAX_BATCH_SIZE,
     },
     utils::storage::plus::{ItemStorage, MapStorage},
 };
@@ -17,17 +20,82 @@ pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
-pub fn validate_vk(deps: Deps, user: Addr, key: String) -> StdResult<QueryAnswer> {
+pub fn validate_vk(
+    deps: Deps,
+    env: Env,
+    user: Addr,
+    key: String,
+    contract: Option<Addr>,
+) -> StdResult<QueryAnswer> {
     Ok(QueryAnswer::ValidateViewingKey {
-        is_valid: Key::verify(deps.storage, user, key)?,
+        is_valid: Key::verify_restricted(
+            deps.storage,
+            user,
+            key,
+            env.block.time.seconds(),
+            contract.as_ref(),
+        )?,
     })
 }
 
-pub fn validate_permit(deps: Deps, permit: QueryPermit) -> StdResult<QueryAnswer> {
+fn check_permit(deps: Deps, permit: QueryPermit) -> StdResult<PermitValidation> {
     let user = permit.validate(deps.api, None)?.as_addr(None)?;
 
-    Ok(QueryAnswer::ValidatePermit {
+    // Permits must be signed for the current epoch, older ones were revoked by a rotation
+    // and newer ones would outlive the rotations before them
+    let is_stale = permit.params.epoch.unwrap_or_default()
+        != KeyEpoch::current(deps.storage, user.clone())?;
+
+    Ok(PermitValidation {
         user: user.clone(),
-        is_revoked: PermitKey::may_load(deps.storage, (user, permit.params.key))?.is_some(),
+        is_revoked: is_stale
+            || PermitKey::may_load(deps.storage, (user, permit.params.key))?.is_some(),
+    })
+}
+
+pub fn validate_permit(deps: Deps, permit: QueryPermit) -> StdResult<QueryAnswer> {
+    let PermitValidation { user, is_revoked } = check_permit(deps, permit)?;
+
+    Ok(QueryAnswer::ValidatePermit { user, is_revoked })
+}
+
+pub fn batch_validate(
+    deps: Deps,
+    env: Env,
+    viewing_keys: Vec<ViewingKeyCheck>,
+    permits: Vec<QueryPermit>,
+    contract: Option<Addr>,
+) -> StdResult<QueryAnswer> {
+    if viewing_keys.len() + permits.len() > MAX_BATCH_SIZE {
+        return Err(StdError::generic_err(format!(
+            "Cannot validate more than {} items at once",
+            MAX_BATCH_SIZE
+        )));
+    }
+
+    // A failing item is reported as invalid instead of failing the whole batch
+    let now = env.block.time.seconds();
+    let viewing_keys = viewing_keys
+        .into_iter()
+        .map(|check| {
+            Key::verify_restricted(deps.storage, check.user, check.key, now, contract.as_ref())
+                .unwrap_or(false)
+        })
+        .collect();
+
+    let permits = permits
+        .into_iter()
+        .map(|permit| check_permit(deps, permit).ok())
+        .collect();
+
+    Ok(QueryAnswer::BatchValidate {
+        viewing_keys,
+        permits,
+    })
+}
+
+pub fn key_epoch(deps: Deps, user: Addr) -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::KeyEpoch {
+        epoch: KeyEpoch::current(deps.storage, user)?,
     })
 }
@@ -86,6 +86,8 @@ fn runstate_block_permits() {
 
     let msg = query_auth::ExecuteMsg::SetViewingKey {
         key: "key".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     };
 
@@ -96,6 +98,8 @@ fn runstate_block_permits() {
 
     let msg = query_auth::ExecuteMsg::CreateViewingKey {
         entropy: "random".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     };
 
@@ -137,6 +141,8 @@ fn runstate_block_vks() {
 
     let msg = query_auth::ExecuteMsg::SetViewingKey {
         key: "key".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     };
 
@@ -147,6 +153,8 @@ fn runstate_block_vks() {
 
     let msg = query_auth::ExecuteMsg::CreateViewingKey {
         entropy: "random".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     };
 
@@ -188,6 +196,8 @@ fn runstate_block_all() {
 
     let msg = query_auth::ExecuteMsg::SetViewingKey {
         key: "key".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     };
 
@@ -198,6 +208,8 @@ fn runstate_block_all() {
 
     let msg = query_auth::ExecuteMsg::CreateViewingKey {
         entropy: "random".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     };
 
@@ -218,6 +230,8 @@ fn set_vk() {
     assert!(
         query_auth::ExecuteMsg::SetViewingKey {
             key: "password".to_string(),
+            expires_at: None,
+            contracts: None,
             padding: None
         }
         .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
@@ -231,6 +245,8 @@ fn create_vk() {
 
     let data = query_auth::ExecuteMsg::CreateViewingKey {
         entropy: "blah".to_string(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }
     .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
@@ -272,3 +288,50 @@ fn block_permit_key() {
 
     assert!(validate_permit(&chain, &auth).unwrap().1);
 }
+
+#[test]
+fn rotate_all() {
+    let (mut chain, auth) = init_contract().unwrap();
+    let permit_user = "secret19rla95xfp22je7hyxv7h0nhm6cwtwahu69zraq";
+
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
+        padding: None,
+    }
+    .test_exec(&auth, &mut chain, Addr::unchecked(permit_user), &[])
+    .unwrap();
+
+    assert!(validate_vk(&chain, &auth, permit_user, "password").unwrap());
+    assert!(!validate_permit(&chain, &auth).unwrap().1);
+
+    let data = query_auth::ExecuteMsg::RotateAll { padding: None }
+        .test_exec(&auth, &mut chain, Addr::unchecked(permit_user), &[])
+        .unwrap()
+        .data
+        .unwrap();
+
+    match from_binary(&data).unwrap() {
+        query_auth::ExecuteAnswer::RotateAll { epoch } => assert_eq!(epoch, 1),
+        _ => assert!(false),
+    }
+
+    // Keys and permits issued before the rotation are no longer valid
+    assert!(!validate_vk(&chain, &auth, permit_user, "password").unwrap());
+    assert!(validate_permit(&chain, &auth).unwrap().1);
+
+    // Other users are untouched
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
+        padding: None,
+    }
+    .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+    .unwrap();
+    query_auth::ExecuteMsg::RotateAll { padding: None }
+        .test_exec(&auth, &mut chain, Addr::unchecked(permit_user), &[])
+        .unwrap();
+    assert!(validate_vk(&chain, &auth, "user", "password").unwrap());
+}
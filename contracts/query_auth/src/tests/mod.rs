@@ -51,6 +51,9 @@ pub fn init_contract() -> StdResult<(App, ContractInfo)> {
             admin::RegistryAction::GrantAccess {
                 permissions: vec![AdminPermissions::QueryAuthAdmin.into_string()],
                 user: "admin".to_string(),
+                contract: None,
+                not_before: None,
+                expires_at: None,
             },
         ],
     }
@@ -64,7 +67,8 @@ pub fn get_permit() -> QueryPermit {
     QueryPermit {
         params: PermitData {
             key: "key".to_string(),
-            data: Binary::from_base64("c29tZSBzdHJpbmc=").unwrap()
+            data: Binary::from_base64("c29tZSBzdHJpbmc=").unwrap(),
+            epoch: None,
         },
         signature: PermitSignature {
             pub_key: PubKey::new(
@@ -94,9 +98,20 @@ pub fn get_config(chain: &App, auth: &ContractInfo) -> StdResult<(Contract, Cont
 }
 
 pub fn validate_vk(chain: &App, auth: &ContractInfo, user: &str, key: &str) -> StdResult<bool> {
+    validate_vk_for(chain, auth, user, key, None)
+}
+
+pub fn validate_vk_for(
+    chain: &App,
+    auth: &ContractInfo,
+    user: &str,
+    key: &str,
+    contract: Option<&str>,
+) -> StdResult<bool> {
     let query: query_auth::QueryAnswer = query_auth::QueryMsg::ValidateViewingKey {
         user: Addr::unchecked(user),
         key: key.to_string(),
+        contract: contract.map(Addr::unchecked),
     }
     .test_query(&auth, &chain)?;
 
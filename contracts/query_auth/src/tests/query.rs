@@ -1,8 +1,19 @@
-use crate::tests::{get_config, get_permit, init_contract, validate_permit, validate_vk};
+use crate::tests::{
+    get_config,
+    get_permit,
+    init_contract,
+    validate_permit,
+    validate_vk,
+    validate_vk_for,
+};
 use shade_protocol::{
-    c_std::{Addr},
-    contract_interfaces::{query_auth, query_auth::ContractStatus},
-    utils::{ExecuteCallback},
+    c_std::{Addr, ContractInfo, StdResult, Timestamp},
+    contract_interfaces::{
+        query_auth,
+        query_auth::{ContractStatus, QueryPermit},
+    },
+    multi_test::App,
+    utils::{ExecuteCallback, Query},
 };
 
 #[test]
@@ -23,6 +34,8 @@ fn vk_validation() {
     assert!(
         query_auth::ExecuteMsg::SetViewingKey {
             key: "password".to_string(),
+            expires_at: None,
+            contracts: None,
             padding: None
         }
         .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
@@ -34,6 +47,79 @@ fn vk_validation() {
     assert!(validate_vk(&chain, &auth, "user", "password").unwrap());
 }
 
+#[test]
+fn restricted_vk_validation() {
+    let (mut chain, auth) = init_contract().unwrap();
+    chain.update_block(|block| block.time = Timestamp::from_seconds(100));
+
+    // Expiration must be in the future
+    assert!(
+        query_auth::ExecuteMsg::SetViewingKey {
+            key: "tracker".to_string(),
+            expires_at: Some(100),
+            contracts: None,
+            padding: None
+        }
+        .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+        .is_err()
+    );
+
+    assert!(
+        query_auth::ExecuteMsg::SetViewingKey {
+            key: "password".to_string(),
+            expires_at: None,
+            contracts: None,
+            padding: None
+        }
+        .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+        .is_ok()
+    );
+
+    assert!(
+        query_auth::ExecuteMsg::SetViewingKey {
+            key: "tracker".to_string(),
+            expires_at: Some(1000),
+            contracts: Some(vec!["staking".to_string()]),
+            padding: None
+        }
+        .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+        .is_ok()
+    );
+
+    // The main key is kept
+    assert!(validate_vk_for(&chain, &auth, "user", "password", Some("governance")).unwrap());
+
+    assert!(validate_vk_for(&chain, &auth, "user", "tracker", Some("staking")).unwrap());
+    assert!(!validate_vk_for(&chain, &auth, "user", "tracker", Some("governance")).unwrap());
+    assert!(!validate_vk(&chain, &auth, "user", "tracker").unwrap());
+
+    chain.update_block(|block| block.time = Timestamp::from_seconds(1000));
+    assert!(!validate_vk_for(&chain, &auth, "user", "tracker", Some("staking")).unwrap());
+
+    assert!(
+        query_auth::ExecuteMsg::SetViewingKey {
+            key: "tracker".to_string(),
+            expires_at: None,
+            contracts: Some(vec!["staking".to_string()]),
+            padding: None
+        }
+        .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+        .is_ok()
+    );
+    assert!(validate_vk_for(&chain, &auth, "user", "tracker", Some("staking")).unwrap());
+
+    assert!(
+        query_auth::ExecuteMsg::RevokeViewingKey {
+            key: "tracker".to_string(),
+            padding: None
+        }
+        .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+        .is_ok()
+    );
+    assert!(!validate_vk_for(&chain, &auth, "user", "tracker", Some("staking")).unwrap());
+    assert!(validate_vk(&chain, &auth, "user", "password").unwrap());
+}
+
 #[test]
 fn permit_validation() {
     let _permit = get_permit();
@@ -48,3 +134,123 @@ fn permit_validation() {
         Addr::unchecked("secret19rla95xfp22je7hyxv7h0nhm6cwtwahu69zraq")
     );
 }
+
+fn batch_validate(
+    chain: &App,
+    auth: &ContractInfo,
+    viewing_keys: Vec<(&str, &str)>,
+    permits: Vec<QueryPermit>,
+) -> StdResult<(Vec<bool>, Vec<Option<query_auth::PermitValidation>>)> {
+    let query: query_auth::QueryAnswer = query_auth::QueryMsg::BatchValidate {
+        viewing_keys: viewing_keys
+            .into_iter()
+            .map(|(user, key)| query_auth::ViewingKeyCheck {
+                user: Addr::unchecked(user),
+                key: key.to_string(),
+            })
+            .collect(),
+        permits,
+        contract: None,
+    }
+    .test_query(auth, chain)?;
+
+    match query {
+        query_auth::QueryAnswer::BatchValidate {
+            viewing_keys,
+            permits,
+        } => Ok((viewing_keys, permits)),
+        _ => panic!("unexpected query answer"),
+    }
+}
+
+#[test]
+fn batch_validation() {
+    let (mut chain, auth) = init_contract().unwrap();
+
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "password".to_string(),
+        expires_at: None,
+        contracts: None,
+        padding: None,
+    }
+    .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+    .unwrap();
+
+    let (vks, permits) = batch_validate(
+        &chain,
+        &auth,
+        vec![("user", "password"), ("user", "wrong"), ("other", "password")],
+        vec![get_permit(); 2],
+    )
+    .unwrap();
+    assert_eq!(vks, vec![true, false, false]);
+    assert_eq!(permits.len(), 2);
+    assert!(permits.iter().all(|permit| !permit.as_ref().unwrap().is_revoked));
+    assert_eq!(
+        permits[0].as_ref().unwrap().user,
+        Addr::unchecked("secret19rla95xfp22je7hyxv7h0nhm6cwtwahu69zraq")
+    );
+
+    // An invalid permit does not fail the rest of the batch
+    let mut tampered = get_permit();
+    tampered.params.key = "other_key".to_string();
+    let (vks, permits) = batch_validate(
+        &chain,
+        &auth,
+        vec![("user", "password")],
+        vec![tampered, get_permit()],
+    )
+    .unwrap();
+    assert_eq!(vks, vec![true]);
+    assert!(permits[0].is_none());
+    assert!(permits[1].is_some());
+
+    // Batches are capped
+    assert!(
+        batch_validate(
+            &chain,
+            &auth,
+            vec![],
+            vec![get_permit(); query_auth::MAX_BATCH_SIZE + 1]
+        )
+        .is_err()
+    );
+
+    // Status gating applies per item type
+    query_auth::ExecuteMsg::SetRunState {
+        state: ContractStatus::DisablePermit,
+        padding: None,
+    }
+    .test_exec(&auth, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    assert!(batch_validate(&chain, &auth, vec![("user", "password")], vec![get_permit()]).is_err());
+    assert!(batch_validate(&chain, &auth, vec![("user", "password")], vec![]).is_ok());
+}
+
+#[test]
+fn key_epoch() {
+    let (mut chain, auth) = init_contract().unwrap();
+
+    let epoch = |chain: &App| -> u64 {
+        match (query_auth::QueryMsg::KeyEpoch {
+            user: Addr::unchecked("user"),
+        })
+        .test_query(&auth, chain)
+        .unwrap()
+        {
+            query_auth::QueryAnswer::KeyEpoch { epoch } => epoch,
+            _ => panic!("unexpected query answer"),
+        }
+    };
+
+    assert_eq!(epoch(&chain), 0);
+
+    query_auth::ExecuteMsg::RotateAll { padding: None }
+        .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+        .unwrap();
+    query_auth::ExecuteMsg::RotateAll { padding: None }
+        .test_exec(&auth, &mut chain, Addr::unchecked("user"), &[])
+        .unwrap();
+
+    assert_eq!(epoch(&chain), 2);
+}
@@ -36,7 +36,7 @@ use shade_protocol::{
 
 pub fn try_update_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     shade_admin: Option<Contract>,
     shd_token: Option<Contract>,
@@ -49,6 +49,7 @@ pub fn try_update_config(
     let mut config = Config::load(deps.storage)?;
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::SkyAdmin,
         info.sender.to_string(),
         &config.shade_admin,
@@ -100,7 +101,7 @@ pub fn try_update_config(
 
 pub fn try_set_cycles(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     cycles_to_set: Vec<Cycle>,
 ) -> StdResult<Response> {
@@ -108,6 +109,7 @@ pub fn try_set_cycles(
     let shade_admin = Config::load(deps.storage)?.shade_admin;
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::SkyAdmin,
         info.sender.to_string(),
         &shade_admin,
@@ -130,7 +132,7 @@ pub fn try_set_cycles(
 
 pub fn try_append_cycle(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     cycles_to_add: Vec<Cycle>,
 ) -> StdResult<Response> {
@@ -138,6 +140,7 @@ pub fn try_append_cycle(
     let shade_admin = Config::load(deps.storage)?.shade_admin;
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::SkyAdmin,
         info.sender.to_string(),
         &shade_admin,
@@ -162,7 +165,7 @@ pub fn try_append_cycle(
 
 pub fn try_update_cycle(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     cycle: Cycle,
     index: Uint128,
@@ -172,6 +175,7 @@ pub fn try_update_cycle(
     let shade_admin = Config::load(deps.storage)?.shade_admin;
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::SkyAdmin,
         info.sender.to_string(),
         &shade_admin,
@@ -190,7 +194,7 @@ pub fn try_update_cycle(
 
 pub fn try_remove_cycle(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     index: Uint128,
 ) -> StdResult<Response> {
@@ -199,6 +203,7 @@ pub fn try_remove_cycle(
     let shade_admin = Config::load(deps.storage)?.shade_admin;
     validate_admin(
         &deps.querier,
+        &env,
         AdminPermissions::SkyAdmin,
         info.sender.to_string(),
         &shade_admin,
@@ -9,15 +9,32 @@ use crate::{
             try_transfer_from,
         },
         burning::{try_batch_burn_from, try_burn, try_burn_from},
-        minting::{try_add_minters, try_batch_mint, try_mint, try_remove_minters, try_set_minters},
-        transfers::{try_batch_send, try_batch_transfer, try_send, try_transfer},
+        minting::{
+            try_add_minters,
+            try_batch_mint,
+            try_mint,
+            try_remove_minters,
+            try_set_minter_allowance,
+            try_set_minters,
+        },
+        transfers::{
+            try_batch_send,
+            try_batch_transfer,
+            try_reject_vesting_grant,
+            try_send,
+            try_transfer,
+            try_transfer_vesting,
+        },
+        load_decoys,
         try_change_admin,
         try_create_viewing_key,
         try_deposit,
+        try_prune_history,
         try_redeem,
         try_register_receive,
         try_revoke_permit,
         try_set_contract_status,
+        try_set_history_retention,
         try_set_viewing_key,
         try_update_query_auth,
     },
@@ -107,10 +124,13 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 recipient,
                 amount,
                 memo,
+                decoys,
+                entropy,
                 ..
             } => {
                 let recipient = deps.api.addr_validate(recipient.as_str())?;
-                try_transfer(deps, env, info, recipient, amount, memo)
+                let decoys = load_decoys(deps.as_ref(), &env, &info.sender, decoys, entropy)?;
+                try_transfer(deps, env, info, recipient, amount, memo, decoys)
             }
 
             ExecuteMsg::Send {
@@ -119,9 +139,12 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 amount,
                 msg,
                 memo,
+                decoys,
+                entropy,
                 ..
             } => {
                 let recipient = deps.api.addr_validate(recipient.as_str())?;
+                let decoys = load_decoys(deps.as_ref(), &env, &info.sender, decoys, entropy)?;
                 try_send(
                     deps,
                     env,
@@ -131,16 +154,41 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                     amount,
                     memo,
                     msg,
+                    decoys,
                 )
             }
 
-            ExecuteMsg::BatchTransfer { actions, .. } => {
-                try_batch_transfer(deps, env, info, actions)
-            }
+            ExecuteMsg::BatchTransfer {
+                actions, entropy, ..
+            } => try_batch_transfer(deps, env, info, actions, entropy),
 
-            ExecuteMsg::BatchSend { actions, .. } => try_batch_send(deps, env, info, actions),
+            ExecuteMsg::BatchSend {
+                actions, entropy, ..
+            } => try_batch_send(deps, env, info, actions, entropy),
 
-            ExecuteMsg::Burn { amount, memo, .. } => try_burn(deps, env, info, amount, memo),
+            ExecuteMsg::Burn {
+                amount,
+                memo,
+                decoys,
+                entropy,
+                ..
+            } => {
+                let decoys = load_decoys(deps.as_ref(), &env, &info.sender, decoys, entropy)?;
+                try_burn(deps, env, info, amount, memo, decoys)
+            }
+            ExecuteMsg::TransferVesting {
+                recipient,
+                amount,
+                schedule,
+                memo,
+                ..
+            } => {
+                let recipient = deps.api.addr_validate(recipient.as_str())?;
+                try_transfer_vesting(deps, env, info, recipient, amount, schedule, memo)
+            }
+            ExecuteMsg::RejectVestingGrant { index, .. } => {
+                try_reject_vesting_grant(deps, env, info, index)
+            }
 
             ExecuteMsg::RegisterReceive { code_hash, .. } => {
                 try_register_receive(deps, env, info, code_hash)
@@ -228,12 +276,17 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 recipient,
                 amount,
                 memo,
+                decoys,
+                entropy,
                 ..
             } => {
                 let recipient = deps.api.addr_validate(recipient.as_str())?;
-                try_mint(deps, env, info, recipient, amount, memo)
+                let decoys = load_decoys(deps.as_ref(), &env, &info.sender, decoys, entropy)?;
+                try_mint(deps, env, info, recipient, amount, memo, decoys)
             }
-            ExecuteMsg::BatchMint { actions, .. } => try_batch_mint(deps, env, info, actions),
+            ExecuteMsg::BatchMint {
+                actions, entropy, ..
+            } => try_batch_mint(deps, env, info, actions, entropy),
             ExecuteMsg::AddMinters { minters, .. } => {
                 let minters = validate_vec(deps.api, minters)?;
                 try_add_minters(deps, env, info, minters)
@@ -246,11 +299,24 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 let minters = validate_vec(deps.api, minters)?;
                 try_set_minters(deps, env, info, minters)
             }
+            ExecuteMsg::SetMinterAllowance { minter, limit, .. } => {
+                let minter = deps.api.addr_validate(minter.as_str())?;
+                try_set_minter_allowance(deps, env, info, minter, limit)
+            }
             ExecuteMsg::ChangeAdmin { address, .. } => {
                 let address = deps.api.addr_validate(address.as_str())?;
                 try_change_admin(deps, env, info, address)
             }
             ExecuteMsg::UpdateQueryAuth { auth } => try_update_query_auth(deps, env, info, auth),
+            ExecuteMsg::SetHistoryRetention { retention, .. } => {
+                try_set_history_retention(deps, env, info, retention)
+            }
+            ExecuteMsg::PruneHistory {
+                accounts, limit, ..
+            } => {
+                let accounts = validate_vec(deps.api, accounts)?;
+                try_prune_history(deps, env, info, accounts, limit)
+            }
             ExecuteMsg::SetContractStatus { level, .. } => {
                 try_set_contract_status(deps, env, info, level)
             }
@@ -263,7 +329,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
     )
 }
 
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     pad_query_result(
         to_binary(&match msg {
             QueryMsg::TokenInfo {} => query::token_info(deps)?,
@@ -271,6 +337,8 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             QueryMsg::ContractStatus {} => query::contract_status(deps)?,
             QueryMsg::ExchangeRate {} => query::exchange_rate(deps)?,
             QueryMsg::Minters {} => query::minters(deps)?,
+            QueryMsg::MinterAllowances {} => query::minter_allowances(deps, env)?,
+            QueryMsg::ListChannels {} => query::list_channels()?,
 
             QueryMsg::WithPermit {
                 permit,
@@ -334,6 +402,32 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
                         query::allowance(deps, owner, spender)?
                     }
+                    QueryWithPermit::AllowancesGiven {
+                        owner,
+                        page,
+                        page_size,
+                    } => {
+                        let owner = deps.api.addr_validate(&owner)?;
+
+                        if !params.contains(Permission::Allowance) || owner != account {
+                            return Err(unauthorized_permit(Permission::Allowance));
+                        }
+
+                        query::allowances_given(deps, owner, page.unwrap_or(0), page_size)?
+                    }
+                    QueryWithPermit::AllowancesReceived {
+                        spender,
+                        page,
+                        page_size,
+                    } => {
+                        let spender = deps.api.addr_validate(&spender)?;
+
+                        if !params.contains(Permission::Allowance) || spender != account {
+                            return Err(unauthorized_permit(Permission::Allowance));
+                        }
+
+                        query::allowances_received(deps, spender, page.unwrap_or(0), page_size)?
+                    }
                     QueryWithPermit::Balance {} => {
                         if !params.contains(Permission::Balance) {
                             return Err(unauthorized_permit(Permission::Balance));
@@ -365,6 +459,20 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                             page_size,
                         )?
                     }
+                    QueryWithPermit::VestingSchedules {} => {
+                        if !params.contains(Permission::Balance) {
+                            return Err(unauthorized_permit(Permission::Balance));
+                        }
+
+                        query::vesting_schedules(deps, env, account)?
+                    }
+                    QueryWithPermit::ChannelInfo { channels } => {
+                        if !params.contains(Permission::Owner) {
+                            return Err(unauthorized_permit(Permission::Owner));
+                        }
+
+                        query::channel_info(deps, env, account, channels)?
+                    }
                 }
             }
 
@@ -376,17 +484,43 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 } => {
                     let owner = deps.api.addr_validate(&owner)?;
                     let spender = deps.api.addr_validate(&spender)?;
-                    if try_authenticate_vk(&deps, owner.clone(), key.clone())?
-                        || try_authenticate_vk(&deps, spender.clone(), key)?
+                    if try_authenticate_vk(&deps, &env, owner.clone(), key.clone())?
+                        || try_authenticate_vk(&deps, &env, spender.clone(), key)?
                     {
                         query::allowance(deps, owner, spender)?
                     } else {
                         return Err(invalid_viewing_key());
                     }
                 }
+                QueryMsg::AllowancesGiven {
+                    owner,
+                    key,
+                    page,
+                    page_size,
+                } => {
+                    let owner = deps.api.addr_validate(&owner)?;
+                    if try_authenticate_vk(&deps, &env, owner.clone(), key)? {
+                        query::allowances_given(deps, owner, page.unwrap_or(0), page_size)?
+                    } else {
+                        return Err(invalid_viewing_key());
+                    }
+                }
+                QueryMsg::AllowancesReceived {
+                    spender,
+                    key,
+                    page,
+                    page_size,
+                } => {
+                    let spender = deps.api.addr_validate(&spender)?;
+                    if try_authenticate_vk(&deps, &env, spender.clone(), key)? {
+                        query::allowances_received(deps, spender, page.unwrap_or(0), page_size)?
+                    } else {
+                        return Err(invalid_viewing_key());
+                    }
+                }
                 QueryMsg::Balance { address, key } => {
                     let address = deps.api.addr_validate(&address)?;
-                    if try_authenticate_vk(&deps, address.clone(), key.clone())? {
+                    if try_authenticate_vk(&deps, &env, address.clone(), key.clone())? {
                         query::balance(deps, address.clone())?
                     } else {
                         return Err(invalid_viewing_key());
@@ -399,7 +533,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     page_size,
                 } => {
                     let address = deps.api.addr_validate(&address)?;
-                    if try_authenticate_vk(&deps, address.clone(), key.clone())? {
+                    if try_authenticate_vk(&deps, &env, address.clone(), key.clone())? {
                         query::transfer_history(
                             deps,
                             address.clone(),
@@ -417,7 +551,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                     page_size,
                 } => {
                     let address = deps.api.addr_validate(&address)?;
-                    if try_authenticate_vk(&deps, address.clone(), key.clone())? {
+                    if try_authenticate_vk(&deps, &env, address.clone(), key.clone())? {
                         query::transaction_history(
                             deps,
                             address.clone(),
@@ -428,6 +562,14 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                         return Err(invalid_viewing_key());
                     }
                 }
+                QueryMsg::VestingSchedules { address, key } => {
+                    let address = deps.api.addr_validate(&address)?;
+                    if try_authenticate_vk(&deps, &env, address.clone(), key)? {
+                        query::vesting_schedules(deps, env, address)?
+                    } else {
+                        return Err(invalid_viewing_key());
+                    }
+                }
                 _ => return Err(not_authenticated_msg()),
             },
         }),
@@ -435,9 +577,11 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     )
 }
 
-fn try_authenticate_vk(deps: &Deps, address: Addr, key: String) -> StdResult<bool> {
+fn try_authenticate_vk(deps: &Deps, env: &Env, address: Addr, key: String) -> StdResult<bool> {
     match QueryAuth::may_load(deps.storage)? {
         None => Key::verify(deps.storage, address, key),
-        Some(authenticator) => authenticate_vk(address, key, &deps.querier, &authenticator.0),
+        Some(authenticator) => {
+            authenticate_vk(address, key, &deps.querier, env, &authenticator.0)
+        }
     }
 }
@@ -1,9 +1,13 @@
-use crate::handle::transfers::{try_send_impl, try_transfer_impl};
+use crate::handle::{
+    notify,
+    transfers::{try_send_impl, try_transfer_impl},
+};
 use shade_protocol::{
     c_std::{to_binary, Addr, Binary, DepsMut, Env, MessageInfo, Response, StdResult, Uint128},
     contract_interfaces::snip20::{
         batch,
-        manager::{Allowance, CoinInfo},
+        manager::{Allowance, CoinInfo, Decoys},
+        notification::Notifier,
         ExecuteAnswer,
     },
     utils::{
@@ -39,7 +43,7 @@ pub fn try_increase_allowance(
         allowance.expiration = expiration;
     }
 
-    allowance.save(deps.storage, (owner.clone(), spender.clone()))?;
+    allowance.set(deps.storage, &owner, &spender)?;
 
     Ok(
         Response::new().set_data(to_binary(&ExecuteAnswer::IncreaseAllowance {
@@ -76,7 +80,7 @@ pub fn try_decrease_allowance(
         allowance.expiration = expiration;
     }
 
-    allowance.save(deps.storage, (owner.clone(), spender.clone()))?;
+    allowance.set(deps.storage, &owner, &spender)?;
 
     Ok(
         Response::new().set_data(to_binary(&ExecuteAnswer::IncreaseAllowance {
@@ -106,9 +110,18 @@ pub fn try_transfer_from(
         memo,
         denom,
         &env.block,
+        &Decoys::default(),
     )?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::TransferFrom { status: Success })?))
+    let mut notifier = Notifier::default();
+    notifier.received(&recipient, &owner, amount);
+
+    notify(
+        Response::new().set_data(to_binary(&ExecuteAnswer::TransferFrom { status: Success })?),
+        deps.storage,
+        &env,
+        notifier,
+    )
 }
 
 pub fn try_batch_transfer_from(
@@ -119,23 +132,31 @@ pub fn try_batch_transfer_from(
 ) -> StdResult<Response> {
     let denom = CoinInfo::load(deps.storage)?.symbol;
     let block = &env.block;
+    let mut notifier = Notifier::default();
     for action in actions {
+        let owner = deps.api.addr_validate(action.owner.as_str())?;
+        let recipient = deps.api.addr_validate(action.recipient.as_str())?;
         try_transfer_impl(
             deps.storage,
             &info.sender,
-            Some(&deps.api.addr_validate(action.owner.as_str())?),
-            &deps.api.addr_validate(action.recipient.as_str())?,
+            Some(&owner),
+            &recipient,
             action.amount,
             action.memo,
             denom.clone(),
             block,
+            &Decoys::default(),
         )?;
+        notifier.received(&recipient, &owner, action.amount);
     }
 
-    Ok(
+    notify(
         Response::new().set_data(to_binary(&ExecuteAnswer::BatchTransferFrom {
             status: Success,
         })?),
+        deps.storage,
+        &env,
+        notifier,
     )
 }
 
@@ -164,11 +185,20 @@ pub fn try_send_from(
         msg,
         denom,
         &env.block,
+        &Decoys::default(),
     )?;
 
-    Ok(Response::new()
-        .set_data(to_binary(&ExecuteAnswer::SendFrom { status: Success })?)
-        .add_submessages(messages))
+    let mut notifier = Notifier::default();
+    notifier.received(&recipient, &owner, amount);
+
+    notify(
+        Response::new()
+            .set_data(to_binary(&ExecuteAnswer::SendFrom { status: Success })?)
+            .add_submessages(messages),
+        deps.storage,
+        &env,
+        notifier,
+    )
 }
 
 pub fn try_batch_send_from(
@@ -180,24 +210,34 @@ pub fn try_batch_send_from(
     let mut messages = vec![];
     let sender = info.sender;
     let denom = CoinInfo::load(deps.storage)?.symbol;
+    let mut notifier = Notifier::default();
 
     for action in actions {
+        let owner = deps.api.addr_validate(action.owner.as_str())?;
+        let recipient = deps.api.addr_validate(action.recipient.as_str())?;
         try_send_impl(
             deps.storage,
             &mut messages,
             &sender,
-            Some(&deps.api.addr_validate(action.owner.as_str())?),
-            &deps.api.addr_validate(action.recipient.as_str())?,
+            Some(&owner),
+            &recipient,
             action.recipient_code_hash,
             action.amount,
             action.memo,
             action.msg,
             denom.clone(),
             &env.block,
+            &Decoys::default(),
         )?;
+        notifier.received(&recipient, &owner, action.amount);
     }
 
-    Ok(Response::new()
-        .set_data(to_binary(&ExecuteAnswer::BatchSendFrom { status: Success })?)
-        .add_submessages(messages))
+    notify(
+        Response::new()
+            .set_data(to_binary(&ExecuteAnswer::BatchSendFrom { status: Success })?)
+            .add_submessages(messages),
+        deps.storage,
+        &env,
+        notifier,
+    )
 }
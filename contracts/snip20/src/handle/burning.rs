@@ -3,7 +3,7 @@ use shade_protocol::{
     contract_interfaces::snip20::{
         batch,
         errors::burning_disabled,
-        manager::{Allowance, Balance, CoinInfo, Config, TotalSupply},
+        manager::{Allowance, Balance, CoinInfo, Config, Decoys, TotalSupply},
         transaction_history::store_burn,
         ExecuteAnswer,
     },
@@ -16,6 +16,7 @@ pub fn try_burn(
     info: MessageInfo,
     amount: Uint128,
     memo: Option<String>,
+    decoys: Decoys,
 ) -> StdResult<Response> {
     let sender = &info.sender;
     let denom = CoinInfo::load(deps.storage)?.symbol;
@@ -25,7 +26,7 @@ pub fn try_burn(
         return Err(burning_disabled());
     }
 
-    Balance::sub(deps.storage, amount, sender)?;
+    Balance::sub_with_decoys(deps.storage, amount, sender, &decoys, &env.block)?;
     // Dec total supply
     TotalSupply::sub(deps.storage, amount)?;
 
@@ -59,7 +60,7 @@ pub fn try_burn_from(
     }
 
     Allowance::spend(deps.storage, &owner, &sender, amount, &env.block)?;
-    Balance::sub(deps.storage, amount, &owner)?;
+    Balance::sub(deps.storage, amount, &owner, &env.block)?;
     // Dec total supply
     TotalSupply::sub(deps.storage, amount)?;
 
@@ -105,6 +106,7 @@ pub fn try_batch_burn_from(
             deps.storage,
             action.amount,
             &deps.api.addr_validate(action.owner.as_str())?,
+            &env.block,
         )?;
 
         // Dec total supply
@@ -1,13 +1,40 @@
+use crate::handle::{decoy_rng, notify, validate_decoys};
 use shade_protocol::{
-    c_std::{to_binary, Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128},
+    c_std::{
+        to_binary,
+        Addr,
+        Binary,
+        DepsMut,
+        Env,
+        MessageInfo,
+        Response,
+        StdResult,
+        Storage,
+        Uint128,
+    },
     contract_interfaces::snip20::{
         batch,
         errors::{minting_disabled, not_admin, not_minter},
-        manager::{Admin, Balance, CoinInfo, Config, Minters, TotalSupply},
+        manager::{
+            Admin,
+            Balance,
+            CoinInfo,
+            Config,
+            Decoys,
+            MaxSupply,
+            MintLimit,
+            MinterAllowance,
+            Minters,
+            TotalSupply,
+        },
+        notification::Notifier,
         transaction_history::store_mint,
         ExecuteAnswer,
     },
-    utils::{generic_response::ResponseStatus::Success, storage::plus::ItemStorage},
+    utils::{
+        generic_response::ResponseStatus::Success,
+        storage::plus::{ItemStorage, MapStorage},
+    },
 };
 
 fn try_mint_impl(
@@ -18,8 +45,9 @@ fn try_mint_impl(
     denom: String,
     memo: Option<String>,
     block: &shade_protocol::c_std::BlockInfo,
+    decoys: &Decoys,
 ) -> StdResult<()> {
-    Balance::add(storage, amount, recipient)?;
+    Balance::add_with_decoys(storage, amount, recipient, decoys)?;
     store_mint(storage, minter, recipient, amount, denom, memo, block)?;
     Ok(())
 }
@@ -31,6 +59,7 @@ pub fn try_mint(
     recipient: Addr,
     amount: Uint128,
     memo: Option<String>,
+    decoys: Decoys,
 ) -> StdResult<Response> {
     // Mint enabled
     if !Config::mint_enabled(deps.storage)? {
@@ -40,10 +69,11 @@ pub fn try_mint(
     if !Minters::load(deps.storage)?.0.contains(&info.sender) {
         return Err(not_minter(&info.sender));
     }
+    MinterAllowance::spend(deps.storage, &info.sender, amount, &env.block)?;
     // Inc total supply
-    TotalSupply::add(deps.storage, amount)?;
+    let supply = TotalSupply::add(deps.storage, amount)?;
+    MaxSupply::check(deps.storage, supply)?;
     let sender = info.sender;
-    let block = env.block;
     let denom = CoinInfo::load(deps.storage)?.symbol;
     try_mint_impl(
         deps.storage,
@@ -52,10 +82,19 @@ pub fn try_mint(
         amount,
         denom,
         memo,
-        &block,
+        &env.block,
+        &decoys,
     )?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::Mint { status: Success })?))
+    let mut notifier = Notifier::default();
+    notifier.received(&recipient, &sender, amount);
+
+    notify(
+        Response::new().set_data(to_binary(&ExecuteAnswer::Mint { status: Success })?),
+        deps.storage,
+        &env,
+        notifier,
+    )
 }
 
 pub fn try_batch_mint(
@@ -63,6 +102,7 @@ pub fn try_batch_mint(
     env: Env,
     info: MessageInfo,
     actions: Vec<batch::MintAction>,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
     // Mint enabled
     if !Config::mint_enabled(deps.storage)? {
@@ -74,24 +114,36 @@ pub fn try_batch_mint(
     }
 
     let sender = info.sender;
-    let block = env.block;
+    let mut rng = decoy_rng(deps.storage, &env, &sender, entropy)?;
+    let mut notifier = Notifier::default();
     let denom = CoinInfo::load(deps.storage)?.symbol;
-    let supply = TotalSupply::load(deps.storage)?;
+    let mut supply = TotalSupply::load(deps.storage)?;
     for action in actions {
-        supply.0.checked_add(action.amount)?;
+        MinterAllowance::spend(deps.storage, &sender, action.amount, &env.block)?;
+        supply.0 = supply.0.checked_add(action.amount)?;
+        let recipient = deps.api.addr_validate(action.recipient.as_str())?;
+        let decoys = validate_decoys(deps.api, &mut rng, action.decoys)?;
         try_mint_impl(
             deps.storage,
             &sender,
-            &deps.api.addr_validate(action.recipient.as_str())?,
+            &recipient,
             action.amount,
             denom.clone(),
             action.memo,
-            &block,
+            &env.block,
+            &decoys,
         )?;
+        notifier.received(&recipient, &sender, action.amount);
     }
+    MaxSupply::check(deps.storage, supply.0)?;
     supply.save(deps.storage)?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::BatchMint { status: Success })?))
+    notify(
+        Response::new().set_data(to_binary(&ExecuteAnswer::BatchMint { status: Success })?),
+        deps.storage,
+        &env,
+        notifier,
+    )
 }
 
 pub fn try_add_minters(
@@ -132,6 +184,7 @@ pub fn try_remove_minters(
     let mut minters = Minters::load(deps.storage)?;
     for minter in minters_to_remove {
         minters.0.retain(|x| x != &minter);
+        MinterAllowance::remove(deps.storage, minter);
     }
     minters.save(deps.storage)?;
 
@@ -152,7 +205,38 @@ pub fn try_set_minters(
         return Err(not_admin());
     }
 
+    for minter in Minters::load(deps.storage)?.0 {
+        if !minters.contains(&minter) {
+            MinterAllowance::remove(deps.storage, minter);
+        }
+    }
     Minters(minters).save(deps.storage)?;
 
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::SetMinters { status: Success })?))
 }
+
+pub fn try_set_minter_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    minter: Addr,
+    limit: Option<MintLimit>,
+) -> StdResult<Response> {
+    if Admin::load(deps.storage)?.0 != info.sender {
+        return Err(not_admin());
+    }
+    if !Minters::load(deps.storage)?.0.contains(&minter) {
+        return Err(not_minter(&minter));
+    }
+
+    match limit {
+        Some(limit) => MinterAllowance::new(limit, &env.block).save(deps.storage, minter)?,
+        None => MinterAllowance::remove(deps.storage, minter),
+    }
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetMinterAllowance {
+            status: Success,
+        })?),
+    )
+}
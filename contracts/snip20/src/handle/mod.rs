@@ -7,14 +7,18 @@ use shade_protocol::{
     c_std::{
         to_binary,
         Addr,
+        Api,
         BankMsg,
+        Binary,
         Coin,
         CosmosMsg,
+        Deps,
         DepsMut,
         Env,
         MessageInfo,
         Response,
         StdResult,
+        Storage,
         Uint128,
     },
     contract_interfaces::snip20::{
@@ -26,25 +30,30 @@ use shade_protocol::{
             redeem_disabled,
             unsupported_token,
         },
+        notification::Notifier,
         manager::{
             Admin,
             Balance,
             CoinInfo,
             Config,
             ContractStatusLevel,
+            Decoys,
             HashedKey,
             Key,
+            MaxSupply,
             PermitKey,
             RandSeed,
             ReceiverHash,
             TotalSupply,
         },
-        transaction_history::{store_deposit, store_redeem},
+        transaction_history::{prune_history, store_deposit, store_redeem, HistoryRetention},
         ExecuteAnswer,
     },
     query_authentication::viewing_keys::ViewingKey,
     snip20::manager::QueryAuth,
     utils::{
+        asset::validate_vec,
+        crypto::Prng,
         generic_response::ResponseStatus::Success,
         storage::plus::{ItemStorage, MapStorage},
     },
@@ -63,7 +72,7 @@ pub fn try_redeem(
         return Err(redeem_disabled());
     }
 
-    Balance::sub(deps.storage, amount, &sender)?;
+    Balance::sub(deps.storage, amount, &sender, &env.block)?;
     TotalSupply::sub(deps.storage, amount)?;
 
     let token_reserve = Uint128::from(
@@ -112,7 +121,8 @@ pub fn try_deposit(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Resp
         return Err(deposit_disabled());
     }
 
-    TotalSupply::add(deps.storage, amount)?;
+    let supply = TotalSupply::add(deps.storage, amount)?;
+    MaxSupply::check(deps.storage, supply)?;
     Balance::add(deps.storage, amount, &sender)?;
 
     store_deposit(
@@ -164,6 +174,51 @@ pub fn try_update_query_auth(
     )
 }
 
+pub fn try_set_history_retention(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    retention: HistoryRetention,
+) -> StdResult<Response> {
+    if info.sender != Admin::load(deps.storage)?.0 {
+        return Err(not_admin());
+    }
+
+    retention.validate()?;
+    retention.save(deps.storage)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::SetHistoryRetention {
+            status: Success,
+        })?),
+    )
+}
+
+pub fn try_prune_history(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    accounts: Vec<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Response> {
+    if info.sender != Admin::load(deps.storage)?.0 {
+        return Err(not_admin());
+    }
+
+    let limit = limit.map_or(u64::MAX, |limit| limit as u64);
+    let mut pruned = 0u64;
+    for account in accounts.iter() {
+        pruned += prune_history(deps.storage, account, env.block.time, limit)?;
+    }
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::PruneHistory {
+            status: Success,
+            pruned,
+        })?),
+    )
+}
+
 pub fn try_set_contract_status(
     deps: DepsMut,
     _env: Env,
@@ -236,3 +291,60 @@ pub fn try_revoke_permit(
 
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RevokePermit { status: Success })?))
 }
+
+pub fn decoy_rng(
+    storage: &dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    entropy: Option<Binary>,
+) -> StdResult<Prng> {
+    let seed = RandSeed::load(storage)?.0;
+    Ok(Decoys::rng(
+        &seed,
+        &env.block,
+        sender,
+        entropy.unwrap_or_default().as_slice(),
+    ))
+}
+
+pub fn validate_decoys(
+    api: &dyn Api,
+    rng: &mut Prng,
+    decoys: Option<Vec<String>>,
+) -> StdResult<Decoys> {
+    match decoys {
+        None => Ok(Decoys::default()),
+        Some(decoys) => Ok(Decoys::new(validate_vec(api, decoys)?, rng)),
+    }
+}
+
+/// Decoys for single actions, the seed is only loaded when decoys are given
+pub fn load_decoys(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    decoys: Option<Vec<String>>,
+    entropy: Option<Binary>,
+) -> StdResult<Decoys> {
+    match decoys {
+        None => Ok(Decoys::default()),
+        Some(_) => {
+            let mut rng = decoy_rng(deps.storage, env, sender, entropy)?;
+            validate_decoys(deps.api, &mut rng, decoys)
+        }
+    }
+}
+
+/// Adds the encrypted notifications as plaintext attributes so wallets can find them by id
+pub fn notify(
+    response: Response,
+    storage: &mut dyn Storage,
+    env: &Env,
+    notifier: Notifier,
+) -> StdResult<Response> {
+    let mut response = response;
+    for (key, value) in notifier.attributes(storage, env)? {
+        response = response.add_attribute_plaintext(key, value);
+    }
+    Ok(response)
+}
@@ -1,3 +1,4 @@
+use crate::handle::{decoy_rng, notify, validate_decoys};
 use shade_protocol::{
     c_std::{
         to_binary,
@@ -15,7 +16,18 @@ use shade_protocol::{
     contract_interfaces::snip20::{
         batch,
         errors::transfer_disabled,
-        manager::{Allowance, Balance, CoinInfo, Config, ReceiverHash},
+        manager::{
+            Allowance,
+            Balance,
+            CoinInfo,
+            Config,
+            Decoys,
+            ReceiverHash,
+            VestingGrant,
+            VestingGrants,
+            VestingSchedule,
+        },
+        notification::Notifier,
         transaction_history::store_transfer,
         ExecuteAnswer,
         ReceiverHandleMsg,
@@ -28,6 +40,7 @@ use shade_protocol::{
     Contract,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn try_transfer_impl(
     storage: &mut dyn Storage,
     sender: &Addr, //spender when using from
@@ -37,6 +50,7 @@ pub fn try_transfer_impl(
     memo: Option<String>,
     denom: String,
     block: &shade_protocol::c_std::BlockInfo,
+    decoys: &Decoys,
 ) -> StdResult<()> {
     if !Config::transfer_enabled(storage)? {
         return Err(transfer_disabled());
@@ -50,7 +64,7 @@ pub fn try_transfer_impl(
         }
     };
 
-    Balance::transfer(storage, amount, some_owner, recipient)?;
+    Balance::transfer(storage, amount, some_owner, recipient, decoys, block)?;
 
     store_transfer(
         storage, some_owner, sender, recipient, amount, denom, memo, block,
@@ -65,6 +79,40 @@ pub fn try_transfer(
     recipient: Addr,
     amount: Uint128,
     memo: Option<String>,
+    decoys: Decoys,
+) -> StdResult<Response> {
+    let denom = CoinInfo::load(deps.storage)?.symbol;
+    try_transfer_impl(
+        deps.storage,
+        &info.sender,
+        None,
+        &recipient,
+        amount,
+        memo,
+        denom,
+        &env.block,
+        &decoys,
+    )?;
+
+    let mut notifier = Notifier::default();
+    notifier.received(&recipient, &info.sender, amount);
+
+    notify(
+        Response::new().set_data(to_binary(&ExecuteAnswer::Transfer { status: Success })?),
+        deps.storage,
+        &env,
+        notifier,
+    )
+}
+
+pub fn try_transfer_vesting(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Addr,
+    amount: Uint128,
+    schedule: VestingSchedule,
+    memo: Option<String>,
 ) -> StdResult<Response> {
     let denom = CoinInfo::load(deps.storage)?.symbol;
     try_transfer_impl(
@@ -76,9 +124,63 @@ pub fn try_transfer(
         memo,
         denom,
         &env.block,
+        &Decoys::default(),
+    )?;
+
+    VestingGrants::grant(
+        deps.storage,
+        &recipient,
+        VestingGrant {
+            sender: info.sender.clone(),
+            amount,
+            schedule,
+        },
+        &env.block,
     )?;
 
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::Transfer { status: Success })?))
+    let mut notifier = Notifier::default();
+    notifier.received(&recipient, &info.sender, amount);
+
+    notify(
+        Response::new().set_data(to_binary(&ExecuteAnswer::TransferVesting { status: Success })?),
+        deps.storage,
+        &env,
+        notifier,
+    )
+}
+
+pub fn try_reject_vesting_grant(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    index: u32,
+) -> StdResult<Response> {
+    let (grant, locked) = VestingGrants::reject(deps.storage, &info.sender, index, &env.block)?;
+
+    let mut notifier = Notifier::default();
+    if !locked.is_zero() {
+        let denom = CoinInfo::load(deps.storage)?.symbol;
+        try_transfer_impl(
+            deps.storage,
+            &info.sender,
+            None,
+            &grant.sender,
+            locked,
+            None,
+            denom,
+            &env.block,
+            &Decoys::default(),
+        )?;
+        notifier.received(&grant.sender, &info.sender, locked);
+    }
+
+    notify(
+        Response::new()
+            .set_data(to_binary(&ExecuteAnswer::RejectVestingGrant { status: Success })?),
+        deps.storage,
+        &env,
+        notifier,
+    )
 }
 
 pub fn try_batch_transfer(
@@ -86,23 +188,34 @@ pub fn try_batch_transfer(
     env: Env,
     info: MessageInfo,
     actions: Vec<batch::TransferAction>,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
     let sender = info.sender;
-    let block = env.block;
+    let mut rng = decoy_rng(deps.storage, &env, &sender, entropy)?;
+    let mut notifier = Notifier::default();
     let denom = CoinInfo::load(deps.storage)?.symbol;
     for action in actions {
+        let recipient = deps.api.addr_validate(action.recipient.as_str())?;
+        let decoys = validate_decoys(deps.api, &mut rng, action.decoys)?;
         try_transfer_impl(
             deps.storage,
             &sender,
             None,
-            &deps.api.addr_validate(action.recipient.as_str())?,
+            &recipient,
             action.amount,
             action.memo,
             denom.clone(),
-            &block,
+            &env.block,
+            &decoys,
         )?;
+        notifier.received(&recipient, &sender, action.amount);
     }
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::BatchTransfer { status: Success })?))
+    notify(
+        Response::new().set_data(to_binary(&ExecuteAnswer::BatchTransfer { status: Success })?),
+        deps.storage,
+        &env,
+        notifier,
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -149,6 +262,7 @@ pub fn try_send_impl(
     msg: Option<Binary>,
     denom: String,
     block: &shade_protocol::c_std::BlockInfo,
+    decoys: &Decoys,
 ) -> StdResult<()> {
     try_transfer_impl(
         storage,
@@ -159,6 +273,7 @@ pub fn try_send_impl(
         memo.clone(),
         denom,
         block,
+        decoys,
     )?;
     try_add_receiver_api_callback(
         storage,
@@ -184,6 +299,7 @@ pub fn try_send(
     amount: Uint128,
     memo: Option<String>,
     msg: Option<Binary>,
+    decoys: Decoys,
 ) -> StdResult<Response> {
     let mut messages = vec![];
     let denom = CoinInfo::load(deps.storage)?.symbol;
@@ -200,11 +316,20 @@ pub fn try_send(
         msg,
         denom,
         &env.block,
+        &decoys,
     )?;
 
-    Ok(Response::new()
-        .set_data(to_binary(&ExecuteAnswer::Send { status: Success })?)
-        .add_submessages(messages))
+    let mut notifier = Notifier::default();
+    notifier.received(&recipient, &info.sender, amount);
+
+    notify(
+        Response::new()
+            .set_data(to_binary(&ExecuteAnswer::Send { status: Success })?)
+            .add_submessages(messages),
+        deps.storage,
+        &env,
+        notifier,
+    )
 }
 
 pub fn try_batch_send(
@@ -212,28 +337,40 @@ pub fn try_batch_send(
     env: Env,
     info: MessageInfo,
     actions: Vec<batch::SendAction>,
+    entropy: Option<Binary>,
 ) -> StdResult<Response> {
     let mut messages = vec![];
     let sender = info.sender;
+    let mut rng = decoy_rng(deps.storage, &env, &sender, entropy)?;
+    let mut notifier = Notifier::default();
     let denom = CoinInfo::load(deps.storage)?.symbol;
 
     for action in actions {
+        let recipient = deps.api.addr_validate(action.recipient.as_str())?;
+        let decoys = validate_decoys(deps.api, &mut rng, action.decoys)?;
         try_send_impl(
             deps.storage,
             &mut messages,
             &sender,
             None,
-            &deps.api.addr_validate(action.recipient.as_str())?,
+            &recipient,
             action.recipient_code_hash,
             action.amount,
             action.memo,
             action.msg,
             denom.clone(),
             &env.block,
+            &decoys,
         )?;
+        notifier.received(&recipient, &sender, action.amount);
     }
 
-    Ok(Response::new()
-        .set_data(to_binary(&ExecuteAnswer::BatchSend { status: Success })?)
-        .add_submessages(messages))
+    notify(
+        Response::new()
+            .set_data(to_binary(&ExecuteAnswer::BatchSend { status: Success })?)
+            .add_submessages(messages),
+        deps.storage,
+        &env,
+        notifier,
+    )
 }
@@ -1,15 +1,28 @@
 use shade_protocol::c_std::{Uint128, Deps};
-use shade_protocol::c_std::{Addr, StdResult};
+use shade_protocol::c_std::{Addr, Binary, Env, StdResult};
 use shade_protocol::{
     contract_interfaces::snip20::{
+        errors::channel_not_found,
         manager::{
             Allowance,
+            AllowanceGivenResult,
+            AllowanceReceivedResult,
             Balance,
             CoinInfo,
             Config,
             ContractStatusLevel,
+            MaxSupply,
+            MinterAllowance,
+            MinterAllowanceResult,
             Minters,
             TotalSupply,
+            VestingGrants,
+        },
+        notification::{
+            notification_seed,
+            ChannelInfoData,
+            NOTIFICATION_MODE,
+            RECEIVED_CHANNEL,
         },
         transaction_history::{RichTx, Tx},
         QueryAnswer,
@@ -87,6 +100,62 @@ pub fn minters(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
+pub fn minter_allowances(deps: Deps, env: Env) -> StdResult<QueryAnswer> {
+    let mut allowances = vec![];
+    for minter in Minters::load(deps.storage)?.0 {
+        let allowance = match MinterAllowance::may_load(deps.storage, minter.clone())? {
+            Some(mut allowance) => {
+                allowance.refresh(&env.block)?;
+                Some(allowance)
+            }
+            None => None,
+        };
+
+        allowances.push(MinterAllowanceResult {
+            minter,
+            remaining: allowance.as_ref().map(|allowance| allowance.remaining()),
+            limit: allowance.map(|allowance| allowance.limit),
+        });
+    }
+
+    Ok(QueryAnswer::MinterAllowances {
+        max_supply: MaxSupply::may_load(deps.storage)?.map(|max| max.0),
+        allowances,
+    })
+}
+
+pub fn list_channels() -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::ListChannels {
+        channels: vec![RECEIVED_CHANNEL.to_string()],
+    })
+}
+
+pub fn channel_info(
+    deps: Deps,
+    env: Env,
+    account: Addr,
+    channels: Vec<String>,
+) -> StdResult<QueryAnswer> {
+    let seed = Binary::from(&notification_seed(deps.storage, &account)?[..]);
+
+    let mut info = vec![];
+    for channel in channels {
+        if channel != RECEIVED_CHANNEL {
+            return Err(channel_not_found(&channel));
+        }
+        info.push(ChannelInfoData {
+            channel,
+            seed: seed.clone(),
+            mode: NOTIFICATION_MODE.to_string(),
+        });
+    }
+
+    Ok(QueryAnswer::ChannelInfo {
+        as_of_block: env.block.height,
+        channels: info,
+    })
+}
+
 pub fn allowance(
     deps: Deps,
     owner: Addr,
@@ -107,6 +176,60 @@ pub fn allowance(
     })
 }
 
+pub fn allowances_given(
+    deps: Deps,
+    owner: Addr,
+    page: u32,
+    page_size: u32,
+) -> StdResult<QueryAnswer> {
+    let count = Allowance::total_spenders(deps.storage, &owner)?;
+    let start = page.saturating_mul(page_size);
+
+    let mut allowances = vec![];
+    for spender in Allowance::spenders(deps.storage, &owner, start, page_size)? {
+        let allowance = Allowance::load(deps.storage, (owner.clone(), spender.clone()))?;
+        allowances.push(AllowanceGivenResult {
+            spender,
+            allowance: allowance.amount,
+            expiration: allowance.expiration,
+        });
+    }
+
+    Ok(QueryAnswer::AllowancesGiven {
+        owner,
+        allowances,
+        count,
+        incomplete: !Allowance::indexes_complete(deps.storage)?,
+    })
+}
+
+pub fn allowances_received(
+    deps: Deps,
+    spender: Addr,
+    page: u32,
+    page_size: u32,
+) -> StdResult<QueryAnswer> {
+    let count = Allowance::total_owners(deps.storage, &spender)?;
+    let start = page.saturating_mul(page_size);
+
+    let mut allowances = vec![];
+    for owner in Allowance::owners(deps.storage, &spender, start, page_size)? {
+        let allowance = Allowance::load(deps.storage, (owner.clone(), spender.clone()))?;
+        allowances.push(AllowanceReceivedResult {
+            owner,
+            allowance: allowance.amount,
+            expiration: allowance.expiration,
+        });
+    }
+
+    Ok(QueryAnswer::AllowancesReceived {
+        spender,
+        allowances,
+        count,
+        incomplete: !Allowance::indexes_complete(deps.storage)?,
+    })
+}
+
 pub fn balance(
     deps: Deps,
     account: Addr,
@@ -116,6 +239,17 @@ pub fn balance(
     })
 }
 
+pub fn vesting_schedules(
+    deps: Deps,
+    env: Env,
+    account: Addr,
+) -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::VestingSchedules {
+        locked: VestingGrants::locked(deps.storage, &account, &env.block)?,
+        grants: VestingGrants::load_or_default(deps.storage, &account)?.0,
+    })
+}
+
 pub fn transfer_history(
     deps: Deps,
     account: Addr,
@@ -20,7 +20,8 @@ fn burn() {
         enable_redeem: None,
         enable_mint: None,
         enable_burn: Some(true),
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     chain.update_block(|block| block.time = Timestamp::from_seconds(0));
@@ -28,6 +29,8 @@ fn burn() {
     // Insufficient tokens
     assert!(ExecuteMsg::Burn {
         amount: Uint128::new(8000),
+        decoys: None,
+        entropy: None,
         padding: None,
         memo: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("finger"), &[]).is_err());
@@ -35,6 +38,8 @@ fn burn() {
     // Burn some
     assert!(ExecuteMsg::Burn {
         amount: Uint128::new(4000),
+        decoys: None,
+        entropy: None,
         padding: None,
         memo: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("finger"), &[]).is_ok());
@@ -68,7 +73,8 @@ fn burn_from() {
         enable_redeem: None,
         enable_mint: None,
         enable_burn: Some(true),
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     chain.update_block(|block| block.time = Timestamp::from_seconds(0));
@@ -153,7 +159,8 @@ fn batch_burn_from() {
         enable_redeem: None,
         enable_mint: None,
         enable_burn: Some(true),
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     chain.update_block(|block| block.time = Timestamp::from_seconds(0));
@@ -1,8 +1,13 @@
-use shade_protocol::c_std::Addr;
+use shade_protocol::c_std::{Addr, Timestamp};
 use shade_protocol::utils::{ExecuteCallback, Query, MultiTestable};
 use shade_protocol::c_std::Uint128;
-use shade_protocol::contract_interfaces::snip20::{ExecuteMsg, InitConfig};
-use shade_protocol::contract_interfaces::snip20::manager::{Balance, Minters, TotalSupply};
+use shade_protocol::contract_interfaces::snip20::{
+    batch, ExecuteMsg, InitConfig, QueryAnswer, QueryMsg
+};
+use shade_protocol::contract_interfaces::snip20::manager::{
+    Balance, MintLimit, Minters, TotalSupply
+};
+use shade_protocol::utils::cycle::Cycle;
 use shade_protocol::utils::storage::plus::{ItemStorage, MapStorage};
 use crate::tests::init_snip20_with_config;
 
@@ -14,13 +19,16 @@ fn mint() {
         enable_redeem: None,
         enable_mint: Some(true),
         enable_burn: None,
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     assert!(ExecuteMsg::Mint {
         recipient: "jimmy".into(),
         amount: Uint128::new(1000),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_err());
 
@@ -33,6 +41,8 @@ fn mint() {
         recipient: "jimmy".into(),
         amount: Uint128::new(1500),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_ok());
 
@@ -54,7 +64,8 @@ fn set_minters() {
         enable_redeem: None,
         enable_mint: Some(true),
         enable_burn: None,
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     assert!(ExecuteMsg::SetMinters {
@@ -90,7 +101,8 @@ fn add_minters() {
         enable_redeem: None,
         enable_mint: Some(true),
         enable_burn: None,
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     assert!(ExecuteMsg::AddMinters {
@@ -130,7 +142,8 @@ fn remove_minters() {
         enable_redeem: None,
         enable_mint: Some(true),
         enable_burn: None,
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     assert!(ExecuteMsg::AddMinters {
@@ -149,4 +162,108 @@ fn remove_minters() {
                        Addr::unchecked("some_other")
                    ]);
     }).unwrap();
-}
\ No newline at end of file
+}
+#[test]
+fn mint_caps() {
+    let (mut chain, snip) = init_snip20_with_config(None, Some(InitConfig {
+        public_total_supply: None,
+        enable_deposit: None,
+        enable_redeem: None,
+        enable_mint: Some(true),
+        enable_burn: None,
+        enable_transfer: None,
+        max_supply: Some(Uint128::new(2000))
+    })).unwrap();
+
+    chain.update_block(|block| block.time = Timestamp::from_seconds(0));
+
+    assert!(ExecuteMsg::SetMinters {
+        minters: vec!["minter".into()],
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_ok());
+
+    let limit = MintLimit {
+        amount: Uint128::new(500),
+        cycle: Cycle::Seconds { seconds: Uint128::new(100) }
+    };
+
+    assert!(ExecuteMsg::SetMinterAllowance {
+        minter: "minter".into(),
+        limit: Some(limit.clone()),
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("minter"), &[]).is_err());
+
+    assert!(ExecuteMsg::SetMinterAllowance {
+        minter: "notminter".into(),
+        limit: Some(limit.clone()),
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_err());
+
+    assert!(ExecuteMsg::SetMinterAllowance {
+        minter: "minter".into(),
+        limit: Some(limit.clone()),
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_ok());
+
+    let mint = |amount: u128| ExecuteMsg::Mint {
+        recipient: "jimmy".into(),
+        amount: Uint128::new(amount),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None
+    };
+
+    assert!(mint(501).test_exec(&snip, &mut chain, Addr::unchecked("minter"), &[]).is_err());
+    assert!(mint(300).test_exec(&snip, &mut chain, Addr::unchecked("minter"), &[]).is_ok());
+
+    // Batches share the same allowance
+    assert!(ExecuteMsg::BatchMint {
+        actions: vec![
+            batch::MintAction {
+                recipient: "jimmy".into(),
+                amount: Uint128::new(150),
+                memo: None,
+                decoys: None,
+            },
+            batch::MintAction {
+                recipient: "jimmy".into(),
+                amount: Uint128::new(100),
+                memo: None,
+                decoys: None,
+            },
+        ],
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("minter"), &[]).is_err());
+
+    let answer: QueryAnswer = QueryMsg::MinterAllowances {  }.test_query(&snip, &chain).unwrap();
+    match answer {
+        QueryAnswer::MinterAllowances { max_supply, allowances } => {
+            assert_eq!(max_supply, Some(Uint128::new(2000)));
+            assert_eq!(allowances.len(), 1);
+            assert_eq!(allowances[0].minter, Addr::unchecked("minter"));
+            assert_eq!(allowances[0].limit, Some(limit.clone()));
+            assert_eq!(allowances[0].remaining, Some(Uint128::new(200)));
+        },
+        _ => assert!(false)
+    }
+
+    // Window refreshes
+    chain.update_block(|block| block.time = Timestamp::from_seconds(100));
+    assert!(mint(500).test_exec(&snip, &mut chain, Addr::unchecked("minter"), &[]).is_ok());
+
+    // Lifting the allowance still leaves the max supply
+    assert!(ExecuteMsg::SetMinterAllowance {
+        minter: "minter".into(),
+        limit: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_ok());
+
+    assert!(mint(1201).test_exec(&snip, &mut chain, Addr::unchecked("minter"), &[]).is_err());
+    assert!(mint(1200).test_exec(&snip, &mut chain, Addr::unchecked("minter"), &[]).is_ok());
+
+    chain.deps(&snip.address, |storage| {
+        assert_eq!(TotalSupply::load(storage).unwrap().0, Uint128::new(2000));
+    }).unwrap();
+}
@@ -112,7 +112,8 @@ fn contract_status_stop_all() {
         enable_redeem: Some(true),
         enable_mint: None,
         enable_burn: None,
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     let scrt_coin = Coin {
@@ -138,6 +139,8 @@ fn contract_status_stop_all() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
 
@@ -156,6 +159,8 @@ fn contract_status_stop_all() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
 
@@ -174,7 +179,8 @@ fn contract_status_stop_all_but_redeem() {
         enable_redeem: Some(true),
         enable_mint: None,
         enable_burn: None,
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     let scrt_coin = Coin {
@@ -200,6 +206,8 @@ fn contract_status_stop_all_but_redeem() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
 
@@ -218,6 +226,8 @@ fn contract_status_stop_all_but_redeem() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
 
@@ -1,7 +1,11 @@
-use shade_protocol::c_std::Addr;
+use shade_protocol::c_std::{Addr, Binary, Timestamp};
+use shade_protocol::multi_test::{App, AppResponse};
 use shade_protocol::utils::{ExecuteCallback, Query};
 use shade_protocol::c_std::Uint128;
-use shade_protocol::contract_interfaces::snip20::{ExecuteMsg, InitialBalance, QueryMsg, QueryAnswer};
+use shade_protocol::contract_interfaces::snip20::{
+    batch, ExecuteMsg, InitialBalance, QueryMsg, QueryAnswer
+};
+use shade_protocol::contract_interfaces::snip20::manager::{VestingSchedule, MAX_VESTING_GRANTS};
 use crate::tests::init_snip20_with_config;
 
 #[test]
@@ -46,6 +50,8 @@ fn transfer() {
         recipient: "dylan".into(),
         amount: Uint128::new(100),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
 
@@ -75,6 +81,8 @@ fn transfer() {
         recipient: "dylan".into(),
         amount: Uint128::new(1000),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
 }
@@ -97,6 +105,8 @@ fn send() {
         amount: Uint128::new(100),
         recipient_code_hash: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
         msg: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
@@ -128,7 +138,296 @@ fn send() {
         amount: Uint128::new(1000),
         recipient_code_hash: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
         msg: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
-}
\ No newline at end of file
+}
+
+#[test]
+fn transfer_with_decoys() {
+    let (mut chain, snip) = init_snip20_with_config(Some(vec![
+        InitialBalance{
+            address: "bob".into(),
+            amount: (Uint128::new(1000))
+        },
+        InitialBalance {
+            address: "dylan".into(),
+            amount: Uint128::new(1000)
+        },
+        InitialBalance {
+            address: "carol".into(),
+            amount: Uint128::new(50)
+        },
+    ]), None).unwrap();
+
+    assert!(ExecuteMsg::Transfer {
+        recipient: "dylan".into(),
+        amount: Uint128::new(100),
+        memo: None,
+        decoys: Some(vec!["carol".into(), "bob".into(), "ghost".into()]),
+        entropy: Some(Binary::from("entropy".as_bytes())),
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
+
+    assert!(ExecuteMsg::BatchTransfer {
+        actions: vec![
+            batch::TransferAction {
+                recipient: "carol".into(),
+                amount: Uint128::new(10),
+                memo: None,
+                decoys: Some(vec!["dylan".into(), "ghost".into()]),
+            },
+            batch::TransferAction {
+                recipient: "dylan".into(),
+                amount: Uint128::new(10),
+                memo: None,
+                decoys: None,
+            },
+        ],
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
+
+    // Decoy balances are rewritten but never change
+    for (address, expected) in [("bob", 880), ("dylan", 1110), ("carol", 60)] {
+        let answer: QueryAnswer = QueryMsg::Balance {
+                address: address.into(),
+                key: "password".into()
+            }.test_query(&snip, &chain).unwrap();
+
+        match answer {
+            QueryAnswer::Balance {amount} => assert_eq!(amount, Uint128::new(expected)),
+            _ => assert!(false)
+        }
+    }
+}
+
+#[test]
+fn transfer_notifications() {
+    let (mut chain, snip) = init_snip20_with_config(Some(vec![
+        InitialBalance{
+            address: "bob".into(),
+            amount: (Uint128::new(1000))
+        },
+    ]), None).unwrap();
+
+    let notifications = |res: &AppResponse| -> Vec<String> {
+        res.events.iter()
+            .flat_map(|event| event.attributes.iter())
+            .map(|attr| attr.key.trim().to_string())
+            .filter(|key| key.starts_with("snip52:"))
+            .collect()
+    };
+
+    let res = ExecuteMsg::Transfer {
+        recipient: "dylan".into(),
+        amount: Uint128::new(100),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).unwrap();
+    assert_eq!(notifications(&res).len(), 1);
+
+    // One notification per recipient, even when they receive several times
+    let res = ExecuteMsg::BatchTransfer {
+        actions: vec![
+            batch::TransferAction {
+                recipient: "carol".into(),
+                amount: Uint128::new(10),
+                memo: None,
+                decoys: None,
+            },
+            batch::TransferAction {
+                recipient: "dylan".into(),
+                amount: Uint128::new(10),
+                memo: None,
+                decoys: None,
+            },
+            batch::TransferAction {
+                recipient: "carol".into(),
+                amount: Uint128::new(10),
+                memo: None,
+                decoys: None,
+            },
+        ],
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).unwrap();
+
+    let ids = notifications(&res);
+    assert_eq!(ids.len(), 2);
+    assert_ne!(ids[0], ids[1]);
+
+    // Messages sharing a block and tx index still get their own ids
+    let transfer = |chain: &mut App| ExecuteMsg::Transfer {
+        recipient: "dylan".into(),
+        amount: Uint128::new(10),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, chain, Addr::unchecked("bob"), &[]).unwrap();
+
+    let first = notifications(&transfer(&mut chain));
+    let second = notifications(&transfer(&mut chain));
+    assert_eq!(second.len(), 1);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn transfer_vesting() {
+    let (mut chain, snip) = init_snip20_with_config(Some(vec![
+        InitialBalance{
+            address: "bob".into(),
+            amount: (Uint128::new(1000))
+        },
+    ]), None).unwrap();
+
+    chain.update_block(|block| block.time = Timestamp::from_seconds(0));
+
+    assert!(ExecuteMsg::TransferVesting {
+        recipient: "dylan".into(),
+        amount: Uint128::new(500),
+        schedule: VestingSchedule::Linear { start: 100, end: 100 },
+        memo: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
+
+    assert!(ExecuteMsg::TransferVesting {
+        recipient: "dylan".into(),
+        amount: Uint128::new(500),
+        schedule: VestingSchedule::Linear { start: 100, end: 600 },
+        memo: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
+
+    assert!(ExecuteMsg::TransferVesting {
+        recipient: "dylan".into(),
+        amount: Uint128::new(200),
+        schedule: VestingSchedule::Cliff { release: 1000 },
+        memo: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
+
+    let transfer = |amount: u128| ExecuteMsg::Transfer {
+        recipient: "bob".into(),
+        amount: Uint128::new(amount),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None
+    };
+
+    // Nothing has vested yet
+    assert!(transfer(1).test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).is_err());
+    assert!(ExecuteMsg::Burn {
+        amount: Uint128::new(1),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).is_err());
+
+    // Halfway through the linear schedule
+    chain.update_block(|block| block.time = Timestamp::from_seconds(350));
+    assert!(transfer(251).test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).is_err());
+    assert!(transfer(250).test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).is_ok());
+
+    ExecuteMsg::SetViewingKey {
+        key: "password".into(),
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).unwrap();
+
+    let answer: QueryAnswer = QueryMsg::VestingSchedules {
+        address: "dylan".into(),
+        key: "password".into()
+    }.test_query(&snip, &chain).unwrap();
+
+    match answer {
+        QueryAnswer::VestingSchedules { grants, locked } => {
+            assert_eq!(grants.len(), 2);
+            assert_eq!(grants[0].sender, Addr::unchecked("bob"));
+            assert_eq!(locked, Uint128::new(450));
+        },
+        _ => assert!(false)
+    }
+
+    // Linear schedule is done but the cliff has not been reached
+    chain.update_block(|block| block.time = Timestamp::from_seconds(600));
+    assert!(transfer(251).test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).is_err());
+    assert!(transfer(250).test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).is_ok());
+
+    chain.update_block(|block| block.time = Timestamp::from_seconds(1000));
+    assert!(transfer(200).test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).is_ok());
+}
+
+#[test]
+fn reject_vesting_grant() {
+    let (mut chain, snip) = init_snip20_with_config(Some(vec![
+        InitialBalance{
+            address: "bob".into(),
+            amount: (Uint128::new(1000))
+        },
+    ]), None).unwrap();
+
+    chain.update_block(|block| block.time = Timestamp::from_seconds(0));
+
+    // Grants that never release fill up the recipient's slots
+    for _ in 0..MAX_VESTING_GRANTS {
+        ExecuteMsg::TransferVesting {
+            recipient: "dylan".into(),
+            amount: Uint128::new(1),
+            schedule: VestingSchedule::Cliff { release: u64::MAX },
+            memo: None,
+            padding: None
+        }.test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).unwrap();
+    }
+
+    let vest = |amount: u128| ExecuteMsg::TransferVesting {
+        recipient: "dylan".into(),
+        amount: Uint128::new(amount),
+        schedule: VestingSchedule::Linear { start: 100, end: 600 },
+        memo: None,
+        padding: None
+    };
+    assert!(vest(500).test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_err());
+
+    assert!(ExecuteMsg::RejectVestingGrant {
+        index: MAX_VESTING_GRANTS as u32,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).is_err());
+
+    ExecuteMsg::RejectVestingGrant {
+        index: 0,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).unwrap();
+
+    assert!(vest(500).test_exec(&snip, &mut chain, Addr::unchecked("bob"), &[]).is_ok());
+
+    // Only the locked part of the grant goes back to the grantor
+    chain.update_block(|block| block.time = Timestamp::from_seconds(350));
+    ExecuteMsg::RejectVestingGrant {
+        index: MAX_VESTING_GRANTS as u32 - 1,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("dylan"), &[]).unwrap();
+
+    for (user, balance) in [("bob", 1000 - MAX_VESTING_GRANTS as u128 + 1 - 250), ("dylan", 259)] {
+        ExecuteMsg::SetViewingKey {
+            key: "password".into(),
+            padding: None
+        }.test_exec(&snip, &mut chain, Addr::unchecked(user), &[]).unwrap();
+
+        let answer: QueryAnswer = QueryMsg::Balance {
+            address: user.into(),
+            key: "password".into()
+        }.test_query(&snip, &chain).unwrap();
+
+        match answer {
+            QueryAnswer::Balance { amount } => assert_eq!(amount, Uint128::new(balance)),
+            _ => assert!(false)
+        }
+    }
+}
@@ -14,7 +14,8 @@ fn deposit() {
         enable_redeem: Some(true),
         enable_mint: None,
         enable_burn: None,
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     let scrt_coin = Coin {
@@ -53,6 +54,44 @@ fn deposit() {
     }).unwrap();
 }
 
+#[test]
+fn deposit_max_supply() {
+    let (mut chain, snip20) = init_snip20_with_config(None, Some(InitConfig{
+        public_total_supply: None,
+        enable_deposit: Some(true),
+        enable_redeem: None,
+        enable_mint: None,
+        enable_burn: None,
+        enable_transfer: None,
+        max_supply: Some(Uint128::new(1000))
+    })).unwrap();
+
+    let scrt_coin = |amount: u128| Coin {
+        denom: "uscrt".into(),
+        amount: Uint128::new(amount)
+    };
+
+    chain.init_modules(|router, _, storage| {
+        router.bank.init_balance(storage, &Addr::unchecked("marco"), vec![scrt_coin(2000)]).unwrap();
+    });
+
+    assert!(ExecuteMsg::Deposit {
+        padding: None
+    }.test_exec(&snip20, &mut chain, Addr::unchecked("marco"), &vec![scrt_coin(800)]).is_ok());
+
+    assert!(ExecuteMsg::Deposit {
+        padding: None
+    }.test_exec(&snip20, &mut chain, Addr::unchecked("marco"), &vec![scrt_coin(201)]).is_err());
+
+    assert!(ExecuteMsg::Deposit {
+        padding: None
+    }.test_exec(&snip20, &mut chain, Addr::unchecked("marco"), &vec![scrt_coin(200)]).is_ok());
+
+    chain.deps(&snip20.address, |storage| {
+        assert_eq!(TotalSupply::load(storage).unwrap().0, Uint128::new(1000));
+    }).unwrap();
+}
+
 #[test]
 fn redeem() {
     let (mut chain, snip20) = init_snip20_with_config(None, Some(InitConfig{
@@ -61,7 +100,8 @@ fn redeem() {
         enable_redeem: Some(true),
         enable_mint: None,
         enable_burn: None,
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
 
     let scrt_coin = Coin {
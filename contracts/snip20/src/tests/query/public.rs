@@ -47,7 +47,8 @@ fn token_config() {
         enable_redeem: Some(true),
         enable_mint: None,
         enable_burn: None,
-        enable_transfer: None
+        enable_transfer: None,
+        max_supply: None
     })).unwrap();
     let answer: QueryAnswer = QueryMsg::TokenConfig {  }.test_query(&snip, &chain).unwrap();
 
@@ -70,4 +71,16 @@ fn token_config() {
     }
 }
 
-// TODO: add exchange rate after IBC is added
\ No newline at end of file
+// TODO: add exchange rate after IBC is added
+#[test]
+fn list_channels() {
+    let (chain, snip) = init_snip20_with_config(None, None).unwrap();
+    let answer: QueryAnswer = QueryMsg::ListChannels {  }.test_query(&snip, &chain).unwrap();
+
+    match answer {
+        QueryAnswer::ListChannels { channels } => {
+            assert_eq!(channels, vec!["recvd".to_string()]);
+        },
+        _ => assert!(false)
+    }
+}
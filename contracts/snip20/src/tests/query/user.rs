@@ -1,6 +1,9 @@
 use shade_protocol::c_std::{Coin, Addr, Uint128};
 use shade_protocol::contract_interfaces::snip20::{ExecuteMsg, InitialBalance, QueryAnswer, QueryMsg};
-use shade_protocol::contract_interfaces::snip20::transaction_history::{RichTx, TxAction};
+use shade_protocol::contract_interfaces::snip20::transaction_history::{
+    HistoryRetention, RichTx, TxAction
+};
+use shade_protocol::multi_test::App;
 use shade_protocol::query_auth;
 use shade_protocol::utils::{ExecuteCallback, InstantiateCallback, Query, MultiTestable};
 use crate::tests::{create_vk, init_snip20_with_auth, init_snip20_with_config};
@@ -47,6 +50,8 @@ fn allowance_auth_vk() {
 
     query_auth::ExecuteMsg::SetViewingKey {
         key: "password".into(),
+        expires_at: None,
+        contracts: None,
         padding: None,
     }.test_exec(&auth.unwrap(), &mut chain, saul.clone(), &[]).unwrap();
 
@@ -111,6 +116,8 @@ fn transaction_history() {
         recipient: stratos.clone().into_string(),
         amount: Uint128::new(200),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("setsuna"), &[]).unwrap();
 
@@ -120,6 +127,8 @@ fn transaction_history() {
         amount: Uint128::new(140),
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("setsuna"), &[]).unwrap();
 
@@ -127,6 +136,8 @@ fn transaction_history() {
         recipient: felt.clone().into_string(),
         amount: Uint128::new(300),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("setsuna"), &[]).unwrap();
 
@@ -134,6 +145,8 @@ fn transaction_history() {
         recipient: tieria.clone().into_string(),
         amount: Uint128::new(540),
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None
     }.test_exec(&snip, &mut chain, Addr::unchecked("setsuna"), &[]).unwrap();
 
@@ -205,4 +218,197 @@ fn transaction_history() {
         },
         _ => assert!(false)
     }
-}
\ No newline at end of file
+}
+#[test]
+fn allowances_given_and_received_vk() {
+    let (mut chain, snip) = init_snip20_with_config(None, None).unwrap();
+
+    let saul = Addr::unchecked("saul");
+    let goodman = Addr::unchecked("goodman");
+    let kim = Addr::unchecked("kim");
+
+    create_vk(&mut chain, &snip, "saul", None).unwrap();
+    create_vk(&mut chain, &snip, "goodman", None).unwrap();
+
+    let allowances = [(&saul, &goodman, 100), (&saul, &kim, 50), (&kim, &goodman, 25)];
+    for (owner, spender, amount) in allowances {
+        ExecuteMsg::IncreaseAllowance {
+            spender: spender.clone().into_string(),
+            amount: Uint128::new(amount),
+            expiration: Some(1_000_000_000_000),
+            padding: None
+        }.test_exec(&snip, &mut chain, owner.clone(), &[]).unwrap();
+    }
+
+    let answer: QueryAnswer = QueryMsg::AllowancesGiven {
+        owner: saul.clone().into(),
+        key: "password".into(),
+        page: None,
+        page_size: 10
+    }.test_query(&snip, &chain).unwrap();
+
+    match answer {
+        QueryAnswer::AllowancesGiven { owner, allowances, count, incomplete } => {
+            assert_eq!(owner, saul);
+            assert_eq!(count, 2);
+            // Instantiated with the indexes so every allowance is listed
+            assert!(!incomplete);
+            assert_eq!(allowances[0].spender, goodman);
+            assert_eq!(allowances[0].allowance, Uint128::new(100));
+            assert_eq!(allowances[0].expiration, Some(1_000_000_000_000));
+            assert_eq!(allowances[1].spender, kim);
+            assert_eq!(allowances[1].allowance, Uint128::new(50));
+        },
+        _ => assert!(false)
+    }
+
+    // Pages are counted over the full index
+    let answer: QueryAnswer = QueryMsg::AllowancesReceived {
+        spender: goodman.clone().into(),
+        key: "password".into(),
+        page: Some(1),
+        page_size: 1
+    }.test_query(&snip, &chain).unwrap();
+
+    match answer {
+        QueryAnswer::AllowancesReceived { spender, allowances, count, .. } => {
+            assert_eq!(spender, goodman);
+            assert_eq!(count, 2);
+            assert_eq!(allowances.len(), 1);
+            assert_eq!(allowances[0].owner, kim);
+            assert_eq!(allowances[0].allowance, Uint128::new(25));
+        },
+        _ => assert!(false)
+    }
+
+    // Only the owner's key can list what they have given
+    assert!(QueryMsg::AllowancesGiven {
+        owner: kim.clone().into(),
+        key: "password".into(),
+        page: None,
+        page_size: 10
+    }.test_query::<QueryAnswer>(&snip, &chain).is_err());
+
+    // Emptied allowances are dropped from the indexes
+    ExecuteMsg::DecreaseAllowance {
+        spender: goodman.clone().into_string(),
+        amount: Uint128::new(100),
+        expiration: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, saul.clone(), &[]).unwrap();
+
+    let answer: QueryAnswer = QueryMsg::AllowancesReceived {
+        spender: goodman.clone().into(),
+        key: "password".into(),
+        page: None,
+        page_size: 10
+    }.test_query(&snip, &chain).unwrap();
+
+    match answer {
+        QueryAnswer::AllowancesReceived { allowances, count, .. } => {
+            assert_eq!(count, 1);
+            assert_eq!(allowances[0].owner, kim);
+        },
+        _ => assert!(false)
+    }
+}
+
+#[test]
+fn transaction_history_pruning() {
+    let setsuna = Addr::unchecked("setsuna");
+
+    let (mut chain, snip) = init_snip20_with_config(Some(vec![InitialBalance {
+        address: setsuna.clone().into_string(),
+        amount: Uint128::new(1500)
+    }]), None).unwrap();
+
+    for i in 0..4 {
+        ExecuteMsg::Transfer {
+            recipient: "stratos".into(),
+            amount: Uint128::new(100),
+            memo: Some(format!("transfer {}", i)),
+            decoys: None,
+            entropy: None,
+            padding: None
+        }.test_exec(&snip, &mut chain, setsuna.clone(), &[]).unwrap();
+    }
+
+    assert!(ExecuteMsg::SetHistoryRetention {
+        retention: HistoryRetention { max_entries: Some(3), max_age: None },
+        padding: None
+    }.test_exec(&snip, &mut chain, setsuna.clone(), &[]).is_err());
+
+    assert!(ExecuteMsg::SetHistoryRetention {
+        retention: HistoryRetention { max_entries: Some(0), max_age: None },
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_err());
+
+    assert!(ExecuteMsg::SetHistoryRetention {
+        retention: HistoryRetention { max_entries: None, max_age: Some(0) },
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_err());
+
+    // Ages that would overflow the block time never expire anything
+    assert!(ExecuteMsg::SetHistoryRetention {
+        retention: HistoryRetention { max_entries: Some(3), max_age: Some(u64::MAX) },
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_ok());
+
+    // Appending only prunes a couple of entries at a time
+    ExecuteMsg::Transfer {
+        recipient: "stratos".into(),
+        amount: Uint128::new(100),
+        memo: None,
+        decoys: None,
+        entropy: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, setsuna.clone(), &[]).unwrap();
+
+    let history = |chain: &App, page: u32, page_size: u32| -> Vec<RichTx> {
+        let answer: QueryAnswer = QueryMsg::TransactionHistory {
+            address: setsuna.clone().into(),
+            key: "password".into(),
+            page: Some(page),
+            page_size
+        }.test_query(&snip, chain).unwrap();
+
+        match answer {
+            QueryAnswer::TransactionHistory { txs, .. } => txs,
+            _ => panic!("unexpected answer")
+        }
+    };
+
+    let txs = history(&chain, 0, 10);
+    assert_eq!(txs.iter().map(|tx| tx.id).collect::<Vec<_>>(), vec![3, 4, 5, 6]);
+    assert_eq!(txs[0].memo, Some("transfer 1".to_string()));
+    assert_eq!(txs[3].memo, None);
+
+    assert!(ExecuteMsg::PruneHistory {
+        accounts: vec![setsuna.clone().into_string()],
+        limit: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, setsuna.clone(), &[]).is_err());
+
+    assert!(ExecuteMsg::PruneHistory {
+        accounts: vec![setsuna.clone().into_string()],
+        limit: None,
+        padding: None
+    }.test_exec(&snip, &mut chain, Addr::unchecked("admin"), &[]).is_ok());
+
+    // Pages keep pointing at the same entries, pruned ones are left out
+    assert!(history(&chain, 0, 2).is_empty());
+    let txs = history(&chain, 1, 2);
+    assert_eq!(txs.iter().map(|tx| tx.id).collect::<Vec<_>>(), vec![4]);
+    assert_eq!(txs[0].action, TxAction::Transfer {
+        from: setsuna.clone(),
+        sender: setsuna.clone(),
+        recipient: Addr::unchecked("stratos")
+    });
+    assert_eq!(txs[0].coins, Coin {
+        denom: "TKN".into(),
+        amount: Uint128::new(100)
+    });
+
+    let txs = history(&chain, 2, 2);
+    assert_eq!(txs.iter().map(|tx| tx.id).collect::<Vec<_>>(), vec![5, 6]);
+}
@@ -160,7 +160,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
     let response = match msg {
         // Messages always available
         ExecuteMsg::SetContractStatus { level, .. } => {
-            set_contract_status(deps, info, level, ContractStatusLevel::StopAll)
+            set_contract_status(deps, env, info, level, ContractStatusLevel::StopAll)
         }
         // Messages available during panic mode
         ExecuteMsg::Claim {} => try_claim(deps, env, info, ContractStatusLevel::Panicked),
@@ -176,6 +176,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             collector,
         } => update_fees(
             deps,
+            env,
             info,
             staking,
             unbonding,
@@ -480,6 +481,7 @@ fn try_panic_unbond(
     let config = CONFIG.load(deps.storage)?;
     check_if_admin(
         &deps.querier,
+        &env,
         AdminPermissions::DerivativeAdmin,
         info.sender.to_string(),
         &config.admin,
@@ -529,6 +531,7 @@ fn try_panic_withdraw(
     let config = CONFIG.load(deps.storage)?;
     check_if_admin(
         &deps.querier,
+        &env,
         AdminPermissions::DerivativeAdmin,
         info.sender.to_string(),
         &config.admin,
@@ -596,6 +599,7 @@ fn try_panic_withdraw(
 /// StdResult<Response>.
 fn update_fees(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     staking: Option<Fee>,
     unbonding: Option<Fee>,
@@ -606,6 +610,7 @@ fn update_fees(
     let mut config = CONFIG.load(deps.storage)?;
     check_if_admin(
         &deps.querier,
+        &env,
         AdminPermissions::DerivativeAdmin,
         info.sender.to_string(),
         &config.admin,
@@ -1359,6 +1364,7 @@ fn get_token_info<C: CustomQuery>(
 /// Arguments:
 ///
 /// * `querier`: The querier object that can be used to query the state of the blockchain.
+/// * `env`: The environment of this contract, scoped permissions are checked against it.
 /// * `permission`: The permission you want to check for.
 /// * `user`: The user to check if they are an admin.
 /// * `admin_auth`: The contract that holds the admin permissions.
@@ -1369,16 +1375,18 @@ fn get_token_info<C: CustomQuery>(
 #[cfg(not(test))]
 fn check_if_admin(
     querier: &QuerierWrapper,
+    env: &Env,
     permission: AdminPermissions,
     user: String,
     admin_auth: &Contract,
 ) -> StdResult<()> {
-    validate_admin(querier, permission, user, admin_auth)
+    validate_admin(querier, env, permission, user, admin_auth)
 }
 
 #[cfg(test)]
 fn check_if_admin(
     _: &QuerierWrapper,
+    _: &Env,
     _: AdminPermissions,
     user: String,
     _: &Contract,
@@ -1439,6 +1447,7 @@ fn generate_stake_msg(
 /// The response is being returned.
 fn set_contract_status(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     status_level: ContractStatusLevel,
     priority: ContractStatusLevel,
@@ -1447,6 +1456,7 @@ fn set_contract_status(
     check_status(deps.storage, priority)?;
     check_if_admin(
         &deps.querier,
+        &env,
         AdminPermissions::DerivativeAdmin,
         info.sender.to_string(),
         &config.admin,
@@ -1596,6 +1606,7 @@ pub fn validate_permit<T: DeserializeOwned>(
 #[cfg(not(test))]
 pub fn validate_viewing_key(
     querier: &QuerierWrapper,
+    env: &Env,
     config: &Config,
     address: Addr,
     key: String,
@@ -1604,7 +1615,7 @@ pub fn validate_viewing_key(
         address: config.query_auth.address.clone(),
         code_hash: config.query_auth.code_hash.clone(),
     };
-    let is_valid = authenticate_vk(address, key, querier, &authenticator)?;
+    let is_valid = authenticate_vk(address, key, querier, env, &authenticator)?;
 
     if !is_valid {
         return Err(StdError::generic_err("Invalid viewing key"));
@@ -1615,6 +1626,7 @@ pub fn validate_viewing_key(
 #[cfg(test)]
 pub fn validate_viewing_key(
     _querier: &QuerierWrapper,
+    _env: &Env,
     _config: &Config,
     _address: Addr,
     key: String,
@@ -1630,7 +1642,7 @@ pub fn viewing_keys_queries(deps: Deps, env: &Env, msg: QueryMsg) -> StdResult<B
     let (addresses, key) = msg.get_validation_params(deps.api)?;
     let config = CONFIG.load(deps.storage)?;
     for address in addresses {
-        validate_viewing_key(&deps.querier, &config, address, key)?;
+        validate_viewing_key(&deps.querier, env, &config, address, key)?;
 
         return match msg {
             QueryMsg::Unbondings { address, .. } => query_unbondings(&deps, address),
@@ -50,6 +50,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             let mut config = CONFIG.load(deps.storage)?;
             validate_admin(
                 &deps.querier,
+                &env,
                 AdminPermissions::Snip20MigrationAdmin,
                 info.sender.to_string(),
                 &config.admin,
@@ -77,6 +78,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             let config = CONFIG.load(deps.storage)?;
             validate_admin(
                 &deps.querier,
+                &env,
                 AdminPermissions::Snip20MigrationAdmin,
                 info.sender.to_string(),
                 &config.admin,
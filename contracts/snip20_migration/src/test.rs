@@ -44,6 +44,7 @@ pub fn migration_test() {
             enable_mint: Some(true),
             enable_burn: Some(true),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(Snip20::default(), &mut chain, admin.clone(), "token0", &[])
@@ -71,6 +72,7 @@ pub fn migration_test() {
             enable_mint: Some(true),
             enable_burn: Some(false),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(Snip20::default(), &mut chain, admin.clone(), "token1", &[])
@@ -177,6 +179,8 @@ pub fn migration_test() {
         amount: Uint128::new(1000000),
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token0, &mut chain, admin.clone().into(), &[])
@@ -217,6 +221,8 @@ pub fn migration_test() {
         amount: Uint128::new(1_000_000_000_000_000),
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }
     .test_exec(&token0, &mut chain, admin.clone().into(), &[])
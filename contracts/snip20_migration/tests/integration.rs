@@ -50,6 +50,7 @@ fn test_admin() {
             enable_mint: Some(true),
             enable_burn: Some(true),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
@@ -79,6 +80,7 @@ fn test_admin() {
             enable_mint: Some(true),
             enable_burn: Some(true),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
     }
     .test_init(
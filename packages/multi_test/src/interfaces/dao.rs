@@ -116,6 +116,8 @@ pub fn init_dao(
                 tm_allowance_type.clone()[i][j].clone(),
                 tm_allocation_amount[i][j].clone(),
                 tm_allocation_tolerance[i][j].clone(),
+                0,
+                0,
                 i,
             )?;
         }
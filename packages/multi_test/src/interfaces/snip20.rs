@@ -108,6 +108,8 @@ pub fn send_exec(
         amount,
         msg,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
         recipient_code_hash: None,
     }
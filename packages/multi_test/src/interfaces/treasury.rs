@@ -231,6 +231,38 @@ pub fn metrics_query(
     }
 }
 
+pub fn period_report_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+    snip20_symbol: &str,
+    date: Option<String>,
+    epoch: Option<Uint128>,
+    period: Period,
+) -> StdResult<Vec<treasury::MetricTotal>> {
+    let res = treasury::QueryMsg::PeriodReport {
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .address
+            .to_string(),
+        date,
+        epoch,
+        period,
+    }
+    .test_query(
+        &contracts
+            .get(&SupportedContracts::Treasury)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+    )?;
+    match res {
+        treasury::QueryAnswer::PeriodReport { totals } => Ok(totals),
+        _ => Err(StdError::generic_err("query failed")),
+    }
+}
+
 pub fn batch_balance_query(
     chain: &App,
     contracts: &DeployedContracts,
@@ -412,6 +444,167 @@ pub fn set_run_level_exec(
     }
 }
 
+pub fn set_oracle_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    oracle: Option<RawContract>,
+    max_price_age: Option<u64>,
+) -> StdResult<()> {
+    match (treasury::ExecuteMsg::SetOracle {
+        oracle,
+        max_price_age,
+    }
+    .test_exec(
+        &contracts
+            .get(&SupportedContracts::Treasury)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+        Addr::unchecked(sender),
+        &[],
+    )) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(StdError::generic_err(e.to_string())),
+    }
+}
+
+pub fn set_price_key_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    snip20_symbol: &str,
+    key: &str,
+) -> StdResult<()> {
+    match (treasury::ExecuteMsg::SetPriceKey {
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .clone()
+            .address
+            .to_string(),
+        key: key.to_string(),
+    }
+    .test_exec(
+        &contracts
+            .get(&SupportedContracts::Treasury)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+        Addr::unchecked(sender),
+        &[],
+    )) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(StdError::generic_err(e.to_string())),
+    }
+}
+
+fn stream_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    msg: treasury::ExecuteMsg,
+) -> StdResult<()> {
+    match msg.test_exec(
+        &contracts
+            .get(&SupportedContracts::Treasury)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+        Addr::unchecked(sender),
+        &[],
+    ) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(StdError::generic_err(e.to_string())),
+    }
+}
+
+pub fn create_stream_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    snip20_symbol: &str,
+    recipient: &str,
+    amount: Uint128,
+    cycle: Cycle,
+    end: Option<u64>,
+) -> StdResult<()> {
+    stream_exec(chain, sender, contracts, treasury::ExecuteMsg::CreateStream {
+        recipient: recipient.to_string(),
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .clone()
+            .address
+            .to_string(),
+        amount,
+        cycle,
+        start: None,
+        end,
+    })
+}
+
+pub fn withdraw_stream_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    id: u64,
+) -> StdResult<()> {
+    stream_exec(chain, sender, contracts, treasury::ExecuteMsg::WithdrawStream { id })
+}
+
+pub fn pause_stream_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    id: u64,
+) -> StdResult<()> {
+    stream_exec(chain, sender, contracts, treasury::ExecuteMsg::PauseStream { id })
+}
+
+pub fn resume_stream_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    id: u64,
+) -> StdResult<()> {
+    stream_exec(chain, sender, contracts, treasury::ExecuteMsg::ResumeStream { id })
+}
+
+pub fn cancel_stream_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    id: u64,
+) -> StdResult<()> {
+    stream_exec(chain, sender, contracts, treasury::ExecuteMsg::CancelStream { id })
+}
+
+pub fn stream_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+    id: u64,
+) -> StdResult<(treasury::Stream, Uint128)> {
+    let res = treasury::QueryMsg::Stream { id }.test_query(
+        &contracts
+            .get(&SupportedContracts::Treasury)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+    )?;
+    match res {
+        treasury::QueryAnswer::Stream {
+            stream,
+            withdrawable,
+        } => Ok((stream, withdrawable)),
+        _ => Err(StdError::generic_err("query failed")),
+    }
+}
+
 pub fn set_config(
     chain: &mut App,
     sender: &str,
@@ -266,6 +266,70 @@ pub fn metrics_query(
     }
 }
 
+pub fn pnl_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+    treasury_manager_contract: SupportedContracts,
+    snip20_symbol: &str,
+) -> StdResult<Vec<treasury_manager::AdapterPnl>> {
+    let res = treasury_manager::QueryMsg::Pnl {
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .address
+            .to_string(),
+    }
+    .test_query(
+        &contracts
+            .get(&treasury_manager_contract)
+            .unwrap()
+            .clone()
+            .into(),
+        &chain,
+    )?;
+    match res {
+        treasury_manager::QueryAnswer::Pnl { adapters } => Ok(adapters),
+        _ => Err(StdError::generic_err(format!(
+            "Failed to.test_query treasury_manager pnl",
+        ))),
+    }
+}
+
+pub fn period_report_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+    treasury_manager_contract: SupportedContracts,
+    snip20_symbol: &str,
+    date: Option<String>,
+    epoch: Option<Uint128>,
+    period: Period,
+) -> StdResult<Vec<treasury_manager::MetricTotal>> {
+    let res = treasury_manager::QueryMsg::PeriodReport {
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .address
+            .to_string(),
+        date,
+        epoch,
+        period,
+    }
+    .test_query(
+        &contracts
+            .get(&treasury_manager_contract)
+            .unwrap()
+            .clone()
+            .into(),
+        &chain,
+    )?;
+    match res {
+        treasury_manager::QueryAnswer::PeriodReport { totals } => Ok(totals),
+        _ => Err(StdError::generic_err(format!(
+            "Failed to.test_query treasury_manager period report",
+        ))),
+    }
+}
+
 pub fn unbonding_query(
     chain: &App,
     contracts: &DeployedContracts,
@@ -634,6 +698,8 @@ pub fn allocate_exec(
     alloc_type: treasury_manager::AllocationType,
     amount: Uint128,
     tolerance: Uint128,
+    unbond_priority: u32,
+    unbond_latency: u64,
     id: usize,
 ) -> StdResult<()> {
     match (treasury_manager::ExecuteMsg::Allocate {
@@ -649,6 +715,8 @@ pub fn allocate_exec(
             alloc_type,
             amount,
             tolerance,
+            unbond_priority,
+            unbond_latency,
         },
     }
     .test_exec(
@@ -665,3 +733,131 @@ pub fn allocate_exec(
         Err(e) => Err(StdError::generic_err(e.to_string())),
     }
 }
+
+pub fn set_health_policy_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    treasury_manager_contract: SupportedContracts,
+    max_balance_drop: Uint128,
+    emergency_unbond: bool,
+) -> StdResult<()> {
+    match (treasury_manager::ExecuteMsg::SetHealthPolicy {
+        policy: treasury_manager::HealthPolicy {
+            max_balance_drop,
+            emergency_unbond,
+        },
+    }
+    .test_exec(
+        &contracts
+            .get(&treasury_manager_contract)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+        Addr::unchecked(sender),
+        &[],
+    )) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(StdError::generic_err(e.to_string())),
+    }
+}
+
+pub fn reset_health_exec(
+    chain: &mut App,
+    sender: &str,
+    contracts: &DeployedContracts,
+    treasury_manager_contract: SupportedContracts,
+    snip20_symbol: &str,
+    adapter_contract: SupportedContracts,
+) -> StdResult<()> {
+    match (treasury_manager::ExecuteMsg::ResetHealth {
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .address
+            .to_string(),
+        adapter: contracts
+            .get(&adapter_contract)
+            .unwrap()
+            .address
+            .to_string(),
+    }
+    .test_exec(
+        &contracts
+            .get(&treasury_manager_contract)
+            .unwrap()
+            .clone()
+            .into(),
+        chain,
+        Addr::unchecked(sender),
+        &[],
+    )) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(StdError::generic_err(e.to_string())),
+    }
+}
+
+pub fn allocation_health_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+    treasury_manager_contract: SupportedContracts,
+    snip20_symbol: &str,
+) -> StdResult<Vec<treasury_manager::AllocationHealth>> {
+    let res = treasury_manager::QueryMsg::AllocationHealth {
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .address
+            .to_string(),
+    }
+    .test_query(
+        &contracts
+            .get(&treasury_manager_contract)
+            .unwrap()
+            .clone()
+            .into(),
+        &chain,
+    )?;
+    match res {
+        treasury_manager::QueryAnswer::AllocationHealth { allocations, .. } => Ok(allocations),
+        _ => Err(StdError::generic_err(format!(
+            "Failed to.test_query treasury_manager allocation health",
+        ))),
+    }
+}
+
+pub fn unbond_estimate_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+    treasury_manager_contract: SupportedContracts,
+    snip20_symbol: &str,
+    amount: Uint128,
+) -> StdResult<(Uint128, Vec<treasury_manager::UnbondTranche>, Uint128)> {
+    let res = treasury_manager::QueryMsg::UnbondEstimate {
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .address
+            .to_string(),
+        amount,
+    }
+    .test_query(
+        &contracts
+            .get(&treasury_manager_contract)
+            .unwrap()
+            .clone()
+            .into(),
+        &chain,
+    )?;
+    match res {
+        treasury_manager::QueryAnswer::UnbondEstimate {
+            immediate,
+            delayed,
+            unavailable,
+        } => Ok((immediate, delayed, unavailable)),
+        _ => Err(StdError::generic_err(format!(
+            "Failed to.test_query treasury_manager unbond estimate",
+        ))),
+    }
+}
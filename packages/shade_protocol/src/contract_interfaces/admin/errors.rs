@@ -15,6 +15,16 @@ pub enum Error {
     IsShutdown,
     IsUnderMaintenance,
     InvalidPermissionFormat,
+    InvalidPermissionWindow,
+    NotPendingSuper,
+    UnauthorizedApprover,
+    InvalidApprovalThreshold,
+    PendingActionNotFound,
+    AlreadyApproved,
+    InvalidRoleFormat,
+    RoleNotFound,
+    RoleAlreadyExists,
+    NoPendingSuper,
 }
 
 impl_into_u8!(Error);
@@ -36,6 +46,20 @@ impl CodeType for Error {
                 Error::InvalidPermissionFormat => {
                     "{} must be > 10 characters and only contains 0-9, A-Z, and underscores"
                 }
+                Error::InvalidPermissionWindow => "Permission window must end after it starts",
+                Error::NotPendingSuper => "{} is not the pending super admin",
+                Error::UnauthorizedApprover => "{} is not an approver",
+                Error::InvalidApprovalThreshold => {
+                    "Approval threshold {} is invalid for {} approvers"
+                }
+                Error::PendingActionNotFound => "Pending action {} was not found",
+                Error::AlreadyApproved => "{} already approved action {}",
+                Error::InvalidRoleFormat => {
+                    "{} must be 1-64 characters and only contain a-z, 0-9, and dashes"
+                }
+                Error::RoleNotFound => "Role {} does not exist",
+                Error::RoleAlreadyExists => "Role {} already exists",
+                Error::NoPendingSuper => "There is no pending super admin transfer",
             },
             context,
         )
@@ -72,3 +96,36 @@ pub fn invalid_permission_format(permission: &str) -> StdError {
     ])
     .to_error()
 }
+pub fn invalid_permission_window() -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::InvalidPermissionWindow, vec![]).to_error()
+}
+pub fn not_pending_super(address: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::NotPendingSuper, vec![address]).to_error()
+}
+pub fn unauthorized_approver(address: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::UnauthorizedApprover, vec![address]).to_error()
+}
+pub fn invalid_approval_threshold(threshold: &str, approvers: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::InvalidApprovalThreshold, vec![
+        threshold, approvers,
+    ])
+    .to_error()
+}
+pub fn pending_action_not_found(id: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::PendingActionNotFound, vec![id]).to_error()
+}
+pub fn already_approved(address: &str, id: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::AlreadyApproved, vec![address, id]).to_error()
+}
+pub fn invalid_role_format(role: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::InvalidRoleFormat, vec![role]).to_error()
+}
+pub fn role_not_found(role: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::RoleNotFound, vec![role]).to_error()
+}
+pub fn role_already_exists(role: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::RoleAlreadyExists, vec![role]).to_error()
+}
+pub fn no_pending_super() -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::NoPendingSuper, vec![]).to_error()
+}
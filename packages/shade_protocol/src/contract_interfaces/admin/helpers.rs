@@ -3,15 +3,17 @@ use crate::{
     utils::Query,
     Contract,
 };
-use cosmwasm_std::{QuerierWrapper, StdResult};
+use cosmwasm_std::{Env, QuerierWrapper, StdResult};
 
+/// Validates the permission for the calling contract, so grants scoped to it are included
 pub fn validate_admin<T: Into<String> + Clone>(
     querier: &QuerierWrapper,
+    env: &Env,
     permission: AdminPermissions,
     user: T,
     admin_auth: &Contract,
 ) -> StdResult<()> {
-    if admin_is_valid(querier, permission.clone(), user.clone(), admin_auth)? {
+    if admin_is_valid(querier, env, permission.clone(), user.clone(), admin_auth)? {
         Ok(())
     } else {
         Err(unauthorized_admin(&user.into(), &permission.into_string()))
@@ -20,6 +22,7 @@ pub fn validate_admin<T: Into<String> + Clone>(
 
 pub fn admin_is_valid<T: Into<String>>(
     querier: &QuerierWrapper,
+    env: &Env,
     permission: AdminPermissions,
     user: T,
     admin_auth: &Contract,
@@ -28,6 +31,7 @@ pub fn admin_is_valid<T: Into<String>>(
         QueryMsg::ValidateAdminPermission {
             permission: permission.into_string(),
             user: user.into(),
+            contract: Some(env.contract.address.to_string()),
         }
         .query(querier, admin_auth);
 
@@ -46,9 +46,56 @@ impl InstantiateCallback for InstantiateMsg {
 pub enum ExecuteMsg {
     UpdateRegistry { action: RegistryAction },
     UpdateRegistryBulk { actions: Vec<RegistryAction> },
+    // Proposes a new super admin which must then accept the transfer
     TransferSuper { new_super: String },
+    AcceptSuper {},
+    CancelSuperTransfer {},
     SelfDestruct {},
     ToggleStatus { new_status: AdminAuthStatus },
+    // Once set, registry, super transfer and destructive actions require threshold approvals
+    SetApprovers { approvers: Vec<String>, threshold: u8 },
+    ApproveAction { id: u64 },
+    CancelAction { id: u64 },
+}
+
+/// Actions which wait for approvals when an approver set is configured.
+#[cw_serde]
+pub enum ProtectedAction {
+    UpdateRegistry {
+        action: RegistryAction,
+    },
+    UpdateRegistryBulk {
+        actions: Vec<RegistryAction>,
+    },
+    TransferSuper {
+        new_super: String,
+    },
+    SelfDestruct {},
+    Shutdown {},
+    SetApprovers {
+        approvers: Vec<String>,
+        threshold: u8,
+    },
+}
+
+#[cw_serde]
+pub struct PendingAction {
+    pub id: u64,
+    pub action: ProtectedAction,
+    pub proposer: Addr,
+    pub approvals: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct ApproverSet {
+    pub approvers: Vec<Addr>,
+    pub threshold: u8,
+}
+
+impl ApproverSet {
+    pub fn is_required(&self) -> bool {
+        self.threshold > 0
+    }
 }
 
 #[cw_serde]
@@ -59,14 +106,40 @@ pub enum RegistryAction {
     GrantAccess {
         permissions: Vec<String>,
         user: String,
+        // Only grants the permissions on this contract
+        contract: Option<String>,
+        // Seconds timestamps limiting when the permissions are valid
+        not_before: Option<u64>,
+        expires_at: Option<u64>,
     },
     RevokeAccess {
         permissions: Vec<String>,
         user: String,
+        contract: Option<String>,
     },
     DeleteAdmin {
         user: String,
     },
+    // Roles bundle permissions which are granted globally to their members
+    CreateRole {
+        role: String,
+        permissions: Vec<String>,
+    },
+    EditRole {
+        role: String,
+        permissions: Vec<String>,
+    },
+    DeleteRole {
+        role: String,
+    },
+    AssignRole {
+        role: String,
+        user: String,
+    },
+    UnassignRole {
+        role: String,
+        user: String,
+    },
 }
 
 impl ExecuteCallback for ExecuteMsg {
@@ -81,9 +154,45 @@ pub enum QueryMsg {
     #[returns(AdminsResponse)]
     GetAdmins {},
     #[returns(PermissionsResponse)]
-    GetPermissions { user: String },
+    GetPermissions {
+        user: String,
+        // Returns the permissions scoped to this contract instead of the global ones
+        contract: Option<String>,
+    },
     #[returns(ValidateAdminPermissionResponse)]
-    ValidateAdminPermission { permission: String, user: String },
+    ValidateAdminPermission {
+        permission: String,
+        user: String,
+        // Contract the permission is used on, scoped grants only apply to it
+        contract: Option<String>,
+    },
+    #[returns(ExpiringPermissionsResponse)]
+    GetExpiringPermissions {
+        // Seconds from now
+        within: u64,
+    },
+    #[returns(PendingSuperResponse)]
+    GetPendingSuper {},
+    #[returns(ApproverSet)]
+    GetApprovers {},
+    #[returns(PendingActionsResponse)]
+    GetPendingActions {},
+    #[returns(RolesResponse)]
+    GetRoles {},
+    #[returns(RoleMembersResponse)]
+    GetRoleMembers { role: String },
+    // Direct grants followed by the members of roles granting the permission
+    #[returns(PermissionHoldersResponse)]
+    GetPermissionHolders {
+        permission: String,
+        start: Option<u64>,
+        limit: Option<u64>,
+    },
+    #[returns(AuditLogResponse)]
+    GetAuditLog {
+        start: Option<u64>,
+        limit: Option<u64>,
+    },
 }
 
 impl Query for QueryMsg {
@@ -106,6 +215,104 @@ pub struct AdminsResponse {
     pub admins: Vec<Addr>,
 }
 
+#[cw_serde]
+pub struct PermissionWindow {
+    pub not_before: Option<u64>,
+    pub expires_at: Option<u64>,
+}
+
+impl PermissionWindow {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.not_before.map_or(true, |not_before| now >= not_before)
+            && self.expires_at.map_or(true, |expires_at| now < expires_at)
+    }
+}
+
+#[cw_serde]
+pub struct ExpiringPermission {
+    pub user: Addr,
+    pub permission: String,
+    // None for global grants
+    pub contract: Option<Addr>,
+    pub expires_at: u64,
+}
+
+#[cw_serde]
+pub struct ExpiringPermissionsResponse {
+    pub permissions: Vec<ExpiringPermission>,
+}
+
+#[cw_serde]
+pub struct PermissionHolder {
+    pub user: Addr,
+    // None for global grants
+    pub contract: Option<Addr>,
+    // Role the permission is held through, None for direct grants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+}
+
+#[cw_serde]
+pub struct PermissionHoldersResponse {
+    pub holders: Vec<PermissionHolder>,
+    pub total: u64,
+}
+
+#[cw_serde]
+pub enum AuditAction {
+    Registry { action: RegistryAction },
+    TransferSuper { new_super: String },
+    AcceptSuper {},
+    CancelSuperTransfer {},
+    ToggleStatus { new_status: AdminAuthStatus },
+    SelfDestruct {},
+    SetApprovers { approvers: Vec<Addr>, threshold: u8 },
+    CancelAction { id: u64 },
+}
+
+#[cw_serde]
+pub struct AuditEntry {
+    pub id: u64,
+    pub actor: Addr,
+    // Approvers which signed off on the action, empty when it ran right away
+    pub approvers: Vec<Addr>,
+    pub action: AuditAction,
+    // Block time in seconds
+    pub time: u64,
+}
+
+#[cw_serde]
+pub struct AuditLogResponse {
+    pub entries: Vec<AuditEntry>,
+    pub total: u64,
+}
+
+#[cw_serde]
+pub struct Role {
+    pub name: String,
+    pub permissions: Vec<String>,
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct RoleMembersResponse {
+    pub members: Vec<Addr>,
+}
+
+#[cw_serde]
+pub struct PendingSuperResponse {
+    pub pending_super: Option<Addr>,
+}
+
+#[cw_serde]
+pub struct PendingActionsResponse {
+    pub actions: Vec<PendingAction>,
+}
+
 #[cw_serde]
 pub struct ValidateAdminPermissionResponse {
     pub has_permission: bool,
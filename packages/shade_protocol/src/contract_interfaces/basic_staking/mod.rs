@@ -52,6 +52,13 @@ pub struct Unbonding {
     pub complete: Uint128,
 }
 
+// Staked amount recorded whenever a stake changes
+#[cw_serde]
+pub struct Checkpoint {
+    pub time: Uint128,
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 pub struct Reward {
     pub token: Contract,
@@ -245,6 +252,14 @@ pub enum QueryMsg {
     Staked {
         auth: Auth,
     },
+    // Stake held at the end of the given time
+    StakedAt {
+        auth: Auth,
+        time: Uint128,
+    },
+    TotalStakedAt {
+        time: Uint128,
+    },
     Rewards {
         auth: Auth,
     },
@@ -287,6 +302,13 @@ pub enum QueryAnswer {
     Staked {
         amount: Uint128,
     },
+    StakedAt {
+        user: Addr,
+        amount: Uint128,
+    },
+    TotalStakedAt {
+        amount: Uint128,
+    },
     Rewards {
         rewards: Vec<Reward>,
     },
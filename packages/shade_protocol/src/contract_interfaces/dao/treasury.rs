@@ -9,7 +9,7 @@ use crate::c_std::{Addr, Api, Binary, Coin, StdResult, Uint128};
 use crate::utils::{ExecuteCallback, InstantiateCallback, Query};
 use cosmwasm_schema::cw_serde;
 
-use crate::utils::storage::plus::period_storage::Period;
+use crate::utils::storage::plus::period_storage::{self, Period};
 
 /// The permission referenced in the Admin Auth contract to give a user
 /// admin permissions for the Shade Treasury
@@ -35,6 +35,7 @@ pub enum Context {
     Migration,
     Unbond,
     Wrap,
+    Stream,
 }
 
 #[cw_serde]
@@ -46,6 +47,7 @@ pub enum Action {
     FundsReceived,
     SendFunds,
     Wrap,
+    StreamPayout,
 }
 
 #[cw_serde]
@@ -58,10 +60,15 @@ pub struct Metric {
     pub user: Addr,
 }
 
+pub type MetricTotal = period_storage::MetricTotal<Action, Context>;
+
 #[cw_serde]
 pub enum AllowanceType {
     Amount,
     Portion,
+    /// Portion of the USD value of all registered assets,
+    /// tolerance is likewise applied to portfolio value
+    Portfolio,
 }
 
 #[cw_serde]
@@ -104,6 +111,35 @@ pub struct AllowanceMeta {
     pub last_refresh: String,
 }
 
+#[cw_serde]
+pub struct AssetValue {
+    pub asset: Addr,
+    pub key: String,
+    /// USD price of a whole token, 18 decimals
+    pub price: Uint128,
+    pub amount: Uint128,
+    /// USD value of `amount`, 18 decimals
+    pub value: Uint128,
+    /// Share of total portfolio value, 10^18 == 100%
+    pub weight: Uint128,
+}
+
+/// Recurring payment to a recipient out of treasury reserves
+#[cw_serde]
+pub struct Stream {
+    pub id: u64,
+    pub recipient: Addr,
+    pub asset: Addr,
+    /// Paid out for every completed cycle since start
+    pub amount: Uint128,
+    pub cycle: Cycle,
+    /// Seconds, moved forward by the time spent paused
+    pub start: u64,
+    pub end: Option<u64>,
+    pub paid: Uint128,
+    pub paused_at: Option<u64>,
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub admin_auth: RawContract,
@@ -151,6 +187,40 @@ pub enum ExecuteMsg {
     SetRunLevel {
         run_level: RunLevel,
     },
+    // Shade oracle router used to value the portfolio, None disables valuation
+    SetOracle {
+        oracle: Option<RawContract>,
+        // Oldest price accepted in seconds, defaults to an hour
+        max_price_age: Option<u64>,
+    },
+    // Oracle key used to price an asset e.g. "SCRT"
+    SetPriceKey {
+        asset: String,
+        key: String,
+    },
+    // Pay recipient amount every cycle from start (default now) until end
+    CreateStream {
+        recipient: String,
+        asset: String,
+        amount: Uint128,
+        cycle: Cycle,
+        start: Option<u64>,
+        end: Option<u64>,
+    },
+    // Recipient claims everything accrued so far
+    WithdrawStream {
+        id: u64,
+    },
+    PauseStream {
+        id: u64,
+    },
+    ResumeStream {
+        id: u64,
+    },
+    // Pays out what has accrued and removes the stream
+    CancelStream {
+        id: u64,
+    },
 }
 
 impl ExecuteCallback for ExecuteMsg {
@@ -201,6 +271,30 @@ pub enum ExecuteAnswer {
         success: Vec<Coin>,
         failed: Vec<Coin>,
     },
+    SetOracle {
+        status: ResponseStatus,
+    },
+    SetPriceKey {
+        status: ResponseStatus,
+    },
+    CreateStream {
+        status: ResponseStatus,
+        id: u64,
+    },
+    WithdrawStream {
+        status: ResponseStatus,
+        amount: Uint128,
+    },
+    PauseStream {
+        status: ResponseStatus,
+    },
+    ResumeStream {
+        status: ResponseStatus,
+    },
+    CancelStream {
+        status: ResponseStatus,
+        amount: Uint128,
+    },
 }
 
 #[cw_serde]
@@ -231,6 +325,21 @@ pub enum QueryMsg {
     Reserves {
         asset: String,
     },
+    // USD valuation of all registered assets
+    Portfolio {},
+    Stream {
+        id: u64,
+    },
+    Streams {
+        asset: String,
+    },
+    // Metric totals for asset over the period containing date/epoch (default now)
+    PeriodReport {
+        asset: String,
+        date: Option<String>,
+        epoch: Option<Uint128>,
+        period: Period,
+    },
 }
 
 impl Query for QueryMsg {
@@ -247,4 +356,8 @@ pub enum QueryAnswer {
     Metrics { metrics: Vec<Metric> },
     Balance { amount: Uint128 },
     Reserves { amount: Uint128 },
+    Portfolio { total: Uint128, assets: Vec<AssetValue> },
+    Stream { stream: Stream, withdrawable: Uint128 },
+    Streams { streams: Vec<Stream> },
+    PeriodReport { totals: Vec<MetricTotal> },
 }
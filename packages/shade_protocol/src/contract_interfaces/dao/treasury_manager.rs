@@ -4,7 +4,7 @@ use crate::{
     utils::{
         asset::{Contract, RawContract},
         generic_response::ResponseStatus,
-        storage::plus::period_storage::Period,
+        storage::plus::period_storage::{self, Period},
     },
 };
 
@@ -18,6 +18,7 @@ pub enum Context {
     Unbond,
     Claim,
     Holders,
+    Health,
 }
 
 #[cw_serde]
@@ -29,6 +30,8 @@ pub enum Action {
     SendFundsFrom,
     RealizeGains,
     RealizeLosses,
+    Degrade,
+    Restore,
     //TODO
     AddHolder,
     RemoveHolder,
@@ -44,6 +47,56 @@ pub struct Metric {
     pub user: Addr,
 }
 
+pub type MetricTotal = period_storage::MetricTotal<Action, Context>;
+
+/// Capital deployed to an adapter, unbonding releases cost basis pro rata
+/// to the bonded balance and realizes the difference
+#[cw_serde]
+pub struct AdapterAccounting {
+    pub adapter: Addr,
+    pub cost_basis: Uint128,
+    pub realized_gains: Uint128,
+    pub realized_losses: Uint128,
+}
+
+#[cw_serde]
+pub struct AdapterPnl {
+    pub adapter: Addr,
+    pub cost_basis: Uint128,
+    /// Adapter balance excluding what is unbonding
+    pub bonded: Uint128,
+    pub realized_gains: Uint128,
+    pub realized_losses: Uint128,
+    pub unrealized_gains: Uint128,
+    pub unrealized_losses: Uint128,
+}
+
+#[cw_serde]
+pub enum Health {
+    Healthy,
+    Degraded,
+}
+
+/// Degraded allocations are skipped by update & receive no new funds until reset
+#[cw_serde]
+pub struct AllocationHealth {
+    pub contract: Addr,
+    pub health: Health,
+    /// Balance the adapter should hold given the last update & the flows since
+    pub expected_balance: Uint128,
+    pub reason: Option<String>,
+    /// Time of the last health transition
+    pub since: u64,
+}
+
+#[cw_serde]
+pub struct HealthPolicy {
+    /// Portion of the expected balance (10^18 = 100%) an adapter can lose between updates
+    pub max_balance_drop: Uint128,
+    /// Unbond everything from an allocation once it is degraded
+    pub emergency_unbond: bool,
+}
+
 #[cw_serde]
 pub struct Config {
     pub admin_auth: Contract,
@@ -86,6 +139,12 @@ pub struct RawAllocation {
     pub alloc_type: AllocationType,
     pub amount: Uint128,
     pub tolerance: Uint128,
+    /// Lower priorities are unbonded from first, defaults to 0
+    #[serde(default)]
+    pub unbond_priority: u32,
+    /// Expected days for unbonded funds to become claimable, 0 when instant
+    #[serde(default)]
+    pub unbond_latency: u64,
 }
 
 impl RawAllocation {
@@ -96,6 +155,8 @@ impl RawAllocation {
             alloc_type: self.alloc_type,
             amount: self.amount,
             tolerance: self.tolerance,
+            unbond_priority: self.unbond_priority,
+            unbond_latency: self.unbond_latency,
         })
     }
 }
@@ -107,6 +168,12 @@ pub struct Allocation {
     pub alloc_type: AllocationType,
     pub amount: Uint128,
     pub tolerance: Uint128,
+    /// Lower priorities are unbonded from first, defaults to 0
+    #[serde(default)]
+    pub unbond_priority: u32,
+    /// Expected days for unbonded funds to become claimable, 0 when instant
+    #[serde(default)]
+    pub unbond_latency: u64,
 }
 
 #[cw_serde]
@@ -124,6 +191,10 @@ pub struct AllocationMeta {
     pub alloc_type: AllocationType,
     pub amount: Uint128,
     pub tolerance: Uint128,
+    #[serde(default)]
+    pub unbond_priority: u32,
+    #[serde(default)]
+    pub unbond_latency: u64,
 }
 
 #[cw_serde]
@@ -135,6 +206,15 @@ pub struct AllocationTempData {
     pub balance: Uint128,
     pub unbondable: Uint128,
     pub unbonding: Uint128,
+    pub unbond_priority: u32,
+    pub unbond_latency: u64,
+}
+
+/// Funds expected to be claimable after days
+#[cw_serde]
+pub struct UnbondTranche {
+    pub days: u64,
+    pub amount: Uint128,
 }
 
 #[cw_serde]
@@ -174,6 +254,13 @@ pub enum ExecuteMsg {
     RemoveHolder {
         holder: String,
     },
+    SetHealthPolicy {
+        policy: HealthPolicy,
+    },
+    ResetHealth {
+        asset: String,
+        adapter: String,
+    },
     Manager(manager::SubExecuteMsg),
 }
 
@@ -206,6 +293,12 @@ pub enum ExecuteAnswer {
     RemoveHolder {
         status: ResponseStatus,
     },
+    SetHealthPolicy {
+        status: ResponseStatus,
+    },
+    ResetHealth {
+        status: ResponseStatus,
+    },
     Manager(manager::ExecuteAnswer),
 }
 
@@ -228,6 +321,25 @@ pub enum QueryMsg {
         epoch: Option<Uint128>,
         period: Period,
     },
+    // Per adapter cost basis, realized & unrealized results
+    Pnl {
+        asset: String,
+    },
+    // Metric totals for asset over the period containing date/epoch (default now)
+    PeriodReport {
+        asset: String,
+        date: Option<String>,
+        epoch: Option<Uint128>,
+        period: Period,
+    },
+    AllocationHealth {
+        asset: String,
+    },
+    // How an unbond of amount would be filled, immediately & after the adapters' unbonding
+    UnbondEstimate {
+        asset: String,
+        amount: Uint128,
+    },
     Manager(manager::SubQueryMsg),
 }
 
@@ -244,4 +356,15 @@ pub enum QueryAnswer {
     Holders { holders: Vec<Addr> },
     Holding { holding: Holding },
     Metrics { metrics: Vec<Metric> },
+    Pnl { adapters: Vec<AdapterPnl> },
+    PeriodReport { totals: Vec<MetricTotal> },
+    AllocationHealth {
+        policy: Option<HealthPolicy>,
+        allocations: Vec<AllocationHealth>,
+    },
+    UnbondEstimate {
+        immediate: Uint128,
+        delayed: Vec<UnbondTranche>,
+        unavailable: Uint128,
+    },
 }
@@ -40,5 +40,19 @@ errors!("governance";
     VotingMoreThanBalance, "Total vote is greater than available balance", voting_balance,
     VotingMsgNotSet, "Msg missing voting information", voting_msg,
     VotingTimeReached, "Voting time was reached on {}", voting_time,
-    VotingNotInState, "Not in public voting phase", voting_not_state
+    VotingNotInState, "Not in public voting phase", voting_not_state,
+    SnapshotAuthNotSet, "Msg missing staking authentication for snapshot voting", snapshot_auth,
+    SnapshotWrongUser, "Staking authentication does not belong to {}", snapshot_user,
+    VotingMoreThanSnapshot, "Total vote is greater than the staked balance when voting started", voting_snapshot,
+    ProposalNotFailed, "Proposal has not failed", not_failed,
+    CannotRetry, "Proposal could only be retried until {}", cannot_retry,
+    ProposalTimelocked, "Proposal is timelocked until {}", timelocked,
+    ProposalNotQueued, "Proposal is not queued", not_queued,
+    NoGuardian, "Profile {} has no guardian assembly", no_guardian,
+    GuardianAlreadyVetoed, "{} already vetoed this proposal", guardian_already_vetoed,
+    SelfDelegation, "Cannot delegate voting power to yourself", self_delegation,
+    NotDelegating, "No voting power is delegated", not_delegating,
+    TooManyDelegators, "{} already has the maximum of {} delegators", too_many_delegators,
+    LiveDelegation, "Voting power can only be delegated when it is snapshotted", live_delegation,
+    ConvictionAuthNotSet, "Msg missing staking authentication for conviction voting", conviction_auth
 );
@@ -13,8 +13,8 @@ use crate::{
         assembly::{Assembly, AssemblyMsg},
         contract::AllowedContract,
         profile::{Profile, UpdateProfile},
-        proposal::{Proposal, ProposalMsg},
-        vote::Vote,
+        proposal::{Proposal, ProposalFilter, ProposalMsg, ProposalSummary},
+        vote::{Delegation, Delegator, Vote},
     },
     utils::{asset::Contract, generic_response::ResponseStatus},
 };
@@ -32,7 +32,7 @@ use crate::utils::storage::plus::ItemStorage;
 
 // TODO: add errors
 
-// Admin command variable spot
+// Admin command variable spot, typed spots are written as {~address}, {~uint128}, etc.
 pub const MSG_VARIABLE: &str = "{~}";
 
 #[cw_serde]
@@ -41,6 +41,9 @@ pub struct Config {
     pub treasury: Addr,
     // When public voting is enabled, a voting token is expected
     pub vote_token: Option<Contract>,
+    // How public voting power is measured, configs stored before this existed are Live
+    #[serde(default)]
+    pub voting_power: VotingPower,
     // When funding is enabled, a funding token is expected
     pub funding_token: Option<Contract>,
 
@@ -49,6 +52,20 @@ pub struct Config {
     pub migrated_to: Option<Contract>,
 }
 
+#[cw_serde]
+pub enum VotingPower {
+    // Trust the balance reported by the vote token when voting
+    Live,
+    // Votes cannot exceed the stake held when the proposal entered voting
+    Snapshot { staking: Contract },
+}
+
+impl Default for VotingPower {
+    fn default() -> Self {
+        Self::Live
+    }
+}
+
 #[cfg(feature = "governance-impl")]
 impl ItemStorage for Config {
     const ITEM: Item<'static, Self, Json> = Item::new("config-");
@@ -83,6 +100,8 @@ pub struct InstantiateMsg {
     // Token rules
     pub funding_token: Option<Contract>,
     pub vote_token: Option<Contract>,
+    // Defaults to live voting
+    pub voting_power: Option<VotingPower>,
 
     // Migration data
     pub migrator: Option<MigrationInit>,
@@ -131,6 +150,7 @@ pub enum ExecuteMsg {
         treasury: Option<Addr>,
         funding_token: Option<Contract>,
         vote_token: Option<Contract>,
+        voting_power: Option<VotingPower>,
         padding: Option<String>,
     },
     SetRuntimeState {
@@ -145,6 +165,16 @@ pub enum ExecuteMsg {
         proposal: u32,
         padding: Option<String>,
     },
+    /// Re-executes the failed msgs of a proposal before its cancel deadline
+    Retry {
+        proposal: u32,
+        padding: Option<String>,
+    },
+    /// Vetoes a queued proposal once enough of the profile's guardian assembly has called it
+    VetoQueued {
+        proposal: u32,
+        padding: Option<String>,
+    },
     /// Cancels the proposal if the msg keeps failing
     Cancel {
         //TODO: Must be deprecated for v1
@@ -176,7 +206,7 @@ pub enum ExecuteMsg {
         vote: Vote,
         padding: Option<String>,
     },
-    /// Votes on voting token
+    /// Votes on voting token, or delegates the balance with a DelegationMsg
     ReceiveBalance {
         sender: Addr,
         msg: Option<Binary>,
@@ -303,7 +333,11 @@ pub enum ExecuteAnswer {
     SetRuntimeState { status: ResponseStatus },
     Proposal { status: ResponseStatus },
     ReceiveBalance { status: ResponseStatus },
+    Delegate { status: ResponseStatus },
+    Undelegate { status: ResponseStatus },
     Trigger { status: ResponseStatus },
+    Retry { status: ResponseStatus },
+    VetoQueued { status: ResponseStatus },
     Cancel { status: ResponseStatus },
     Update { status: ResponseStatus },
     Receive { status: ResponseStatus },
@@ -336,6 +370,8 @@ pub enum AuthQuery {
     AssemblyVotes { pagination: Pagination },
     Funding { pagination: Pagination },
     Votes { pagination: Pagination },
+    Delegations { pagination: Pagination },
+    DelegatedPower {},
 }
 
 #[remain::sorted]
@@ -353,6 +389,13 @@ pub enum QueryMsg {
         end: u32,
     },
 
+    // Proposal summaries matching the filter, continue from the returned cursor
+    FilteredProposals {
+        filter: ProposalFilter,
+        cursor: Option<u32>,
+        limit: u32,
+    },
+
     TotalAssemblies {},
 
     Assemblies {
@@ -413,6 +456,12 @@ pub enum QueryAnswer {
         props: Vec<Proposal>,
     },
 
+    ProposalSummaries {
+        props: Vec<ProposalSummary>,
+        // None once there is nothing left to scan
+        cursor: Option<u32>,
+    },
+
     Assemblies {
         assemblies: Vec<Assembly>,
     },
@@ -452,4 +501,14 @@ pub enum QueryAnswer {
         votes: Vec<ResponseWithID<Vote>>,
         total: u32,
     },
+
+    UserDelegations {
+        delegation: Option<Delegation>,
+        delegators: Vec<Delegator>,
+        total: u32,
+    },
+
+    UserDelegatedPower {
+        power: Uint128,
+    },
 }
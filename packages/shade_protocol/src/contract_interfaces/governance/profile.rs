@@ -1,6 +1,7 @@
 use crate::{
-    c_std::{StdError, StdResult, Storage, Uint128},
+    c_std::{StdError, StdResult, Storage, Uint128, Uint256},
     contract_interfaces::governance::stored_id::ID,
+    utils::{asset::Contract, calc::sqrt},
 };
 
 use cosmwasm_schema::cw_serde;
@@ -26,6 +27,9 @@ pub struct Profile {
     // Require token voting
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<VoteProfile>,
+    // Require a delay before execution
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timelock: Option<TimelockProfile>,
     // Once the contract is approved, theres a deadline for the tx to be executed and completed
     // else it will just be canceled and assume that the tx failed
     pub cancel_deadline: u64,
@@ -45,6 +49,7 @@ impl Profile {
             assembly: Self::assembly_voting(storage, id)?,
             funding: Self::funding(storage, id)?,
             token: Self::public_voting(storage, id)?,
+            timelock: Self::timelock(storage, id)?,
             cancel_deadline: data.cancel_deadline,
         })
     }
@@ -70,6 +75,8 @@ impl Profile {
 
         Self::save_funding(storage, id, self.funding.clone())?;
 
+        Self::save_timelock(storage, id, self.timelock.clone())?;
+
         Ok(())
     }
 
@@ -102,6 +109,9 @@ impl Profile {
         id: u16,
         token: Option<VoteProfile>,
     ) -> StdResult<()> {
+        if let Some(token) = &token {
+            token.validate()?;
+        }
         VoteProfileType(token).save(storage, TOKEN_PROFILE_KEY, id)
     }
 
@@ -116,6 +126,19 @@ impl Profile {
     ) -> StdResult<()> {
         FundProfileType(funding).save(storage, id)
     }
+
+    pub fn timelock(storage: &dyn Storage, id: u16) -> StdResult<Option<TimelockProfile>> {
+        // Profiles created before timelocks existed have nothing stored
+        Ok(TimelockProfileType::may_load(storage, id)?.and_then(|timelock| timelock.0))
+    }
+
+    pub fn save_timelock(
+        storage: &mut dyn Storage,
+        id: u16,
+        timelock: Option<TimelockProfile>,
+    ) -> StdResult<()> {
+        TimelockProfileType(timelock).save(storage, id)
+    }
 }
 
 #[cfg(feature = "governance-impl")]
@@ -142,6 +165,25 @@ pub struct VoteProfile {
     pub yes_threshold: Count,
     // Expected veto votes
     pub veto_threshold: Count,
+    // How token balances translate into voting power
+    #[serde(default)]
+    pub weighting: Weighting,
+}
+
+#[cfg(feature = "governance-impl")]
+impl VoteProfile {
+    /// Participation percentages are taken from the unweighted total supply,
+    /// so they can only be compared against a linear tally
+    pub fn validate(&self) -> StdResult<()> {
+        if let Count::Percentage { .. } = self.threshold {
+            if self.weighting != Weighting::Linear {
+                return Err(StdError::generic_err(
+                    "Percentage threshold requires linear weighting",
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(feature = "governance-impl")]
@@ -173,6 +215,63 @@ impl MapStorage<'static, u16> for FundProfileType {
     const MAP: Map<'static, u16, Self> = Map::new("fund_profile-");
 }
 
+#[cfg(feature = "governance-impl")]
+#[cw_serde]
+pub struct TimelockProfile {
+    // Time a passed proposal must wait before being triggered
+    pub delay: u64,
+    // Assembly allowed to veto proposals while queued
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guardian: Option<u16>,
+    // Guardian members that must veto before a proposal is stopped, defaults to a majority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guardian_threshold: Option<u16>,
+}
+
+#[cfg(feature = "governance-impl")]
+#[cw_serde]
+struct TimelockProfileType(pub Option<TimelockProfile>);
+
+#[cfg(feature = "governance-impl")]
+impl MapStorage<'static, u16> for TimelockProfileType {
+    const MAP: Map<'static, u16, Self> = Map::new("timelock_profile-");
+}
+
+/// Voting power given to a balance, thresholds are compared against the weighted tally
+/// so non linear weightings require a literal participation threshold
+#[cw_serde]
+pub enum Weighting {
+    Linear,
+    // Square root of the balance
+    Quadratic,
+    // Balance is increased by the remaining lockup of the staker's unbondings,
+    // a lockup equal to period doubles the power
+    Conviction { staking: Contract, period: u64 },
+}
+
+impl Default for Weighting {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl Weighting {
+    /// Lockup is the amount of seconds the power remains locked for
+    pub fn weigh(&self, power: Uint128, lockup: u64) -> StdResult<Uint128> {
+        Ok(match self {
+            Weighting::Linear => power,
+            Weighting::Quadratic => Uint128::try_from(sqrt(Uint256::from(power))?)?,
+            Weighting::Conviction { period, .. } => {
+                if *period == 0 {
+                    power
+                } else {
+                    power.checked_add(power.multiply_ratio(lockup, *period))?
+                }
+            }
+        })
+    }
+}
+
 /// Helps simplify the given limits
 #[cw_serde]
 pub enum Count {
@@ -197,6 +296,10 @@ pub struct UpdateProfile {
     pub disable_token: bool,
     // Require token voting
     pub token: Option<UpdateVoteProfile>,
+    // Timelock status
+    pub disable_timelock: bool,
+    // Require a delay before execution
+    pub timelock: Option<UpdateTimelockProfile>,
     // Once the contract is approved, theres a deadline for the tx to be executed and completed
     // else it will just be canceled and assume that the tx failed
     pub cancel_deadline: Option<u64>,
@@ -212,6 +315,8 @@ pub struct UpdateVoteProfile {
     pub yes_threshold: Option<Count>,
     // Expected veto votes
     pub veto_threshold: Option<Count>,
+    // How token balances translate into voting power
+    pub weighting: Option<Weighting>,
 }
 
 impl UpdateVoteProfile {
@@ -230,6 +335,7 @@ impl UpdateVoteProfile {
                     .veto_threshold
                     .clone()
                     .unwrap_or(profile.veto_threshold.clone()),
+                weighting: self.weighting.clone().unwrap_or(profile.weighting.clone()),
             };
         } else {
             new_profile = VoteProfile {
@@ -249,6 +355,7 @@ impl UpdateVoteProfile {
                     None => Err(StdError::generic_err("Vote profile must be set")),
                     Some(ret) => Ok(ret),
                 }?,
+                weighting: self.weighting.clone().unwrap_or_default(),
             };
         }
 
@@ -306,3 +413,38 @@ impl UpdateFundProfile {
         Ok(new_profile)
     }
 }
+
+#[cw_serde]
+pub struct UpdateTimelockProfile {
+    // Time a passed proposal must wait before being triggered
+    pub delay: Option<u64>,
+    // Assembly allowed to veto proposals while queued
+    pub guardian: Option<u16>,
+    // Guardian members that must veto before a proposal is stopped, defaults to a majority
+    pub guardian_threshold: Option<u16>,
+}
+
+impl UpdateTimelockProfile {
+    pub fn update_profile(&self, profile: &Option<TimelockProfile>) -> StdResult<TimelockProfile> {
+        let new_profile: TimelockProfile;
+
+        if let Some(profile) = profile {
+            new_profile = TimelockProfile {
+                delay: self.delay.unwrap_or(profile.delay),
+                guardian: self.guardian.or(profile.guardian),
+                guardian_threshold: self.guardian_threshold.or(profile.guardian_threshold),
+            };
+        } else {
+            new_profile = TimelockProfile {
+                delay: match self.delay {
+                    None => Err(StdError::generic_err("Timelock profile must be set")),
+                    Some(ret) => Ok(ret),
+                }?,
+                guardian: self.guardian,
+                guardian_threshold: self.guardian_threshold,
+            };
+        }
+
+        Ok(new_profile)
+    }
+}
@@ -29,6 +29,10 @@ pub struct Proposal {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub msgs: Option<Vec<ProposalMsg>>,
 
+    // Execution result of each msg
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msg_outcomes: Option<Vec<MsgOutcome>>,
+
     // Assembly
     // Assembly that called the proposal
     pub assembly: u16,
@@ -55,6 +59,19 @@ const ASSEMBLY_VOTE: Map<'static, (u32, Addr), Vote> = Map::new("user-assembly-v
 const ASSEMBLY_VOTES: Map<'static, u32, Vote> = Map::new("total-assembly-votes-");
 const PUBLIC_VOTE: Map<'static, (u32, Addr), Vote> = Map::new("user-public-vote-");
 const PUBLIC_VOTES: Map<'static, u32, Vote> = Map::new("total-public-votes-");
+const PUBLIC_VOTE_POWER: Map<'static, (u32, Addr), Uint128> = Map::new("user-public-vote-power-");
+const DELEGATED_VOTERS: Map<'static, (u32, Addr), Vec<DelegatedVoter>> =
+    Map::new("proposal-delegated-voters-");
+const VOTE_DELEGATE: Map<'static, (u32, Addr), Addr> = Map::new("proposal-vote-delegate-");
+const PROPOSAL_CREATED: Map<'static, u32, u64> = Map::new("proposal_created-");
+const GUARDIAN_VETOES: Map<'static, u32, Vec<Addr>> = Map::new("proposal_guardian_vetoes-");
+// Secondary indices used for filtered queries
+const ASSEMBLY_PROPOSALS_SIZE: Map<'static, u16, u32> = Map::new("assembly_proposals_size-");
+const ASSEMBLY_PROPOSALS: Map<'static, (u16, u32), u32> = Map::new("assembly_proposals-");
+const STATUS_PROPOSALS_SIZE: Map<'static, u8, u32> = Map::new("status_proposals_size-");
+const STATUS_PROPOSALS: Map<'static, (u8, u32), u32> = Map::new("status_proposals-");
+// Latest position of a proposal in a status list, older positions are stale
+const STATUS_PROPOSAL_INDEX: Map<'static, (u8, u32), u32> = Map::new("status_proposal_index-");
 
 #[cfg(feature = "governance-impl")]
 impl Proposal {
@@ -77,6 +94,11 @@ impl Proposal {
 
         Self::save_assembly(storage, id, self.assembly)?;
 
+        Self::add_assembly_proposal(storage, self.assembly, id)?;
+
+        // Proposals are created with a status that starts at creation
+        PROPOSAL_CREATED.save(storage, id, &self.status.start().unwrap_or_default())?;
+
         Self::save_status(storage, id, self.status.clone())?;
 
         Self::save_status_history(storage, id, self.status_history.clone())?;
@@ -126,28 +148,153 @@ impl Proposal {
             }
         }
 
-        let assembly_data = Assembly::data(storage, assembly)?;
+        let (assembly_vote_tally, public_vote_tally) =
+            Self::tallies(storage, id, Assembly::data(storage, assembly)?.profile)?;
+
+        let msg_outcomes = match &msgs {
+            None => None,
+            Some(msgs) => {
+                let mut outcomes = vec![];
+                for i in 0..msgs.len() as u32 {
+                    outcomes.push(Self::msg_outcome(storage, id, i)?);
+                }
+                Some(outcomes)
+            }
+        };
 
         Ok(Self {
             title: description.title,
             proposer: description.proposer,
             metadata: description.metadata,
             msgs,
+            msg_outcomes,
             assembly,
-            assembly_vote_tally: match Profile::assembly_voting(storage, assembly_data.profile)? {
-                None => None,
-                Some(_) => Some(Self::assembly_votes(storage, id)?),
-            },
-            public_vote_tally: match Profile::public_voting(storage, assembly_data.profile)? {
-                None => None,
-                Some(_) => Some(Self::public_votes(storage, id)?),
-            },
+            assembly_vote_tally,
+            public_vote_tally,
             status,
             status_history,
             funders,
         })
     }
 
+    /// Loads the proposal without its msgs, history and funders
+    pub fn summary(storage: &dyn Storage, id: u32) -> StdResult<ProposalSummary> {
+        let description = Self::description(storage, id)?;
+        let assembly = Self::assembly(storage, id)?;
+        let (assembly_vote_tally, public_vote_tally) =
+            Self::tallies(storage, id, Assembly::data(storage, assembly)?.profile)?;
+
+        Ok(ProposalSummary {
+            id,
+            proposer: description.proposer,
+            title: description.title,
+            assembly,
+            status: Self::status(storage, id)?,
+            created: Self::created(storage, id)?,
+            assembly_vote_tally,
+            public_vote_tally,
+        })
+    }
+
+    fn tallies(
+        storage: &dyn Storage,
+        id: u32,
+        profile: u16,
+    ) -> StdResult<(Option<Vote>, Option<Vote>)> {
+        let assembly_vote_tally = match Profile::assembly_voting(storage, profile)? {
+            None => None,
+            Some(_) => Some(Self::assembly_votes(storage, id)?),
+        };
+        let public_vote_tally = match Profile::public_voting(storage, profile)? {
+            None => None,
+            Some(_) => Some(Self::public_votes(storage, id)?),
+        };
+        Ok((assembly_vote_tally, public_vote_tally))
+    }
+
+    pub fn created(storage: &dyn Storage, id: u32) -> StdResult<u64> {
+        if let Some(created) = PROPOSAL_CREATED.may_load(storage, id)? {
+            return Ok(created);
+        }
+
+        // Proposals from before the creation time was stored start with their first status
+        let history = Self::status_history(storage, id)?;
+        Ok(match history.first() {
+            Some(status) => status.start(),
+            None => Self::status(storage, id)?.start(),
+        }
+        .unwrap_or_default())
+    }
+
+    /// Proposals created before the secondary indices existed are missing from them,
+    /// this returns the first proposal that was indexed
+    pub fn first_indexed(storage: &dyn Storage) -> StdResult<u32> {
+        let mut low = 0;
+        let mut high = ID::proposal(storage).map(|id| id + 1).unwrap_or(0);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if PROPOSAL_CREATED.may_load(storage, mid)?.is_none() {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Finds the first proposal created at or after the given time,
+    /// proposal IDs are created in chronological order
+    pub fn first_created_after(storage: &dyn Storage, time: u64) -> StdResult<u32> {
+        let mut low = 0;
+        let mut high = ID::proposal(storage).map(|id| id + 1).unwrap_or(0);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if Self::created(storage, mid)? < time {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    pub fn total_assembly_proposals(storage: &dyn Storage, assembly: u16) -> StdResult<u32> {
+        Ok(ASSEMBLY_PROPOSALS_SIZE
+            .may_load(storage, assembly)?
+            .unwrap_or_default())
+    }
+
+    pub fn assembly_proposal(storage: &dyn Storage, assembly: u16, index: u32) -> StdResult<u32> {
+        ASSEMBLY_PROPOSALS.load(storage, (assembly, index))
+    }
+
+    fn add_assembly_proposal(storage: &mut dyn Storage, assembly: u16, id: u32) -> StdResult<()> {
+        let index = Self::total_assembly_proposals(storage, assembly)?;
+        ASSEMBLY_PROPOSALS.save(storage, (assembly, index), &id)?;
+        ASSEMBLY_PROPOSALS_SIZE.save(storage, assembly, &(index + 1))
+    }
+
+    pub fn total_status_proposals(storage: &dyn Storage, kind: &StatusKind) -> StdResult<u32> {
+        Ok(STATUS_PROPOSALS_SIZE
+            .may_load(storage, kind.key())?
+            .unwrap_or_default())
+    }
+
+    /// Returns None if the proposal has since moved to another status
+    pub fn status_proposal(
+        storage: &dyn Storage,
+        kind: &StatusKind,
+        index: u32,
+    ) -> StdResult<Option<u32>> {
+        let id = STATUS_PROPOSALS.load(storage, (kind.key(), index))?;
+        if STATUS_PROPOSAL_INDEX.may_load(storage, (kind.key(), id))? != Some(index)
+            || &Self::status(storage, id)?.kind() != kind
+        {
+            return Ok(None);
+        }
+        Ok(Some(id))
+    }
+
     pub fn msg(storage: &dyn Storage, id: u32) -> StdResult<Option<Vec<ProposalMsg>>> {
         match ProposalMsgs::may_load(storage, id)? {
             None => Ok(None),
@@ -159,6 +306,19 @@ impl Proposal {
         ProposalMsgs(data).save(storage, id)
     }
 
+    pub fn msg_outcome(storage: &dyn Storage, id: u32, msg_index: u32) -> StdResult<MsgOutcome> {
+        Ok(MsgOutcome::may_load(storage, (id, msg_index))?.unwrap_or(MsgOutcome::Pending))
+    }
+
+    pub fn save_msg_outcome(
+        storage: &mut dyn Storage,
+        id: u32,
+        msg_index: u32,
+        data: MsgOutcome,
+    ) -> StdResult<()> {
+        data.save(storage, (id, msg_index))
+    }
+
     pub fn description(storage: &dyn Storage, id: u32) -> StdResult<ProposalDescription> {
         ProposalDescription::load(storage, id)
     }
@@ -184,6 +344,15 @@ impl Proposal {
     }
 
     pub fn save_status(storage: &mut dyn Storage, id: u32, data: Status) -> StdResult<()> {
+        // Index the proposal under its new status
+        let kind = data.kind();
+        if Status::may_load(storage, id)?.map(|status| status.kind()) != Some(kind.clone()) {
+            let index = Self::total_status_proposals(storage, &kind)?;
+            STATUS_PROPOSALS.save(storage, (kind.key(), index), &id)?;
+            STATUS_PROPOSAL_INDEX.save(storage, (kind.key(), id), &index)?;
+            STATUS_PROPOSALS_SIZE.save(storage, kind.key(), &(index + 1))?;
+        }
+
         data.save(storage, id)
     }
 
@@ -199,6 +368,18 @@ impl Proposal {
         StatusHistory(data).save(storage, id)
     }
 
+    pub fn guardian_vetoes(storage: &dyn Storage, id: u32) -> StdResult<Vec<Addr>> {
+        Ok(GUARDIAN_VETOES.may_load(storage, id)?.unwrap_or_default())
+    }
+
+    pub fn save_guardian_vetoes(
+        storage: &mut dyn Storage,
+        id: u32,
+        data: &Vec<Addr>,
+    ) -> StdResult<()> {
+        GUARDIAN_VETOES.save(storage, id, data)
+    }
+
     pub fn funders(storage: &dyn Storage, id: u32) -> StdResult<Vec<Addr>> {
         let funders = match Funders::may_load(storage, id)? {
             None => vec![],
@@ -264,6 +445,24 @@ impl Proposal {
         data.save(storage, PUBLIC_VOTE, (id, user.clone()))
     }
 
+    // Weighted power the user's vote was counted with
+    pub fn public_vote_power(
+        storage: &dyn Storage,
+        id: u32,
+        user: &Addr,
+    ) -> StdResult<Option<Uint128>> {
+        PUBLIC_VOTE_POWER.may_load(storage, (id, user.clone()))
+    }
+
+    pub fn save_public_vote_power(
+        storage: &mut dyn Storage,
+        id: u32,
+        user: &Addr,
+        power: Uint128,
+    ) -> StdResult<()> {
+        PUBLIC_VOTE_POWER.save(storage, (id, user.clone()), &power)
+    }
+
     // Total public votes
     pub fn public_votes(storage: &dyn Storage, id: u32) -> StdResult<Vote> {
         match Vote::may_load(storage, PUBLIC_VOTES, id)? {
@@ -275,6 +474,85 @@ impl Proposal {
     pub fn save_public_votes(storage: &mut dyn Storage, id: u32, data: &Vote) -> StdResult<()> {
         data.save(storage, PUBLIC_VOTES, id)
     }
+
+    // Delegators whose power was counted in the delegate's vote
+    pub fn delegated_voters(
+        storage: &dyn Storage,
+        id: u32,
+        delegate: &Addr,
+    ) -> StdResult<Vec<DelegatedVoter>> {
+        Ok(DELEGATED_VOTERS
+            .may_load(storage, (id, delegate.clone()))?
+            .unwrap_or_default())
+    }
+
+    pub fn save_delegated_voters(
+        storage: &mut dyn Storage,
+        id: u32,
+        delegate: &Addr,
+        data: &Vec<DelegatedVoter>,
+    ) -> StdResult<()> {
+        DELEGATED_VOTERS.save(storage, (id, delegate.clone()), data)
+    }
+
+    // Delegate that voted with the user's power
+    pub fn vote_delegate(storage: &dyn Storage, id: u32, user: &Addr) -> StdResult<Option<Addr>> {
+        VOTE_DELEGATE.may_load(storage, (id, user.clone()))
+    }
+
+    pub fn save_vote_delegate(
+        storage: &mut dyn Storage,
+        id: u32,
+        user: &Addr,
+        delegate: Option<&Addr>,
+    ) -> StdResult<()> {
+        match delegate {
+            None => VOTE_DELEGATE.remove(storage, (id, user.clone())),
+            Some(delegate) => VOTE_DELEGATE.save(storage, (id, user.clone()), delegate)?,
+        }
+        Ok(())
+    }
+}
+
+#[cw_serde]
+pub struct DelegatedVoter {
+    pub voter: Addr,
+    pub power: Uint128,
+}
+
+impl DelegatedVoter {
+    pub fn total_power(voters: &[DelegatedVoter]) -> StdResult<Uint128> {
+        let mut power = Uint128::zero();
+        for voter in voters.iter() {
+            power = power.checked_add(voter.power)?;
+        }
+        Ok(power)
+    }
+}
+
+#[cw_serde]
+pub struct ProposalSummary {
+    pub id: u32,
+    pub proposer: Addr,
+    pub title: String,
+    pub assembly: u16,
+    pub status: Status,
+    // Time when the proposal was created
+    pub created: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assembly_vote_tally: Option<Vote>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_vote_tally: Option<Vote>,
+}
+
+#[cw_serde]
+pub struct ProposalFilter {
+    pub status: Option<StatusKind>,
+    pub assembly: Option<u16>,
+    pub proposer: Option<Addr>,
+    // Inclusive creation time range
+    pub from: Option<u64>,
+    pub to: Option<u64>,
 }
 
 #[cw_serde]
@@ -301,6 +579,19 @@ pub struct ProposalMsg {
 #[cw_serde]
 struct ProposalMsgs(pub Vec<ProposalMsg>);
 
+#[cw_serde]
+pub enum MsgOutcome {
+    // Msg has not been executed
+    Pending,
+    Success,
+    Failed { error: String },
+}
+
+#[cfg(feature = "governance-impl")]
+impl MapStorage<'static, (u32, u32)> for MsgOutcome {
+    const MAP: Map<'static, (u32, u32), Self> = Map::new("proposal_msg_outcome-");
+}
+
 #[cfg(feature = "governance-impl")]
 impl MapStorage<'static, u32> for ProposalMsgs {
     const MAP: Map<'static, u32, Self> = Map::new("proposal_msgs-");
@@ -341,6 +632,15 @@ pub enum Status {
     Vetoed {
         slash_percent: Uint128,
     },
+    // Proposal was vetoed by the guardian assembly while queued
+    GuardianVetoed {
+        assembly: u16,
+    },
+    // Proposal was approved and must wait for its timelock before being triggered
+    Queued {
+        start: u64,
+        end: u64,
+    },
     // Proposal was approved, has a set timeline before it can be canceled
     Passed {
         start: u64,
@@ -348,6 +648,11 @@ pub enum Status {
     },
     // If proposal is a msg then it was executed and was successful
     Success,
+    // A msg failed when executed, the proposal can be retried until its cancel deadline
+    Failed {
+        msg_index: u32,
+        error: String,
+    },
     // Proposal never got executed after a cancel deadline,
     // assumed that tx failed everytime it got triggered
     Canceled,
@@ -361,6 +666,78 @@ impl Status {
             end: seconds + Profile::data(storage, profile)?.cancel_deadline,
         })
     }
+
+    /// Queues the approved proposal if its profile has a timelock, else it passes
+    pub fn approved(storage: &dyn Storage, profile: u16, time: &Timestamp) -> StdResult<Status> {
+        let seconds = time.seconds();
+        match Profile::timelock(storage, profile)? {
+            Some(timelock) if timelock.delay > 0 => Ok(Self::Queued {
+                start: seconds,
+                end: seconds + timelock.delay,
+            }),
+            _ => Self::passed(storage, profile, time),
+        }
+    }
+
+    pub fn kind(&self) -> StatusKind {
+        match self {
+            Status::AssemblyVote { .. } => StatusKind::AssemblyVote,
+            Status::Funding { .. } => StatusKind::Funding,
+            Status::Voting { .. } => StatusKind::Voting,
+            Status::Expired => StatusKind::Expired,
+            Status::Rejected => StatusKind::Rejected,
+            Status::Vetoed { .. } => StatusKind::Vetoed,
+            Status::GuardianVetoed { .. } => StatusKind::GuardianVetoed,
+            Status::Queued { .. } => StatusKind::Queued,
+            Status::Passed { .. } => StatusKind::Passed,
+            Status::Success => StatusKind::Success,
+            Status::Failed { .. } => StatusKind::Failed,
+            Status::Canceled => StatusKind::Canceled,
+        }
+    }
+
+    /// Start of the status period, if the status has one
+    pub fn start(&self) -> Option<u64> {
+        match self {
+            Status::AssemblyVote { start, .. }
+            | Status::Funding { start, .. }
+            | Status::Voting { start, .. }
+            | Status::Queued { start, .. }
+            | Status::Passed { start, .. } => Some(*start),
+            _ => None,
+        }
+    }
+
+    /// Finds the cancel deadline of the last time the proposal passed
+    pub fn cancel_deadline(history: &[Status]) -> Option<u64> {
+        history.iter().rev().find_map(|status| match status {
+            Status::Passed { end, .. } => Some(*end),
+            _ => None,
+        })
+    }
+}
+
+// Status variant without its data, used for filtering
+#[cw_serde]
+pub enum StatusKind {
+    AssemblyVote,
+    Funding,
+    Voting,
+    Expired,
+    Rejected,
+    Vetoed,
+    GuardianVetoed,
+    Queued,
+    Passed,
+    Success,
+    Failed,
+    Canceled,
+}
+
+impl StatusKind {
+    pub fn key(&self) -> u8 {
+        self.clone() as u8
+    }
 }
 
 #[cfg(feature = "governance-impl")]
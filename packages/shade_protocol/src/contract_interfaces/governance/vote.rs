@@ -1,14 +1,35 @@
-use crate::c_std::{StdResult, Uint128};
+use crate::{
+    basic_staking::Auth,
+    c_std::{Addr, StdResult, Storage, Uint128},
+};
 
 use cosmwasm_schema::cw_serde;
+use secret_storage_plus::Map;
 
 #[cfg(feature = "governance-impl")]
-use crate::utils::storage::plus::NaiveMapStorage;
+use crate::utils::storage::plus::{MapStorage, NaiveMapStorage};
 
 #[cw_serde]
 pub struct ReceiveBalanceMsg {
     pub vote: Vote,
     pub proposal: u32,
+    // Required when voting power is snapshotted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Auth>,
+}
+
+/// Sent as the ReceiveBalance msg through the vote token instead of a vote,
+/// these are not part of ExecuteMsg. Only accepted when voting power is snapshotted
+#[cw_serde]
+pub enum DelegationMsg {
+    Delegate {
+        to: Addr,
+        // Used to look up the delegator's stake whenever a proposal is tallied,
+        // delegators must delegate again after changing their viewing key
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        auth: Option<Auth>,
+    },
+    Undelegate {},
 }
 
 #[cw_serde]
@@ -58,22 +79,83 @@ impl Vote {
             abstain: self.abstain.checked_add(vote.abstain)?,
         })
     }
+
+    /// Splits the given power with the same proportions as this vote
+    pub fn scale(&self, power: Uint128) -> StdResult<Self> {
+        let total = self.total_count()?;
+        if total.is_zero() {
+            return Ok(Self::default());
+        }
+
+        Ok(Self {
+            yes: self.yes.multiply_ratio(power, total),
+            no: self.no.multiply_ratio(power, total),
+            no_with_veto: self.no_with_veto.multiply_ratio(power, total),
+            abstain: self.abstain.multiply_ratio(power, total),
+        })
+    }
 }
 
-pub struct TalliedVotes {
-    pub yes: Uint128,
-    pub no: Uint128,
-    pub veto: Uint128,
-    pub total: Uint128,
+#[cw_serde]
+pub struct Delegation {
+    pub to: Addr,
 }
 
-impl TalliedVotes {
-    pub fn tally(votes: Vote) -> Self {
-        Self {
-            yes: votes.yes,
-            no: votes.no + votes.no_with_veto,
-            veto: votes.no_with_veto,
-            total: votes.yes + votes.no + votes.no_with_veto + votes.abstain,
+#[cw_serde]
+pub struct Delegator {
+    pub address: Addr,
+    // Currently staked balance, missing when the delegator's auth stopped working
+    pub power: Option<Uint128>,
+}
+
+/// Every delegator is visited when their delegate votes
+pub const MAX_DELEGATORS: usize = 50;
+
+const DELEGATORS: Map<'static, (Addr, Addr), Vec<Addr>> = Map::new("vote_delegators-");
+// Kept apart from the delegation so it is never returned by queries
+const DELEGATION_AUTH: Map<'static, (Addr, Addr), Auth> = Map::new("vote_delegation_auth-");
+
+#[cfg(feature = "governance-impl")]
+impl MapStorage<'static, (Addr, Addr)> for Delegation {
+    const MAP: Map<'static, (Addr, Addr), Self> = Map::new("vote_delegation-");
+}
+
+/// Delegations are stored per vote token so changing it resets them
+#[cfg(feature = "governance-impl")]
+impl Delegation {
+    pub fn delegators(
+        storage: &dyn Storage,
+        token: &Addr,
+        delegate: &Addr,
+    ) -> StdResult<Vec<Addr>> {
+        Ok(DELEGATORS
+            .may_load(storage, (token.clone(), delegate.clone()))?
+            .unwrap_or_default())
+    }
+
+    pub fn save_delegators(
+        storage: &mut dyn Storage,
+        token: &Addr,
+        delegate: &Addr,
+        delegators: &Vec<Addr>,
+    ) -> StdResult<()> {
+        DELEGATORS.save(storage, (token.clone(), delegate.clone()), delegators)
+    }
+
+    pub fn auth(storage: &dyn Storage, token: &Addr, delegator: &Addr) -> StdResult<Option<Auth>> {
+        DELEGATION_AUTH.may_load(storage, (token.clone(), delegator.clone()))
+    }
+
+    pub fn save_auth(
+        storage: &mut dyn Storage,
+        token: &Addr,
+        delegator: &Addr,
+        auth: Option<&Auth>,
+    ) -> StdResult<()> {
+        match auth {
+            None => DELEGATION_AUTH.remove(storage, (token.clone(), delegator.clone())),
+            Some(auth) => DELEGATION_AUTH.save(storage, (token.clone(), delegator.clone()), auth)?,
         }
+        Ok(())
     }
 }
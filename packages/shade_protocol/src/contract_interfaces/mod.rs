@@ -6,6 +6,9 @@ pub mod dao;
 
 pub mod oracles;
 
+#[cfg(feature = "shade_oracles")]
+pub mod shade_oracles;
+
 #[cfg(feature = "mint")]
 pub mod mint;
 
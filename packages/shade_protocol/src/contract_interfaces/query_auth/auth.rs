@@ -29,6 +29,25 @@ impl Key {
         Self(base64::encode(key))
     }
 
+    /// Checks the main key, then the restricted keys which allow the contract at this time.
+    pub fn verify_restricted(
+        storage: &dyn Storage,
+        address: Addr,
+        key: String,
+        now: u64,
+        contract: Option<&Addr>,
+    ) -> StdResult<bool> {
+        if Key::verify(storage, address.clone(), key.clone())? {
+            return Ok(true);
+        }
+        Ok(match RestrictedKeys::may_load(storage, address)? {
+            None => false,
+            Some(keys) => keys.0.iter().any(|restricted| {
+                Key(key.clone()).compare(&restricted.hash) && restricted.allows(now, contract)
+            }),
+        })
+    }
+
     pub fn verify(storage: &dyn Storage, address: Addr, key: String) -> StdResult<bool> {
         Ok(match HashedKey::may_load(storage, address)? {
             None => {
@@ -56,6 +75,60 @@ impl MapStorage<'static, Addr> for HashedKey {
     const MAP: Map<'static, Addr, Self> = Map::new("hashed-viewing-key-");
 }
 
+pub const MAX_RESTRICTED_KEYS: usize = 10;
+
+/// Viewing key which expires and/or only works for some contracts.
+#[cw_serde]
+pub struct RestrictedKey {
+    pub hash: [u8; KEY_SIZE],
+    pub expires_at: Option<u64>,
+    pub contracts: Option<Vec<Addr>>,
+}
+
+impl RestrictedKey {
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.map_or(false, |expires_at| now >= expires_at)
+    }
+
+    pub fn allows(&self, now: u64, contract: Option<&Addr>) -> bool {
+        !self.is_expired(now)
+            && match &self.contracts {
+                None => true,
+                Some(contracts) => contract.map_or(false, |contract| contracts.contains(contract)),
+            }
+    }
+}
+
+#[cw_serde]
+pub struct RestrictedKeys(pub Vec<RestrictedKey>);
+
+impl MapStorage<'static, Addr> for RestrictedKeys {
+    const MAP: Map<'static, Addr, Self> = Map::new("restricted-viewing-keys-");
+}
+
+
+/// Incremented when the user rotates their keys.
+#[cw_serde]
+pub struct KeyEpoch(pub u64);
+
+impl MapStorage<'static, Addr> for KeyEpoch {
+    const MAP: Map<'static, Addr, Self> = Map::new("key-epoch-");
+}
+
+impl KeyEpoch {
+    pub fn current(storage: &dyn Storage, user: Addr) -> StdResult<u64> {
+        Ok(KeyEpoch::may_load(storage, user)?.map_or(0, |epoch| epoch.0))
+    }
+
+    /// Drops the user's viewing keys and revokes permits signed for older epochs.
+    pub fn rotate(storage: &mut dyn Storage, user: Addr) -> StdResult<u64> {
+        let epoch = KeyEpoch::current(storage, user.clone())? + 1;
+        KeyEpoch(epoch).save(storage, user.clone())?;
+        HashedKey::remove(storage, user.clone());
+        RestrictedKeys::remove(storage, user);
+        Ok(epoch)
+    }
+}
 
 #[cw_serde]
 pub struct PermitKey(pub bool);
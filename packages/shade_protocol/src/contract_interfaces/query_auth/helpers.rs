@@ -1,7 +1,7 @@
-use cosmwasm_std::{Addr, from_binary, QuerierWrapper, StdError, StdResult};
+use cosmwasm_std::{Addr, Env, from_binary, QuerierWrapper, StdError, StdResult};
 use serde::de::DeserializeOwned;
 use crate::{Contract, query_auth};
-use crate::query_auth::QueryPermit;
+use crate::query_auth::{QueryPermit, ViewingKeyCheck};
 use crate::utils::Query;
 
 pub struct PermitAuthentication<T: DeserializeOwned> {
@@ -36,15 +36,18 @@ pub fn authenticate_permit<T: DeserializeOwned>(
     })
 }
 
+/// The calling contract is sent along so keys limited to other contracts are rejected.
 pub fn authenticate_vk(
     address: Addr,
     key: String,
     querier: &QuerierWrapper,
+    env: &Env,
     authenticator: &Contract
 ) -> StdResult<bool> {
     let res: query_auth::QueryAnswer = query_auth::QueryMsg::ValidateViewingKey {
         user: address,
         key,
+        contract: Some(env.contract.address.clone()),
     }.query(querier, authenticator)?;
 
     match res {
@@ -53,4 +56,25 @@ pub fn authenticate_vk(
         }
         _ => Err(StdError::generic_err("Unauthorized")),
     }
-}
\ No newline at end of file
+}
+
+/// Validates many viewing keys in a single query, results keep the order of the checks.
+pub fn authenticate_vks(
+    checks: Vec<ViewingKeyCheck>,
+    querier: &QuerierWrapper,
+    env: &Env,
+    authenticator: &Contract
+) -> StdResult<Vec<bool>> {
+    let res: query_auth::QueryAnswer = query_auth::QueryMsg::BatchValidate {
+        viewing_keys: checks,
+        permits: vec![],
+        contract: Some(env.contract.address.clone()),
+    }.query(querier, authenticator)?;
+
+    match res {
+        query_auth::QueryAnswer::BatchValidate { viewing_keys, .. } => {
+            Ok(viewing_keys)
+        }
+        _ => Err(StdError::generic_err("Unauthorized")),
+    }
+}
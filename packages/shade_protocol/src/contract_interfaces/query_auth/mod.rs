@@ -80,12 +80,26 @@ pub enum ExecuteMsg {
         padding: Option<String>,
     },
 
+    // Keys with an expiration or contracts are added next to the main key instead of replacing it
     SetViewingKey {
         key: String,
+        expires_at: Option<u64>,
+        contracts: Option<Vec<String>>,
         padding: Option<String>,
     },
     CreateViewingKey {
         entropy: String,
+        expires_at: Option<u64>,
+        contracts: Option<Vec<String>>,
+        padding: Option<String>,
+    },
+    // Removes a key with an expiration or contracts
+    RevokeViewingKey {
+        key: String,
+        padding: Option<String>,
+    },
+    // Invalidates every viewing key and every permit signed for an older epoch
+    RotateAll {
         padding: Option<String>,
     },
 
@@ -105,6 +119,8 @@ pub enum ExecuteAnswer {
     SetRunState { status: ResponseStatus },
     SetViewingKey { status: ResponseStatus },
     CreateViewingKey { key: String },
+    RevokeViewingKey { status: ResponseStatus },
+    RotateAll { epoch: u64 },
     BlockPermitKey { status: ResponseStatus },
 }
 
@@ -114,15 +130,44 @@ pub type QueryPermit = Permit<PermitData>;
 #[cw_serde]
 pub struct PermitData {
     pub data: Binary,
+    // Key epoch the permit was signed for, permits from older epochs are revoked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub epoch: Option<u64>,
+    pub key: String,
+}
+
+pub const MAX_BATCH_SIZE: usize = 50;
+
+#[cw_serde]
+pub struct ViewingKeyCheck {
+    pub user: Addr,
     pub key: String,
 }
 
+#[cw_serde]
+pub struct PermitValidation {
+    pub user: Addr,
+    pub is_revoked: bool,
+}
+
 #[cw_serde]
 pub enum QueryMsg {
     Config {},
 
-    ValidateViewingKey { user: Addr, key: String },
+    // Contract is the one asking, keys limited to other contracts are rejected
+    ValidateViewingKey {
+        user: Addr,
+        key: String,
+        contract: Option<Addr>,
+    },
     ValidatePermit { permit: QueryPermit },
+    // Validates up to MAX_BATCH_SIZE keys and permits, results keep the request order
+    BatchValidate {
+        viewing_keys: Vec<ViewingKeyCheck>,
+        permits: Vec<QueryPermit>,
+        contract: Option<Addr>,
+    },
+    KeyEpoch { user: Addr },
 }
 
 impl Query for QueryMsg {
@@ -142,4 +187,12 @@ pub enum QueryAnswer {
         user: Addr,
         is_revoked: bool,
     },
+    BatchValidate {
+        viewing_keys: Vec<bool>,
+        // None when the permit could not be validated
+        permits: Vec<Option<PermitValidation>>,
+    },
+    KeyEpoch {
+        epoch: u64,
+    },
 }
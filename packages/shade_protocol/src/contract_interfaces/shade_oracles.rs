@@ -1,13 +1,21 @@
 //#! 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{StdResult, QuerierWrapper};
+use crate::c_std::{QuerierWrapper, StdResult, Uint128};
 use crate::{
-    Contract,
+    utils::{asset::Contract, Query},
     BLOCK_SIZE,
-    utils::{Query},
 };
 use std::collections::HashMap;
 
+/// Price data as reported by a shade oracle, `rate` is normalized to 18 decimals
+#[cw_serde]
+#[derive(Default)]
+pub struct ReferenceData {
+    pub rate: Uint128,
+    pub last_updated_base: u64,
+    pub last_updated_quote: u64,
+}
+
 #[cw_serde]
 #[derive(Default)]
 pub struct OraclePrice {
@@ -15,8 +23,15 @@ pub struct OraclePrice {
     pub data: ReferenceData,
 }
 
-pub type PriceResponse = OraclePrice;
-pub type PricesResponse = Vec<OraclePrice>;
+#[cw_serde]
+pub struct PriceResponse {
+    pub price: OraclePrice,
+}
+
+#[cw_serde]
+pub struct PricesResponse {
+    pub prices: Vec<OraclePrice>,
+}
 
 #[cw_serde]
 pub enum RouterQueryMsg {
@@ -8,6 +8,7 @@ pub struct TransferAction {
     pub recipient: String,
     pub amount: Uint128,
     pub memo: Option<String>,
+    pub decoys: Option<Vec<String>>,
 }
 
 #[cw_serde]
@@ -17,6 +18,7 @@ pub struct SendAction {
     pub amount: Uint128,
     pub msg: Option<Binary>,
     pub memo: Option<String>,
+    pub decoys: Option<Vec<String>>,
 }
 
 #[cw_serde]
@@ -42,6 +44,7 @@ pub struct MintAction {
     pub recipient: String,
     pub amount: Uint128,
     pub memo: Option<String>,
+    pub decoys: Option<Vec<String>>,
 }
 
 #[cw_serde]
@@ -48,6 +48,16 @@ pub enum Error {
     ContractStatusLevelInvalidConversion,
     TxCodeInvalidConversion,
     LegacyCannotConvertFromTx,
+
+    // Notification errors
+    ChannelNotFound,
+    VestingLocked,
+    InvalidVestingSchedule,
+    TooManyVestingGrants,
+    VestingGrantNotFound,
+    MaxSupplyExceeded,
+    MintAllowanceExceeded,
+    InvalidHistoryRetention,
 }
 
 impl_into_u8!(Error);
@@ -81,6 +91,14 @@ impl CodeType for Error {
             Error::ContractStatusLevelInvalidConversion => build_string("Stored enum id {} is greater than total supported enum items", context),
             Error::TxCodeInvalidConversion => build_string("Stored action id {} is greater than total supported enum items", context),
             Error::LegacyCannotConvertFromTx => build_string("Legacy Txs only supports Transfer", context),
+            Error::ChannelNotFound => build_string("Notification channel {} does not exist", context),
+            Error::VestingLocked => build_string("Account has {} tokens that are still vesting", context),
+            Error::InvalidVestingSchedule => build_string("Vesting schedule is invalid, {}", context),
+            Error::TooManyVestingGrants => build_string("Account already has {} vesting grants", context),
+            Error::VestingGrantNotFound => build_string("No vesting grant at index {}", context),
+            Error::MaxSupplyExceeded => build_string("Minting would exceed the max supply of {}", context),
+            Error::MintAllowanceExceeded => build_string("Minter can only mint {} more this cycle", context),
+            Error::InvalidHistoryRetention => build_string("History retention is invalid, {}", context),
         }
     }
 }
@@ -123,6 +141,40 @@ pub fn permit_not_found() -> StdError {
     DetailedError::from_code(TARGET, Error::PermitNotFound, vec![]).to_error()
 }
 
+pub fn channel_not_found(channel: &str) -> StdError {
+    DetailedError::from_code(TARGET, Error::ChannelNotFound, vec![channel]).to_error()
+}
+
+pub fn vesting_locked(locked: Uint128) -> StdError {
+    DetailedError::from_code(TARGET, Error::VestingLocked, vec![&locked.to_string()]).to_error()
+}
+
+pub fn invalid_vesting_schedule(reason: &str) -> StdError {
+    DetailedError::from_code(TARGET, Error::InvalidVestingSchedule, vec![reason]).to_error()
+}
+
+pub fn too_many_vesting_grants(max: usize) -> StdError {
+    DetailedError::from_code(TARGET, Error::TooManyVestingGrants, vec![&max.to_string()]).to_error()
+}
+
+pub fn vesting_grant_not_found(index: u32) -> StdError {
+    DetailedError::from_code(TARGET, Error::VestingGrantNotFound, vec![&index.to_string()])
+        .to_error()
+}
+
+pub fn max_supply_exceeded(max: Uint128) -> StdError {
+    DetailedError::from_code(TARGET, Error::MaxSupplyExceeded, vec![&max.to_string()]).to_error()
+}
+
+pub fn mint_allowance_exceeded(remaining: Uint128) -> StdError {
+    DetailedError::from_code(TARGET, Error::MintAllowanceExceeded, vec![&remaining.to_string()])
+        .to_error()
+}
+
+pub fn invalid_history_retention(reason: &str) -> StdError {
+    DetailedError::from_code(TARGET, Error::InvalidHistoryRetention, vec![reason]).to_error()
+}
+
 pub fn unauthorized_permit(auth: Permission) -> StdError {
     let perm = match auth {
         Permission::Allowance => String::from("allowance"),
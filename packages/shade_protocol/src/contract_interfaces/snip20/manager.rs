@@ -2,17 +2,28 @@ use crate::c_std::{Addr, BlockInfo, StdResult, Storage};
 use cosmwasm_std::Timestamp;
 
 #[cfg(feature = "snip20-impl")]
-use crate::utils::storage::plus::{ItemStorage, MapStorage, NaiveItemStorage};
+use crate::utils::{
+    crypto::Prng,
+    cycle::{exceeds_cycle, parse_utc_datetime, utc_from_timestamp},
+    storage::plus::{ItemStorage, MapStorage, NaiveItemStorage},
+};
 use crate::{
     c_std::Uint128,
     contract_interfaces::snip20::errors::{
         allowance_expired,
         contract_status_level_invalid,
         insufficient_allowance,
+        invalid_vesting_schedule,
+        max_supply_exceeded,
+        mint_allowance_exceeded,
         no_funds,
         not_enough_funds,
+        too_many_vesting_grants,
+        vesting_grant_not_found,
+        vesting_locked,
     },
     impl_into_u8,
+    utils::cycle::Cycle,
     Contract,
 };
 use cosmwasm_schema::cw_serde;
@@ -239,7 +250,13 @@ impl Balance {
         Ok(supply)
     }
 
-    pub fn sub(storage: &mut dyn Storage, amount: Uint128, addr: &Addr) -> StdResult<Uint128> {
+    /// Fails if it would dip into tokens that are still vesting
+    pub fn sub(
+        storage: &mut dyn Storage,
+        amount: Uint128,
+        addr: &Addr,
+        block: &BlockInfo,
+    ) -> StdResult<Uint128> {
         let subtractee = match Self::load(storage, addr.clone()) {
             Ok(amount) => amount.0,
             Err(_) => return Err(no_funds()),
@@ -248,22 +265,222 @@ impl Balance {
             Ok(supply) => supply,
             Err(_) => return Err(not_enough_funds()),
         };
+        let locked = VestingGrants::locked(storage, addr, block)?;
+        if supply < locked {
+            return Err(vesting_locked(locked));
+        }
         Balance::set(storage, supply, addr)?;
         Ok(supply)
     }
 
+    /// Saves the decoy balances back unchanged
+    fn rewrite(storage: &mut dyn Storage, accounts: &[Addr]) -> StdResult<()> {
+        for account in accounts {
+            Self::may_load(storage, account.clone())?
+                .unwrap_or(Self(Uint128::zero()))
+                .save(storage, account.clone())?;
+        }
+        Ok(())
+    }
+
+    pub fn add_with_decoys(
+        storage: &mut dyn Storage,
+        amount: Uint128,
+        addr: &Addr,
+        decoys: &Decoys,
+    ) -> StdResult<Uint128> {
+        let (before, after) = decoys.split();
+        Self::rewrite(storage, before)?;
+        let supply = Self::add(storage, amount, addr)?;
+        Self::rewrite(storage, after)?;
+        Ok(supply)
+    }
+
+    pub fn sub_with_decoys(
+        storage: &mut dyn Storage,
+        amount: Uint128,
+        addr: &Addr,
+        decoys: &Decoys,
+        block: &BlockInfo,
+    ) -> StdResult<Uint128> {
+        let (before, after) = decoys.split();
+        Self::rewrite(storage, before)?;
+        let supply = Self::sub(storage, amount, addr, block)?;
+        Self::rewrite(storage, after)?;
+        Ok(supply)
+    }
+
+    /// Decoys hide the recipient, the sender is already public as the signer
     pub fn transfer(
         storage: &mut dyn Storage,
         amount: Uint128,
         sender: &Addr,
         recipient: &Addr,
+        decoys: &Decoys,
+        block: &BlockInfo,
     ) -> StdResult<()> {
-        Self::sub(storage, amount, sender)?;
-        Self::add(storage, amount, recipient)?;
+        Self::sub(storage, amount, sender, block)?;
+        Self::add_with_decoys(storage, amount, recipient, decoys)?;
         Ok(())
     }
 }
 
+/// Accounts whose balances get rewritten next to a real balance update,
+/// the real account is written at `position` among them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Decoys {
+    pub accounts: Vec<Addr>,
+    pub position: usize,
+}
+
+#[cfg(feature = "snip20-impl")]
+impl Decoys {
+    pub fn new(accounts: Vec<Addr>, rng: &mut Prng) -> Self {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&rng.rand_bytes()[..8]);
+        let position = (u64::from_be_bytes(bytes) % (accounts.len() as u64 + 1)) as usize;
+
+        Self { accounts, position }
+    }
+
+    /// Seeds the placement with the block, the sender and the user supplied entropy
+    pub fn rng(seed: &[u8], block: &BlockInfo, sender: &Addr, entropy: &[u8]) -> Prng {
+        let mut rng_entropy = Vec::with_capacity(16 + sender.as_str().len() + entropy.len());
+        rng_entropy.extend_from_slice(&block.height.to_be_bytes());
+        rng_entropy.extend_from_slice(&block.time.seconds().to_be_bytes());
+        rng_entropy.extend_from_slice(sender.as_bytes());
+        rng_entropy.extend_from_slice(entropy);
+
+        Prng::new(seed, &rng_entropy)
+    }
+
+    fn split(&self) -> (&[Addr], &[Addr]) {
+        self.accounts.split_at(self.position.min(self.accounts.len()))
+    }
+}
+
+/// Times are in seconds
+#[cw_serde]
+pub enum VestingSchedule {
+    /// Released linearly from start until end
+    Linear { start: u64, end: u64 },
+    /// Released all at once
+    Cliff { release: u64 },
+}
+
+impl VestingSchedule {
+    pub fn validate(&self) -> StdResult<()> {
+        match self {
+            VestingSchedule::Linear { start, end } if end <= start => {
+                Err(invalid_vesting_schedule("end must be after start"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Portion of amount that is still locked at the given time
+    pub fn locked(&self, amount: Uint128, now: u64) -> Uint128 {
+        match self {
+            VestingSchedule::Linear { start, end } => {
+                if now <= *start {
+                    amount
+                } else if now >= *end {
+                    Uint128::zero()
+                } else {
+                    amount.multiply_ratio(end - now, end - start)
+                }
+            }
+            VestingSchedule::Cliff { release } => {
+                if now < *release {
+                    amount
+                } else {
+                    Uint128::zero()
+                }
+            }
+        }
+    }
+}
+
+#[cw_serde]
+pub struct VestingGrant {
+    pub sender: Addr,
+    pub amount: Uint128,
+    pub schedule: VestingSchedule,
+}
+
+// Every grant is checked whenever the account spends, so keep the list short
+pub const MAX_VESTING_GRANTS: usize = 10;
+
+#[cw_serde]
+pub struct VestingGrants(pub Vec<VestingGrant>);
+
+#[cfg(feature = "snip20-impl")]
+impl VestingGrants {
+    pub fn load_or_default(storage: &dyn Storage, addr: &Addr) -> StdResult<Self> {
+        Ok(Self::may_load(storage, addr.clone())?.unwrap_or(Self(vec![])))
+    }
+
+    pub fn locked(storage: &dyn Storage, addr: &Addr, block: &BlockInfo) -> StdResult<Uint128> {
+        let now = block.time.seconds();
+        let mut locked = Uint128::zero();
+        for grant in Self::load_or_default(storage, addr)?.0 {
+            locked = locked.checked_add(grant.schedule.locked(grant.amount, now))?;
+        }
+        Ok(locked)
+    }
+
+    /// Adds a grant, dropping the ones that have fully vested
+    pub fn grant(
+        storage: &mut dyn Storage,
+        addr: &Addr,
+        grant: VestingGrant,
+        block: &BlockInfo,
+    ) -> StdResult<()> {
+        if grant.amount.is_zero() {
+            return Err(invalid_vesting_schedule("amount cannot be zero"));
+        }
+        grant.schedule.validate()?;
+
+        let now = block.time.seconds();
+        let mut grants = Self::load_or_default(storage, addr)?;
+        grants
+            .0
+            .retain(|grant| !grant.schedule.locked(grant.amount, now).is_zero());
+
+        if grants.0.len() >= MAX_VESTING_GRANTS {
+            return Err(too_many_vesting_grants(MAX_VESTING_GRANTS));
+        }
+
+        grants.0.push(grant);
+        grants.save(storage, addr.clone())
+    }
+
+    /// Removes a grant the account did not want, returns it along with how much of it was
+    /// still locked so that can be given back to the grantor
+    pub fn reject(
+        storage: &mut dyn Storage,
+        addr: &Addr,
+        index: u32,
+        block: &BlockInfo,
+    ) -> StdResult<(VestingGrant, Uint128)> {
+        let mut grants = Self::load_or_default(storage, addr)?;
+        if index as usize >= grants.0.len() {
+            return Err(vesting_grant_not_found(index));
+        }
+
+        let grant = grants.0.remove(index as usize);
+        grants.save(storage, addr.clone())?;
+
+        let locked = grant.schedule.locked(grant.amount, block.time.seconds());
+        Ok((grant, locked))
+    }
+}
+
+#[cfg(feature = "snip20-impl")]
+impl MapStorage<'static, Addr> for VestingGrants {
+    const MAP: Map<'static, Addr, Self> = Map::new("vesting-grants-");
+}
+
 #[cw_serde]
 pub struct Minters(pub Vec<Addr>);
 
@@ -272,6 +489,96 @@ impl ItemStorage for Minters {
     const ITEM: Item<'static, Self> = Item::new("minters-");
 }
 
+#[cw_serde]
+pub struct MaxSupply(pub Uint128);
+
+#[cfg(feature = "snip20-impl")]
+impl ItemStorage for MaxSupply {
+    const ITEM: Item<'static, Self> = Item::new("max-supply-");
+}
+
+#[cfg(feature = "snip20-impl")]
+impl MaxSupply {
+    pub fn check(storage: &dyn Storage, supply: Uint128) -> StdResult<()> {
+        match Self::may_load(storage)? {
+            Some(max) if supply > max.0 => Err(max_supply_exceeded(max.0)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Most a minter can mint within each cycle
+#[cw_serde]
+pub struct MintLimit {
+    pub amount: Uint128,
+    pub cycle: Cycle,
+}
+
+#[cw_serde]
+pub struct MinterAllowanceResult {
+    pub minter: Addr,
+    /// None when the minter is unlimited
+    pub limit: Option<MintLimit>,
+    pub remaining: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct MinterAllowance {
+    pub limit: MintLimit,
+    /// Minted since the last refresh
+    pub minted: Uint128,
+    pub last_refresh: String,
+}
+
+#[cfg(feature = "snip20-impl")]
+impl MinterAllowance {
+    pub fn new(limit: MintLimit, block: &BlockInfo) -> Self {
+        Self {
+            limit,
+            minted: Uint128::zero(),
+            last_refresh: utc_from_timestamp(block.time).to_rfc3339(),
+        }
+    }
+
+    /// Starts a new window once the cycle has passed
+    pub fn refresh(&mut self, block: &BlockInfo) -> StdResult<()> {
+        let now = utc_from_timestamp(block.time);
+        let last_refresh = parse_utc_datetime(&self.last_refresh)?;
+        if exceeds_cycle(&now, &last_refresh, self.limit.cycle.clone()) {
+            self.minted = Uint128::zero();
+            self.last_refresh = now.to_rfc3339();
+        }
+        Ok(())
+    }
+
+    pub fn remaining(&self) -> Uint128 {
+        self.limit.amount.saturating_sub(self.minted)
+    }
+
+    /// Minters without an allowance are unlimited
+    pub fn spend(
+        storage: &mut dyn Storage,
+        minter: &Addr,
+        amount: Uint128,
+        block: &BlockInfo,
+    ) -> StdResult<()> {
+        if let Some(mut allowance) = Self::may_load(storage, minter.clone())? {
+            allowance.refresh(block)?;
+            if amount > allowance.remaining() {
+                return Err(mint_allowance_exceeded(allowance.remaining()));
+            }
+            allowance.minted += amount;
+            allowance.save(storage, minter.clone())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "snip20-impl")]
+impl MapStorage<'static, Addr> for MinterAllowance {
+    const MAP: Map<'static, Addr, Self> = Map::new("minter-allowance-");
+}
+
 #[cw_serde]
 pub struct AllowanceResponse {
     pub spender: Addr,
@@ -280,6 +587,20 @@ pub struct AllowanceResponse {
     pub expiration: Option<u64>,
 }
 
+#[cw_serde]
+pub struct AllowanceGivenResult {
+    pub spender: Addr,
+    pub allowance: Uint128,
+    pub expiration: Option<u64>,
+}
+
+#[cw_serde]
+pub struct AllowanceReceivedResult {
+    pub owner: Addr,
+    pub allowance: Uint128,
+    pub expiration: Option<u64>,
+}
+
 #[cw_serde]
 pub struct Allowance {
     pub amount: Uint128,
@@ -322,10 +643,58 @@ impl Allowance {
             return Err(insufficient_allowance());
         }
 
-        allowance.save(storage, (owner.clone(), spender.clone()))?;
+        allowance.set(storage, owner, spender)
+    }
 
+    /// Saves the allowance and keeps the owner and spender indexes in sync,
+    /// the indexes are only written when an allowance becomes empty or non empty.
+    /// Allowances last set before the indexes existed are added once they change.
+    pub fn set(&self, storage: &mut dyn Storage, owner: &Addr, spender: &Addr) -> StdResult<()> {
+        self.save(storage, (owner.clone(), spender.clone()))?;
+
+        let indexed = ALLOWANCE_SPENDERS.contains(storage, owner, spender)?;
+        if self.amount.is_zero() && indexed {
+            ALLOWANCE_SPENDERS.remove(storage, owner, spender)?;
+            ALLOWANCE_OWNERS.remove(storage, spender, owner)?;
+        } else if !self.amount.is_zero() && !indexed {
+            ALLOWANCE_SPENDERS.insert(storage, owner, spender)?;
+            ALLOWANCE_OWNERS.insert(storage, spender, owner)?;
+        }
         Ok(())
     }
+
+    /// False when the storage predates the indexes, so older allowances may be missing
+    pub fn indexes_complete(storage: &dyn Storage) -> StdResult<bool> {
+        Ok(AllowanceIndexesComplete::may_load(storage)?.map_or(false, |complete| complete.0))
+    }
+
+    pub fn total_spenders(storage: &dyn Storage, owner: &Addr) -> StdResult<u32> {
+        ALLOWANCE_SPENDERS.len(storage, owner)
+    }
+
+    /// Spenders the owner has given an allowance to
+    pub fn spenders(
+        storage: &dyn Storage,
+        owner: &Addr,
+        start: u32,
+        limit: u32,
+    ) -> StdResult<Vec<Addr>> {
+        ALLOWANCE_SPENDERS.page(storage, owner, start, limit)
+    }
+
+    pub fn total_owners(storage: &dyn Storage, spender: &Addr) -> StdResult<u32> {
+        ALLOWANCE_OWNERS.len(storage, spender)
+    }
+
+    /// Owners that have given the spender an allowance
+    pub fn owners(
+        storage: &dyn Storage,
+        spender: &Addr,
+        start: u32,
+        limit: u32,
+    ) -> StdResult<Vec<Addr>> {
+        ALLOWANCE_OWNERS.page(storage, spender, start, limit)
+    }
 }
 // (Owner, Spender)
 #[cfg(feature = "snip20-impl")]
@@ -333,6 +702,99 @@ impl MapStorage<'static, (Addr, Addr)> for Allowance {
     const MAP: Map<'static, (Addr, Addr), Self> = Map::new("allowance-");
 }
 
+/// Set on instantiation, contracts that started out with the allowance indexes
+/// list every allowance
+#[cw_serde]
+pub struct AllowanceIndexesComplete(pub bool);
+
+#[cfg(feature = "snip20-impl")]
+impl ItemStorage for AllowanceIndexesComplete {
+    const ITEM: Item<'static, Self> = Item::new("allowance-indexes-complete-");
+}
+
+/// Addresses listed under a key by position so they can be paged without loading
+/// the whole list, removing an address moves the last one into its place
+#[cfg(feature = "snip20-impl")]
+struct AddrIndex {
+    items: Map<'static, (Addr, u32), Addr>,
+    size: Map<'static, Addr, u32>,
+    position: Map<'static, (Addr, Addr), u32>,
+}
+
+#[cfg(feature = "snip20-impl")]
+impl AddrIndex {
+    const fn new(items: &'static str, size: &'static str, position: &'static str) -> Self {
+        Self {
+            items: Map::new(items),
+            size: Map::new(size),
+            position: Map::new(position),
+        }
+    }
+
+    fn len(&self, storage: &dyn Storage, key: &Addr) -> StdResult<u32> {
+        Ok(self.size.may_load(storage, key.clone())?.unwrap_or_default())
+    }
+
+    fn contains(&self, storage: &dyn Storage, key: &Addr, addr: &Addr) -> StdResult<bool> {
+        Ok(self
+            .position
+            .may_load(storage, (key.clone(), addr.clone()))?
+            .is_some())
+    }
+
+    fn page(
+        &self,
+        storage: &dyn Storage,
+        key: &Addr,
+        start: u32,
+        limit: u32,
+    ) -> StdResult<Vec<Addr>> {
+        let end = start.saturating_add(limit).min(self.len(storage, key)?);
+        (start..end)
+            .map(|i| self.items.load(storage, (key.clone(), i)))
+            .collect()
+    }
+
+    fn insert(&self, storage: &mut dyn Storage, key: &Addr, addr: &Addr) -> StdResult<()> {
+        let len = self.len(storage, key)?;
+        self.items.save(storage, (key.clone(), len), addr)?;
+        self.position.save(storage, (key.clone(), addr.clone()), &len)?;
+        self.size.save(storage, key.clone(), &(len + 1))
+    }
+
+    fn remove(&self, storage: &mut dyn Storage, key: &Addr, addr: &Addr) -> StdResult<()> {
+        let position = match self.position.may_load(storage, (key.clone(), addr.clone()))? {
+            None => return Ok(()),
+            Some(position) => position,
+        };
+        let last = self.len(storage, key)? - 1;
+        if position != last {
+            let moved = self.items.load(storage, (key.clone(), last))?;
+            self.items.save(storage, (key.clone(), position), &moved)?;
+            self.position.save(storage, (key.clone(), moved), &position)?;
+        }
+        self.items.remove(storage, (key.clone(), last));
+        self.position.remove(storage, (key.clone(), addr.clone()));
+        self.size.save(storage, key.clone(), &last)
+    }
+}
+
+/// Spenders an owner has given an allowance to
+#[cfg(feature = "snip20-impl")]
+const ALLOWANCE_SPENDERS: AddrIndex = AddrIndex::new(
+    "allowance-spenders-",
+    "allowance-spenders-size-",
+    "allowance-spenders-position-",
+);
+
+/// Owners that have given an allowance to a spender
+#[cfg(feature = "snip20-impl")]
+const ALLOWANCE_OWNERS: AddrIndex = AddrIndex::new(
+    "allowance-owners-",
+    "allowance-owners-size-",
+    "allowance-owners-position-",
+);
+
 #[cw_serde]
 pub struct ReceiverHash(pub String);
 
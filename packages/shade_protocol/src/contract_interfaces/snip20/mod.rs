@@ -2,6 +2,7 @@ pub mod batch;
 pub mod errors;
 pub mod helpers;
 pub mod manager;
+pub mod notification;
 pub mod transaction_history;
 
 use crate::{
@@ -22,15 +23,24 @@ use crate::{
             errors::{invalid_decimals, invalid_name_format, invalid_symbol_format},
             manager::{
                 Admin,
+                AllowanceGivenResult,
+                AllowanceIndexesComplete,
+                AllowanceReceivedResult,
                 Balance,
                 CoinInfo,
                 Config,
                 ContractStatusLevel,
+                MaxSupply,
+                MintLimit,
+                MinterAllowanceResult,
                 Minters,
                 RandSeed,
                 TotalSupply,
+                VestingGrant,
+                VestingSchedule,
             },
-            transaction_history::{RichTx, Tx},
+            notification::ChannelInfoData,
+            transaction_history::{HistoryRetention, RichTx, Tx},
         },
     },
     snip20::manager::QueryAuth,
@@ -139,10 +149,12 @@ impl InstantiateMsg {
         }
 
         TotalSupply::set(storage, total_supply)?;
+        MaxSupply::check(storage, total_supply)?;
 
         ContractStatusLevel::NormalRun.save(storage)?;
 
         Minters(vec![]).save(storage)?;
+        AllowanceIndexesComplete(true).save(storage)?;
 
         if let Some(query_auth) = self.query_auth.clone() {
             QueryAuth(query_auth).save(storage)?;
@@ -172,6 +184,9 @@ pub struct InitConfig {
     /// Indicates whether transferring tokens should be enables
     /// default: True
     pub enable_transfer: Option<bool>,
+    /// Total supply can never be minted or deposited past this
+    /// default: Unlimited
+    pub max_supply: Option<Uint128>,
 }
 
 impl Default for InitConfig {
@@ -183,6 +198,7 @@ impl Default for InitConfig {
             enable_mint: None,
             enable_burn: None,
             enable_transfer: None,
+            max_supply: None,
         }
     }
 }
@@ -199,6 +215,9 @@ impl InitConfig {
             enable_transfer: self.transfer_enabled(),
         }
         .save(storage)?;
+        if let Some(max_supply) = self.max_supply {
+            MaxSupply(max_supply).save(storage)?;
+        }
         Ok(())
     }
 
@@ -244,10 +263,14 @@ pub enum ExecuteMsg {
     },
 
     // Base ERC-20 stuff
+    // Decoy balances are rewritten unchanged so observers can't tell which account changed,
+    // entropy seeds where the real account is written among them
     Transfer {
         recipient: String,
         amount: Uint128,
         memo: Option<String>,
+        decoys: Option<Vec<String>>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     Send {
@@ -256,19 +279,38 @@ pub enum ExecuteMsg {
         amount: Uint128,
         msg: Option<Binary>,
         memo: Option<String>,
+        decoys: Option<Vec<String>>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     BatchTransfer {
         actions: Vec<batch::TransferAction>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     BatchSend {
         actions: Vec<batch::SendAction>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     Burn {
         amount: Uint128,
         memo: Option<String>,
+        decoys: Option<Vec<String>>,
+        entropy: Option<Binary>,
+        padding: Option<String>,
+    },
+    // Transfers tokens that the recipient can only spend once they vest
+    TransferVesting {
+        recipient: String,
+        amount: Uint128,
+        schedule: VestingSchedule,
+        memo: Option<String>,
+        padding: Option<String>,
+    },
+    // Drops one of the sender's vesting grants, whatever is still locked goes back to the grantor
+    RejectVestingGrant {
+        index: u32,
         padding: Option<String>,
     },
     RegisterReceive {
@@ -337,10 +379,13 @@ pub enum ExecuteMsg {
         recipient: String,
         amount: Uint128,
         memo: Option<String>,
+        decoys: Option<Vec<String>>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     BatchMint {
         actions: Vec<batch::MintAction>,
+        entropy: Option<Binary>,
         padding: Option<String>,
     },
     AddMinters {
@@ -355,6 +400,12 @@ pub enum ExecuteMsg {
         minters: Vec<String>,
         padding: Option<String>,
     },
+    // Caps how much a minter can mint per cycle, None lifts the cap
+    SetMinterAllowance {
+        minter: String,
+        limit: Option<MintLimit>,
+        padding: Option<String>,
+    },
 
     // Admin
     ChangeAdmin {
@@ -369,6 +420,17 @@ pub enum ExecuteMsg {
     UpdateQueryAuth {
         auth: Option<Contract>,
     },
+    SetHistoryRetention {
+        retention: HistoryRetention,
+        padding: Option<String>,
+    },
+    // Applies the retention policy to the given accounts
+    PruneHistory {
+        accounts: Vec<String>,
+        // Most entries removed per account
+        limit: Option<u32>,
+        padding: Option<String>,
+    },
 
     // Permit
     RevokePermit {
@@ -444,6 +506,12 @@ pub enum ExecuteAnswer {
     Burn {
         status: ResponseStatus,
     },
+    TransferVesting {
+        status: ResponseStatus,
+    },
+    RejectVestingGrant {
+        status: ResponseStatus,
+    },
     RegisterReceive {
         status: ResponseStatus,
     },
@@ -500,6 +568,9 @@ pub enum ExecuteAnswer {
     SetMinters {
         status: ResponseStatus,
     },
+    SetMinterAllowance {
+        status: ResponseStatus,
+    },
 
     // Other
     ChangeAdmin {
@@ -511,6 +582,13 @@ pub enum ExecuteAnswer {
     UpdateQueryAuth {
         status: ResponseStatus,
     },
+    SetHistoryRetention {
+        status: ResponseStatus,
+    },
+    PruneHistory {
+        status: ResponseStatus,
+        pruned: u64,
+    },
 
     // Permit
     RevokePermit {
@@ -566,19 +644,45 @@ pub enum QueryMsg {
         address: String,
         key: String,
     },
+    /// Pages are counted from the account's first entry, pruned entries are left out
+    /// of their page so later pages never shift
     TransferHistory {
         address: String,
         key: String,
         page: Option<u32>,
         page_size: u32,
     },
+    /// Pages are counted from the account's first entry, pruned entries are left out
+    /// of their page so later pages never shift
     TransactionHistory {
         address: String,
         key: String,
         page: Option<u32>,
         page_size: u32,
     },
+    /// Allowances approved before the allowance indexes were added are only listed
+    /// once they change, the answer is marked incomplete when that may be the case
+    AllowancesGiven {
+        owner: String,
+        key: String,
+        page: Option<u32>,
+        page_size: u32,
+    },
+    /// Allowances approved before the allowance indexes were added are only listed
+    /// once they change, the answer is marked incomplete when that may be the case
+    AllowancesReceived {
+        spender: String,
+        key: String,
+        page: Option<u32>,
+        page_size: u32,
+    },
+    VestingSchedules {
+        address: String,
+        key: String,
+    },
     Minters {},
+    MinterAllowances {},
+    ListChannels {},
     WithPermit {
         permit: Option<QueryPermit>,
         // Extra parameter because of snip20s standards
@@ -594,9 +698,14 @@ impl Query for QueryMsg {
 #[cw_serde]
 pub enum QueryWithPermit {
     Allowance { owner: String, spender: String },
+    AllowancesGiven { owner: String, page: Option<u32>, page_size: u32 },
+    AllowancesReceived { spender: String, page: Option<u32>, page_size: u32 },
     Balance {},
     TransferHistory { page: Option<u32>, page_size: u32 },
     TransactionHistory { page: Option<u32>, page_size: u32 },
+    VestingSchedules {},
+    // Notification seeds for the permit signer
+    ChannelInfo { channels: Vec<String> },
 }
 
 #[cw_serde]
@@ -629,6 +738,18 @@ pub enum QueryAnswer {
         allowance: Uint128,
         expiration: Option<u64>,
     },
+    AllowancesGiven {
+        owner: Addr,
+        allowances: Vec<AllowanceGivenResult>,
+        count: u32,
+        incomplete: bool,
+    },
+    AllowancesReceived {
+        spender: Addr,
+        allowances: Vec<AllowanceReceivedResult>,
+        count: u32,
+        incomplete: bool,
+    },
     Balance {
         amount: Uint128,
     },
@@ -640,10 +761,26 @@ pub enum QueryAnswer {
         txs: Vec<RichTx>,
         total: Option<u64>,
     },
+    VestingSchedules {
+        grants: Vec<VestingGrant>,
+        // Part of the balance that cannot be spent yet
+        locked: Uint128,
+    },
     ViewingKeyError {
         msg: String,
     },
     Minters {
         minters: Vec<Addr>,
     },
+    MinterAllowances {
+        max_supply: Option<Uint128>,
+        allowances: Vec<MinterAllowanceResult>,
+    },
+    ListChannels {
+        channels: Vec<String>,
+    },
+    ChannelInfo {
+        as_of_block: u64,
+        channels: Vec<ChannelInfoData>,
+    },
 }
@@ -0,0 +1,158 @@
+//! SNIP-52 style private push notifications.
+//!
+//! Every account has a notification seed `hmac_sha256(rand_seed, "snip52:" + address)` which
+//! it can fetch with a `ChannelInfo` permit query. A notification for a channel is emitted as a
+//! plaintext attribute `snip52:{id}` where `id = hmac_sha256(seed, "{channel}:{height}:{tx}:{n}")`
+//! and `n` counts the recipient's notifications within the tx starting at 0, so a wallet can
+//! compute the ids it expects for any block and look for them without polling.
+//! The value is the JSON payload padded with spaces to `PAYLOAD_BLOCK_SIZE` and xored with the
+//! keystream of `Prng::new(seed, id)`.
+
+use crate::c_std::{Addr, Binary, Uint128};
+#[cfg(feature = "snip20-impl")]
+use crate::{
+    c_std::{to_vec, Env, StdResult, Storage},
+    contract_interfaces::snip20::manager::RandSeed,
+    utils::{
+        crypto::{hmac_sha256, Prng, SHA256_HASH_SIZE},
+        space_pad,
+        storage::plus::{ItemStorage, MapStorage},
+    },
+};
+use cosmwasm_schema::cw_serde;
+#[cfg(feature = "snip20-impl")]
+use secret_storage_plus::Map;
+
+/// Notifies the recipient of transfers, sends and mints
+pub const RECEIVED_CHANNEL: &str = "recvd";
+/// Ids are derived from the block height, the tx index and the notification count
+pub const NOTIFICATION_MODE: &str = "block";
+pub const PAYLOAD_BLOCK_SIZE: usize = 128;
+
+#[cw_serde]
+pub struct ChannelInfoData {
+    pub channel: String,
+    pub seed: Binary,
+    pub mode: String,
+}
+
+#[cw_serde]
+pub struct Received {
+    pub sender: Addr,
+    pub amount: Uint128,
+}
+
+#[cfg(feature = "snip20-impl")]
+pub fn notification_seed(
+    storage: &dyn Storage,
+    account: &Addr,
+) -> StdResult<[u8; SHA256_HASH_SIZE]> {
+    let secret = RandSeed::load(storage)?.0;
+    let mut info = b"snip52:".to_vec();
+    info.extend_from_slice(account.as_bytes());
+    Ok(hmac_sha256(&secret, &info))
+}
+
+#[cfg(feature = "snip20-impl")]
+pub fn notification_id(
+    seed: &[u8],
+    channel: &str,
+    env: &Env,
+    count: u32,
+) -> [u8; SHA256_HASH_SIZE] {
+    let tx_index = env.transaction.as_ref().map_or(0, |tx| tx.index);
+    hmac_sha256(
+        seed,
+        format!("{}:{}:{}:{}", channel, env.block.height, tx_index, count).as_bytes(),
+    )
+}
+
+/// Notifications a recipient got within the last tx that notified them, keeps the ids
+/// unique when several messages in one tx notify the same recipient
+#[cw_serde]
+pub struct NotificationCount {
+    pub height: u64,
+    pub tx_index: u32,
+    pub count: u32,
+}
+
+#[cfg(feature = "snip20-impl")]
+impl MapStorage<'static, Addr> for NotificationCount {
+    const MAP: Map<'static, Addr, Self> = Map::new("notification-count-");
+}
+
+#[cfg(feature = "snip20-impl")]
+impl NotificationCount {
+    /// Returns how many notifications the recipient already got in this tx and counts the next one
+    pub fn next(storage: &mut dyn Storage, recipient: &Addr, env: &Env) -> StdResult<u32> {
+        let height = env.block.height;
+        let tx_index = env.transaction.as_ref().map_or(0, |tx| tx.index);
+        let count = match Self::may_load(storage, recipient.clone())? {
+            Some(last) if last.height == height && last.tx_index == tx_index => last.count,
+            _ => 0,
+        };
+        Self {
+            height,
+            tx_index,
+            count: count + 1,
+        }
+        .save(storage, recipient.clone())?;
+        Ok(count)
+    }
+}
+
+#[cfg(feature = "snip20-impl")]
+pub fn encrypt_notification(seed: &[u8], id: &[u8], mut payload: Vec<u8>) -> Vec<u8> {
+    space_pad(&mut payload, PAYLOAD_BLOCK_SIZE);
+
+    let mut rng = Prng::new(seed, id);
+    for chunk in payload.chunks_mut(SHA256_HASH_SIZE) {
+        let keystream = rng.rand_bytes();
+        for (byte, key) in chunk.iter_mut().zip(keystream.iter()) {
+            *byte ^= key;
+        }
+    }
+
+    payload
+}
+
+/// Collects what each recipient got in a tx so they get a single notification
+#[derive(Default)]
+pub struct Notifier {
+    received: Vec<(Addr, Vec<Received>)>,
+}
+
+#[cfg(feature = "snip20-impl")]
+impl Notifier {
+    pub fn received(&mut self, recipient: &Addr, sender: &Addr, amount: Uint128) {
+        let received = Received {
+            sender: sender.clone(),
+            amount,
+        };
+        match self.received.iter_mut().find(|(addr, _)| addr == recipient) {
+            Some((_, items)) => items.push(received),
+            None => self.received.push((recipient.clone(), vec![received])),
+        }
+    }
+
+    /// Encrypted notification attributes as (key, value)
+    pub fn attributes(
+        self,
+        storage: &mut dyn Storage,
+        env: &Env,
+    ) -> StdResult<Vec<(String, String)>> {
+        let mut attributes = vec![];
+        for (recipient, received) in self.received {
+            let seed = notification_seed(storage, &recipient)?;
+            let count = NotificationCount::next(storage, &recipient, env)?;
+            let id = notification_id(&seed, RECEIVED_CHANNEL, env, count);
+            let data = encrypt_notification(&seed, &id, to_vec(&received)?);
+
+            attributes.push((
+                format!("snip52:{}", Binary::from(&id[..]).to_base64()),
+                Binary::from(data).to_base64(),
+            ));
+        }
+        Ok(attributes)
+    }
+}
@@ -1,16 +1,18 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Timestamp;
+use std::convert::TryFrom;
 
 use crate::{
     c_std::{Addr, BlockInfo, Coin, StdError, StdResult, Storage, Uint128},
     contract_interfaces::snip20::errors::{
+        invalid_history_retention,
         legacy_cannot_convert_from_tx,
         tx_code_invalid_conversion,
     },
 };
 
 #[cfg(feature = "snip20-impl")]
-use crate::utils::storage::plus::{ItemStorage, MapStorage};
+use crate::utils::storage::plus::{Bincode2, ItemStorage, MapStorage};
 #[cfg(feature = "snip20-impl")]
 use secret_storage_plus::{Item, Map};
 
@@ -43,13 +45,15 @@ impl Tx {
         page_size: u32,
     ) -> StdResult<(Vec<Self>, u64)> {
         let id = UserTXTotal::load(storage, for_address.clone())?.0;
+        let first = UserTXStart::first(storage, for_address)?;
         let start_index = page as u64 * page_size as u64;
 
-        // Since we dont know where the legacy txs are then we iterate over everything
-        let mut total = 0u64;
+        // Since we dont know where the legacy txs are then we iterate over everything.
+        // Pruned transfers still count so pages don't shift once history is pruned
+        let mut total = UserTXPrunedTransfers::count(storage, for_address)?;
         let mut txs = vec![];
-        for i in 0..id {
-            match StoredRichTx::load(storage, (for_address.clone(), i))?.into_legacy() {
+        for i in first..id {
+            match load_tx(storage, for_address, i)?.into_legacy() {
                 Ok(tx) => {
                     total += 1;
                     if total >= (start_index + page_size as u64) {
@@ -110,18 +114,20 @@ impl RichTx {
         page_size: u32,
     ) -> StdResult<(Vec<Self>, u64)> {
         let id = UserTXTotal::load(storage, for_address.clone())?.0;
-        let start_index = page as u64 * page_size as u64;
+        let page_start = page as u64 * page_size as u64;
         let size: u64;
-        if (start_index + page_size as u64) > id {
+        if (page_start + page_size as u64) > id {
             size = id;
         } else {
-            size = page_size as u64 + start_index;
+            size = page_size as u64 + page_start;
         }
+        // Pages are counted from the account's first entry so pruning never shifts them,
+        // pruned entries are left out of their page
+        let start_index = page_start.max(UserTXStart::first(storage, for_address)?);
 
         let mut txs = vec![];
         for index in start_index..size {
-            let stored_tx = StoredRichTx::load(storage, (for_address.clone(), index))?;
-            txs.push(stored_tx.into_humanized()?);
+            txs.push(load_tx(storage, for_address, index)?.into_humanized()?);
         }
 
         let length = txs.len() as u64;
@@ -129,6 +135,33 @@ impl RichTx {
     }
 }
 
+/// How much history is kept per account, entries outside of it get pruned oldest first
+#[cw_serde]
+#[derive(Default)]
+pub struct HistoryRetention {
+    /// Newest entries kept per account
+    pub max_entries: Option<u32>,
+    /// Seconds an entry is kept for
+    pub max_age: Option<u64>,
+}
+
+impl HistoryRetention {
+    pub fn validate(&self) -> StdResult<()> {
+        if self.max_entries == Some(0) {
+            return Err(invalid_history_retention("max entries must be above zero"));
+        }
+        if self.max_age == Some(0) {
+            return Err(invalid_history_retention("max age must be above zero"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "snip20-impl")]
+impl ItemStorage for HistoryRetention {
+    const ITEM: Item<'static, Self> = Item::new("history-retention-");
+}
+
 // Stored types:
 #[derive(Clone, Copy, Debug)]
 #[repr(u8)]
@@ -317,6 +350,173 @@ impl MapStorage<'static, (Addr, u64)> for StoredRichTx {
     const MAP: Map<'static, (Addr, u64), Self> = Map::new("stored-rich-tx-");
 }
 
+// Compact layout, numbers are LEB128 varints and strings are varint length prefixed:
+// header | id | block height | block time (nanos) | amount | denom | addresses.. | memo
+// where the header holds the tx code in its lower 3 bits followed by
+// one presence bit for each address and the memo.
+const TX_CODE_MASK: u8 = 0b0000_0111;
+const ADDRESS_FLAGS: [u8; 3] = [1 << 3, 1 << 4, 1 << 5];
+const MEMO_FLAG: u8 = 1 << 6;
+
+#[cw_serde]
+struct CompactTx(Vec<u8>);
+
+#[cfg(feature = "snip20-impl")]
+impl MapStorage<'static, (Addr, u64), Bincode2> for CompactTx {
+    const MAP: Map<'static, (Addr, u64), Self, Bincode2> = Map::new("compact-rich-tx-");
+}
+
+fn corrupt_tx_err() -> StdError {
+    StdError::generic_err("Stored transaction could not be decoded. Storage is corrupt")
+}
+
+fn write_varint(data: &mut Vec<u8>, mut n: u128) {
+    while n >= 0x80 {
+        data.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    data.push(n as u8);
+}
+
+fn write_bytes(data: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(data, bytes.len() as u128);
+    data.extend_from_slice(bytes);
+}
+
+struct CompactReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CompactReader<'a> {
+    fn byte(&mut self) -> StdResult<u8> {
+        let byte = *self.data.get(self.pos).ok_or_else(corrupt_tx_err)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn varint(&mut self) -> StdResult<u128> {
+        let mut n = 0u128;
+        for shift in (0..128).step_by(7) {
+            let byte = self.byte()?;
+            n |= ((byte & 0x7f) as u128) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err(corrupt_tx_err())
+    }
+
+    fn varint_u64(&mut self) -> StdResult<u64> {
+        u64::try_from(self.varint()?).map_err(|_| corrupt_tx_err())
+    }
+
+    fn string(&mut self) -> StdResult<String> {
+        let len = usize::try_from(self.varint()?).map_err(|_| corrupt_tx_err())?;
+        let end = self.pos.checked_add(len).ok_or_else(corrupt_tx_err)?;
+        let bytes = self.data.get(self.pos..end).ok_or_else(corrupt_tx_err)?;
+        self.pos = end;
+        String::from_utf8(bytes.to_vec()).map_err(|_| corrupt_tx_err())
+    }
+
+    fn optional_string(&mut self, present: bool) -> StdResult<Option<String>> {
+        if present {
+            Ok(Some(self.string()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl StoredRichTx {
+    fn compact(&self) -> CompactTx {
+        let addresses = [
+            &self.action.address1,
+            &self.action.address2,
+            &self.action.address3,
+        ];
+
+        let mut header = self.action.tx_type & TX_CODE_MASK;
+        for (flag, address) in ADDRESS_FLAGS.iter().zip(addresses.iter()) {
+            if address.is_some() {
+                header |= flag;
+            }
+        }
+        if self.memo.is_some() {
+            header |= MEMO_FLAG;
+        }
+
+        let mut data = vec![header];
+        write_varint(&mut data, self.id as u128);
+        write_varint(&mut data, self.block_height as u128);
+        write_varint(&mut data, self.block_time.nanos() as u128);
+        write_varint(&mut data, self.coins.amount.u128());
+        write_bytes(&mut data, self.coins.denom.as_bytes());
+        for address in addresses.iter().filter_map(|address| address.as_ref()) {
+            write_bytes(&mut data, address.as_bytes());
+        }
+        if let Some(memo) = &self.memo {
+            write_bytes(&mut data, memo.as_bytes());
+        }
+
+        CompactTx(data)
+    }
+}
+
+impl CompactTx {
+    fn decode(&self) -> StdResult<StoredRichTx> {
+        let mut reader = CompactReader {
+            data: &self.0,
+            pos: 0,
+        };
+
+        let header = reader.byte()?;
+        let id = reader.varint_u64()?;
+        let block_height = reader.varint_u64()?;
+        let block_time = Timestamp::from_nanos(reader.varint_u64()?);
+        let amount = Uint128::new(reader.varint()?);
+        let denom = reader.string()?;
+
+        let mut addresses = vec![];
+        for flag in ADDRESS_FLAGS {
+            let address = reader.optional_string(header & flag != 0)?;
+            addresses.push(address.map(Addr::unchecked));
+        }
+        let memo = reader.optional_string(header & MEMO_FLAG != 0)?;
+
+        if reader.pos != self.0.len() {
+            return Err(corrupt_tx_err());
+        }
+
+        let address3 = addresses.pop().flatten();
+        let address2 = addresses.pop().flatten();
+        let address1 = addresses.pop().flatten();
+
+        Ok(StoredRichTx {
+            id,
+            action: StoredTxAction {
+                tx_type: header & TX_CODE_MASK,
+                address1,
+                address2,
+                address3,
+            },
+            coins: Coin { denom, amount },
+            memo,
+            block_time,
+            block_height,
+        })
+    }
+}
+
+// Entries written before the compact encoding are still kept as json
+#[cfg(feature = "snip20-impl")]
+fn load_tx(storage: &dyn Storage, for_address: &Addr, index: u64) -> StdResult<StoredRichTx> {
+    match CompactTx::may_load(storage, (for_address.clone(), index))? {
+        Some(tx) => tx.decode(),
+        None => StoredRichTx::load(storage, (for_address.clone(), index)),
+    }
+}
+
 // Storage functions:
 #[cw_serde]
 struct TXCount(pub u64);
@@ -342,7 +542,8 @@ impl UserTXTotal {
     pub fn append(
         storage: &mut dyn Storage,
         for_address: &Addr,
-        tx: &StoredRichTx,
+        tx: &CompactTx,
+        block: &BlockInfo,
     ) -> StdResult<()> {
         let id = UserTXTotal::may_load(storage, for_address.clone())?
             .unwrap_or(UserTXTotal(0))
@@ -350,6 +551,8 @@ impl UserTXTotal {
         UserTXTotal(id + 1).save(storage, for_address.clone())?;
         tx.save(storage, (for_address.clone(), id))?;
 
+        prune_history(storage, for_address, block.time, APPEND_PRUNE_LIMIT)?;
+
         Ok(())
     }
 }
@@ -359,6 +562,99 @@ impl MapStorage<'static, Addr> for UserTXTotal {
     const MAP: Map<'static, Addr, Self> = Map::new("user-tx-total-");
 }
 
+// Index of the oldest entry that has not been pruned
+#[cw_serde]
+struct UserTXStart(pub u64);
+
+#[cfg(feature = "snip20-impl")]
+impl UserTXStart {
+    fn first(storage: &dyn Storage, for_address: &Addr) -> StdResult<u64> {
+        Ok(UserTXStart::may_load(storage, for_address.clone())?.map_or(0, |start| start.0))
+    }
+}
+
+#[cfg(feature = "snip20-impl")]
+impl MapStorage<'static, Addr> for UserTXStart {
+    const MAP: Map<'static, Addr, Self> = Map::new("user-tx-start-");
+}
+
+// Pruned entries that were listed in the legacy transfer history
+#[cw_serde]
+struct UserTXPrunedTransfers(pub u64);
+
+#[cfg(feature = "snip20-impl")]
+impl UserTXPrunedTransfers {
+    fn count(storage: &dyn Storage, for_address: &Addr) -> StdResult<u64> {
+        Ok(UserTXPrunedTransfers::may_load(storage, for_address.clone())?
+            .map_or(0, |count| count.0))
+    }
+}
+
+#[cfg(feature = "snip20-impl")]
+impl MapStorage<'static, Addr> for UserTXPrunedTransfers {
+    const MAP: Map<'static, Addr, Self> = Map::new("user-tx-pruned-transfers-");
+}
+
+// Entries pruned on every append, so a tightened policy catches up without spiking gas
+#[cfg(feature = "snip20-impl")]
+const APPEND_PRUNE_LIMIT: u64 = 2;
+
+/// Removes up to `limit` of the oldest entries of an account which fall outside
+/// the retention policy, returns how many were removed
+#[cfg(feature = "snip20-impl")]
+pub fn prune_history(
+    storage: &mut dyn Storage,
+    for_address: &Addr,
+    now: Timestamp,
+    limit: u64,
+) -> StdResult<u64> {
+    let retention = match HistoryRetention::may_load(storage)? {
+        Some(retention) => retention,
+        None => return Ok(0),
+    };
+    let total = match UserTXTotal::may_load(storage, for_address.clone())? {
+        Some(total) => total.0,
+        None => return Ok(0),
+    };
+
+    let mut start = UserTXStart::first(storage, for_address)?;
+    let mut pruned = 0u64;
+    let mut pruned_transfers = 0u64;
+    while start < total && pruned < limit {
+        let tx = load_tx(storage, for_address, start)?;
+        let over_limit = retention
+            .max_entries
+            .map_or(false, |max_entries| total - start > max_entries as u64);
+        let expired = match retention.max_age {
+            Some(max_age) if !over_limit => {
+                tx.block_time.seconds() < now.seconds().saturating_sub(max_age)
+            }
+            _ => false,
+        };
+        if !over_limit && !expired {
+            break;
+        }
+
+        if tx.into_legacy().is_ok() {
+            pruned_transfers += 1;
+        }
+        CompactTx::remove(storage, (for_address.clone(), start));
+        StoredRichTx::remove(storage, (for_address.clone(), start));
+        start += 1;
+        pruned += 1;
+    }
+
+    if pruned > 0 {
+        UserTXStart(start).save(storage, for_address.clone())?;
+    }
+    if pruned_transfers > 0 {
+        let count = UserTXPrunedTransfers::count(storage, for_address)? + pruned_transfers;
+        UserTXPrunedTransfers(count).save(storage, for_address.clone())?;
+    }
+
+    Ok(pruned)
+}
+
 #[cfg(feature = "snip20-impl")]
 #[allow(clippy::too_many_arguments)] // We just need them
 pub fn store_transfer(
@@ -382,21 +678,22 @@ pub fn store_transfer(
         coins,
         memo,
         block,
-    );
+    )
+    .compact();
 
     // Write to the owners history if it's different from the other two addresses
     if owner != sender && owner != receiver {
         // crate::c_std::debug_print("saving transaction history for owner");
-        UserTXTotal::append(storage, owner, &tx)?;
+        UserTXTotal::append(storage, owner, &tx, block)?;
     }
     // Write to the sender's history if it's different from the receiver
     if sender != receiver {
         // crate::c_std::debug_print("saving transaction history for sender");
-        UserTXTotal::append(storage, sender, &tx)?;
+        UserTXTotal::append(storage, sender, &tx, block)?;
     }
     // Always write to the recipient's history
     // crate::c_std::debug_print("saving transaction history for receiver");
-    UserTXTotal::append(storage, receiver, &tx)?;
+    UserTXTotal::append(storage, receiver, &tx, block)?;
 
     Ok(())
 }
@@ -417,12 +714,12 @@ pub fn store_mint(
         amount: amount.into(),
     };
     let action = StoredTxAction::mint(minter.clone(), recipient.clone());
-    let tx = StoredRichTx::new(id, action, coins, memo, block);
+    let tx = StoredRichTx::new(id, action, coins, memo, block).compact();
 
     if minter != recipient {
-        UserTXTotal::append(storage, recipient, &tx)?;
+        UserTXTotal::append(storage, recipient, &tx, block)?;
     }
-    UserTXTotal::append(storage, minter, &tx)?;
+    UserTXTotal::append(storage, minter, &tx, block)?;
 
     Ok(())
 }
@@ -443,12 +740,12 @@ pub fn store_burn(
         amount: amount.into(),
     };
     let action = StoredTxAction::burn(owner.clone(), burner.clone());
-    let tx = StoredRichTx::new(id, action, coins, memo, block);
+    let tx = StoredRichTx::new(id, action, coins, memo, block).compact();
 
     if burner != owner {
-        UserTXTotal::append(storage, owner, &tx)?;
+        UserTXTotal::append(storage, owner, &tx, block)?;
     }
-    UserTXTotal::append(storage, burner, &tx)?;
+    UserTXTotal::append(storage, burner, &tx, block)?;
 
     Ok(())
 }
@@ -467,9 +764,9 @@ pub fn store_deposit(
         amount: amount.into(),
     };
     let action = StoredTxAction::deposit();
-    let tx = StoredRichTx::new(id, action, coins, None, block);
+    let tx = StoredRichTx::new(id, action, coins, None, block).compact();
 
-    UserTXTotal::append(storage, recipient, &tx)?;
+    UserTXTotal::append(storage, recipient, &tx, block)?;
 
     Ok(())
 }
@@ -488,9 +785,9 @@ pub fn store_redeem(
         amount: amount.into(),
     };
     let action = StoredTxAction::redeem();
-    let tx = StoredRichTx::new(id, action, coins, None, block);
+    let tx = StoredRichTx::new(id, action, coins, None, block).compact();
 
-    UserTXTotal::append(storage, redeemer, &tx)?;
+    UserTXTotal::append(storage, redeemer, &tx, block)?;
 
     Ok(())
 }
@@ -12,6 +12,28 @@ pub fn sha_256(data: &[u8]) -> [u8; SHA256_HASH_SIZE] {
     result
 }
 
+/// HMAC-SHA256 as defined in RFC 2104
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; SHA256_HASH_SIZE] {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..SHA256_HASH_SIZE].copy_from_slice(&sha_256(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Vec::with_capacity(BLOCK_SIZE + data.len());
+    inner.extend(block.iter().map(|byte| byte ^ 0x36));
+    inner.extend_from_slice(data);
+
+    let mut outer = Vec::with_capacity(BLOCK_SIZE + SHA256_HASH_SIZE);
+    outer.extend(block.iter().map(|byte| byte ^ 0x5c));
+    outer.extend_from_slice(&sha_256(&inner));
+
+    sha_256(&outer)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,4 +54,26 @@ mod tests {
         ];
         assert_eq!(r, r_expected);
     }
+
+    #[test]
+    fn test_hmac_sha256() {
+        // RFC 4231 test case 2
+        let r = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        let r_expected: [u8; SHA256_HASH_SIZE] = [
+            91, 220, 193, 70, 191, 96, 117, 78, 106, 4, 36, 38, 8, 149, 117, 199, 90, 0, 63, 8,
+            157, 39, 57, 131, 157, 236, 88, 185, 100, 236, 56, 67,
+        ];
+        assert_eq!(r, r_expected);
+
+        // RFC 4231 test case 6, key longer than the block size
+        let r = hmac_sha256(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+        );
+        let r_expected: [u8; SHA256_HASH_SIZE] = [
+            96, 228, 49, 89, 30, 224, 182, 127, 13, 138, 38, 170, 203, 245, 183, 127, 142, 11, 198,
+            33, 55, 40, 197, 20, 5, 70, 4, 15, 14, 227, 127, 84,
+        ];
+        assert_eq!(r, r_expected);
+    }
 }
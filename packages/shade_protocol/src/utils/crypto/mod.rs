@@ -1,5 +1,5 @@
 mod hash;
 mod rng;
 
-pub use hash::{sha_256, SHA256_HASH_SIZE};
+pub use hash::{hmac_sha256, sha_256, SHA256_HASH_SIZE};
 pub use rng::Prng;
@@ -1,13 +1,95 @@
-use crate::c_std::{StdError, StdResult};
+use crate::c_std::{to_vec, Api, StdError, StdResult};
 
 use cosmwasm_schema::{cw_serde};
 
+const PLACEHOLDER_START: &str = "{~";
+const PLACEHOLDER_END: &str = "}";
+// Limits nesting when validating rendered msgs
+const MAX_JSON_DEPTH: u8 = 64;
+
 #[cw_serde]
 pub struct FlexibleMsg {
     pub msg: String,
     pub arguments: u16,
 }
 
+/// Placeholder types supported by typed msgs, written as {~type}
+#[cw_serde]
+pub enum MsgArgument {
+    // {~} inserts the argument as is
+    Legacy,
+    // {~address}
+    Address,
+    // {~uint128}
+    Uint128,
+    // {~string}
+    String,
+    // {~bool}
+    Bool,
+    // {~object} or {~object:key,key} to limit the allowed keys
+    Object { keys: Option<Vec<String>> },
+}
+
+impl MsgArgument {
+    fn parse(placeholder: &str) -> StdResult<Self> {
+        Ok(match placeholder {
+            "" => Self::Legacy,
+            "address" => Self::Address,
+            "uint128" => Self::Uint128,
+            "string" => Self::String,
+            "bool" => Self::Bool,
+            "object" => Self::Object { keys: None },
+            _ => match placeholder.strip_prefix("object:") {
+                Some(keys) => Self::Object {
+                    keys: Some(keys.split(',').map(|key| key.trim().to_string()).collect()),
+                },
+                None => {
+                    return Err(StdError::generic_err(format!(
+                        "Unknown msg argument type {}",
+                        placeholder
+                    )));
+                }
+            },
+        })
+    }
+
+    /// Validates the argument and returns its JSON encoding
+    fn render(&self, api: &dyn Api, arg: &str) -> StdResult<String> {
+        Ok(match self {
+            Self::Legacy => arg.to_string(),
+            Self::Address => json_string(api.addr_validate(arg)?.as_str())?,
+            Self::Uint128 => {
+                if arg.is_empty() || !arg.bytes().all(|c| c.is_ascii_digit()) {
+                    return Err(StdError::generic_err(format!("{} is not a uint128", arg)));
+                }
+                let amount = arg
+                    .parse::<u128>()
+                    .map_err(|_| StdError::generic_err(format!("{} is not a uint128", arg)))?;
+                json_string(&amount.to_string())?
+            }
+            Self::String => json_string(arg)?,
+            Self::Bool => match arg {
+                "true" | "false" => arg.to_string(),
+                _ => return Err(StdError::generic_err(format!("{} is not a bool", arg))),
+            },
+            Self::Object { keys } => {
+                let object_keys = JsonParser::new(arg).object_keys()?;
+                if let Some(keys) = keys {
+                    for key in object_keys.iter() {
+                        if !keys.contains(key) {
+                            return Err(StdError::generic_err(format!(
+                                "Object key {} is not allowed",
+                                key
+                            )));
+                        }
+                    }
+                }
+                arg.trim().to_string()
+            }
+        })
+    }
+}
+
 impl FlexibleMsg {
     pub fn new(msg: String, msg_variable: &str) -> FlexibleMsg {
         FlexibleMsg {
@@ -16,6 +98,30 @@ impl FlexibleMsg {
         }
     }
 
+    /// Creates a msg with typed placeholders, fails if any placeholder is unknown
+    pub fn new_typed(msg: String) -> StdResult<FlexibleMsg> {
+        let arguments = Self::placeholders(&msg)?.0.len() as u16;
+        Ok(FlexibleMsg { msg, arguments })
+    }
+
+    /// Splits the msg into the text before each placeholder with its type
+    /// and the text left after the last placeholder
+    fn placeholders(msg: &str) -> StdResult<(Vec<(&str, MsgArgument)>, &str)> {
+        let mut placeholders = vec![];
+        let mut rest = msg;
+
+        while let Some(start) = rest.find(PLACEHOLDER_START) {
+            let after = &rest[start + PLACEHOLDER_START.len()..];
+            let end = after
+                .find(PLACEHOLDER_END)
+                .ok_or_else(|| StdError::generic_err("Unclosed msg argument"))?;
+            placeholders.push((&rest[..start], MsgArgument::parse(&after[..end])?));
+            rest = &after[end + PLACEHOLDER_END.len()..];
+        }
+
+        Ok((placeholders, rest))
+    }
+
     pub fn create_msg(&self, args: Vec<String>, msg_variable: &str) -> StdResult<String> {
         if args.len() as u16 != self.arguments {
             return Err(StdError::generic_err(format!(
@@ -31,4 +137,372 @@ impl FlexibleMsg {
         }
         Ok(msg)
     }
+
+    /// Validates and JSON encodes each argument before replacing its placeholder,
+    /// the resulting msg must be valid JSON
+    pub fn create_typed_msg(&self, api: &dyn Api, args: Vec<String>) -> StdResult<String> {
+        let (placeholders, rest) = Self::placeholders(&self.msg)?;
+        if args.len() != placeholders.len() {
+            return Err(StdError::generic_err(format!(
+                "Msg expected {:?} arguments; received {:?}",
+                placeholders.len(),
+                args.len()
+            )));
+        }
+
+        let mut msg = String::new();
+        for ((text, argument), arg) in placeholders.iter().zip(args.iter()) {
+            msg.push_str(text);
+            msg.push_str(&argument.render(api, arg)?);
+        }
+        msg.push_str(rest);
+
+        JsonParser::new(&msg).validate()?;
+
+        Ok(msg)
+    }
+}
+
+fn json_string(value: &str) -> StdResult<String> {
+    String::from_utf8(to_vec(&value)?).map_err(|_| StdError::generic_err("Invalid utf8"))
+}
+
+/// Minimal JSON validator, numbers are only checked for syntax to avoid floats
+struct JsonParser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(json: &'a str) -> Self {
+        Self {
+            bytes: json.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn error(&self) -> StdError {
+        StdError::generic_err(format!("Invalid JSON at position {}", self.pos))
+    }
+
+    fn validate(&mut self) -> StdResult<()> {
+        self.value(0)?;
+        self.whitespace();
+        if self.pos != self.bytes.len() {
+            return Err(self.error());
+        }
+        Ok(())
+    }
+
+    /// Validates that the JSON is an object and returns its top level keys
+    fn object_keys(&mut self) -> StdResult<Vec<String>> {
+        let mut keys = vec![];
+        self.whitespace();
+        self.object(0, Some(&mut keys))?;
+        self.whitespace();
+        if self.pos != self.bytes.len() {
+            return Err(self.error());
+        }
+        Ok(keys)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> StdResult<()> {
+        if self.peek() != Some(c) {
+            return Err(self.error());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(b' ' | b'\n' | b'\r' | b'\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn value(&mut self, depth: u8) -> StdResult<()> {
+        if depth > MAX_JSON_DEPTH {
+            return Err(self.error());
+        }
+
+        self.whitespace();
+        match self.peek() {
+            Some(b'{') => self.object(depth, None),
+            Some(b'[') => self.array(depth),
+            Some(b'"') => self.string().map(|_| ()),
+            Some(b't') => self.literal("true"),
+            Some(b'f') => self.literal("false"),
+            Some(b'n') => self.literal("null"),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn object(&mut self, depth: u8, mut keys: Option<&mut Vec<String>>) -> StdResult<()> {
+        self.expect(b'{')?;
+        self.whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(());
+        }
+
+        let mut seen = vec![];
+        loop {
+            self.whitespace();
+            let key = self.string()?;
+            if seen.contains(&key) {
+                return Err(StdError::generic_err(format!("Duplicate object key {}", key)));
+            }
+            if let Some(keys) = keys.as_mut() {
+                // Keys are compared as written, escapes could hide a disallowed key
+                if key.contains('\\') {
+                    return Err(StdError::generic_err(format!(
+                        "Object key {} cannot contain escapes",
+                        key
+                    )));
+                }
+                keys.push(key.clone());
+            }
+            seen.push(key);
+            self.whitespace();
+            self.expect(b':')?;
+            self.value(depth + 1)?;
+            self.whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    fn array(&mut self, depth: u8) -> StdResult<()> {
+        self.expect(b'[')?;
+        self.whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(());
+        }
+
+        loop {
+            self.value(depth + 1)?;
+            self.whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    /// Returns the raw string contents without decoding escapes
+    fn string(&mut self) -> StdResult<String> {
+        self.expect(b'"')?;
+        let start = self.pos;
+
+        loop {
+            match self.peek() {
+                None => return Err(self.error()),
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(b'"' | b'\\' | b'/' | b'b' | b'f' | b'n' | b'r' | b't') => {
+                            self.pos += 1
+                        }
+                        Some(b'u') => {
+                            self.pos += 1;
+                            for _ in 0..4 {
+                                match self.peek() {
+                                    Some(c) if c.is_ascii_hexdigit() => self.pos += 1,
+                                    _ => return Err(self.error()),
+                                }
+                            }
+                        }
+                        _ => return Err(self.error()),
+                    }
+                }
+                Some(c) if c < 0x20 => return Err(self.error()),
+                Some(_) => self.pos += 1,
+            }
+        }
+
+        let raw =
+            String::from_utf8(self.bytes[start..self.pos].to_vec()).map_err(|_| self.error())?;
+        self.pos += 1;
+        Ok(raw)
+    }
+
+    fn literal(&mut self, literal: &str) -> StdResult<()> {
+        if self.bytes[self.pos..].starts_with(literal.as_bytes()) {
+            self.pos += literal.len();
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    fn digits(&mut self) -> StdResult<()> {
+        let start = self.pos;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(self.error());
+        }
+        Ok(())
+    }
+
+    fn number(&mut self) -> StdResult<()> {
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        if self.peek() == Some(b'0') {
+            self.pos += 1;
+        } else {
+            self.digits()?;
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            self.digits()?;
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.pos += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.pos += 1;
+            }
+            self.digits()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::c_std::testing::MockApi;
+
+    fn typed_msg(msg: &str, args: Vec<&str>) -> StdResult<String> {
+        FlexibleMsg::new_typed(msg.to_string())?.create_typed_msg(
+            &MockApi::default(),
+            args.iter().map(|a| a.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn counts_typed_arguments() {
+        let msg = FlexibleMsg::new_typed(
+            "{\"a\":{~address},\"b\":{~uint128},\"c\":{~object:x,y},\"d\":{~}}".to_string(),
+        )
+        .unwrap();
+        assert_eq!(msg.arguments, 4);
+    }
+
+    #[test]
+    fn rejects_unknown_arguments() {
+        assert!(FlexibleMsg::new_typed("{\"a\":{~float}}".to_string()).is_err());
+        assert!(FlexibleMsg::new_typed("{\"a\":{~address".to_string()).is_err());
+    }
+
+    #[test]
+    fn legacy_argument() {
+        assert_eq!(
+            typed_msg("{~}", vec!["{\"send\":{}}"]).unwrap(),
+            "{\"send\":{}}".to_string()
+        );
+        assert!(typed_msg("{~}", vec!["{\"send\":"]).is_err());
+    }
+
+    #[test]
+    fn typed_arguments() {
+        assert_eq!(
+            typed_msg(
+                concat!(
+                    "{\"send\":{\"recipient\":{~address},\"amount\":{~uint128},",
+                    "\"memo\":{~string},\"padding\":{~bool}}}"
+                ),
+                vec!["recipient", "100", "a \"memo\"", "true"]
+            )
+            .unwrap(),
+            concat!(
+                "{\"send\":{\"recipient\":\"recipient\",\"amount\":\"100\",",
+                "\"memo\":\"a \\\"memo\\\"\",\"padding\":true}}"
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_typed_arguments() {
+        assert!(typed_msg("{\"a\":{~uint128}}", vec!["-1"]).is_err());
+        assert!(typed_msg("{\"a\":{~uint128}}", vec!["1e3"]).is_err());
+        assert!(typed_msg(
+            "{\"a\":{~uint128}}",
+            vec!["340282366920938463463374607431768211456"]
+        )
+        .is_err());
+        assert!(typed_msg("{\"a\":{~bool}}", vec!["yes"]).is_err());
+        assert!(typed_msg("{\"a\":{~address}}", vec!["Not An Address"]).is_err());
+        assert!(typed_msg("{\"a\":{~uint128}}", vec![]).is_err());
+    }
+
+    #[test]
+    fn string_injection() {
+        // Arguments are escaped so they cannot add keys
+        let msg = typed_msg("{\"a\":{~string}}", vec!["\",\"admin\":\"attacker"]).unwrap();
+        assert_eq!(
+            msg,
+            "{\"a\":\"\\\",\\\"admin\\\":\\\"attacker\"}".to_string()
+        );
+    }
+
+    #[test]
+    fn object_arguments() {
+        assert_eq!(
+            typed_msg("{\"a\":{~object}}", vec!["{\"b\":[1,-2.5e3,null]}"]).unwrap(),
+            "{\"a\":{\"b\":[1,-2.5e3,null]}}".to_string()
+        );
+        assert!(typed_msg("{\"a\":{~object:b,c}}", vec!["{\"b\":1,\"c\":{\"d\":2}}"]).is_ok());
+        assert!(typed_msg("{\"a\":{~object:b,c}}", vec!["{\"b\":1,\"admin\":2}"]).is_err());
+        assert!(typed_msg("{\"a\":{~object}}", vec!["[1]"]).is_err());
+        assert!(typed_msg("{\"a\":{~object}}", vec!["{\"b\":1},\"c\":{}"]).is_err());
+    }
+
+    #[test]
+    fn escaped_object_keys() {
+        // \u0061dmin decodes to admin
+        assert!(typed_msg("{\"a\":{~object:b}}", vec!["{\"\\u0061dmin\":1}"]).is_err());
+        assert!(typed_msg("{\"a\":{~object:b}}", vec!["{\"b\\n\":1}"]).is_err());
+        // Nested keys are not checked against the allowed keys
+        assert!(typed_msg("{\"a\":{~object:b}}", vec!["{\"b\":{\"\\u0061\":1}}"]).is_ok());
+    }
+
+    #[test]
+    fn duplicate_object_keys() {
+        assert!(typed_msg("{\"a\":{~object:b}}", vec!["{\"b\":1,\"b\":2}"]).is_err());
+        assert!(typed_msg("{\"a\":{~object}}", vec!["{\"b\":{\"c\":1,\"c\":2}}"]).is_err());
+        assert!(typed_msg("{\"a\":{~},\"a\":{~}}", vec!["1", "2"]).is_err());
+        assert!(JsonParser::new("{\"a\":{\"a\":1}}").validate().is_ok());
+    }
+
+    #[test]
+    fn json_validation() {
+        assert!(JsonParser::new(" {\"a\" : [true, false, \"\\u00e9\"] } ")
+            .validate()
+            .is_ok());
+        assert!(JsonParser::new("{\"a\":01}").validate().is_err());
+        assert!(JsonParser::new("{\"a\":1,}").validate().is_err());
+        assert!(JsonParser::new("{\"a\":\"\\x\"}").validate().is_err());
+        assert!(JsonParser::new(&"[".repeat(100)).validate().is_err());
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    c_std::{StdResult, Storage, Timestamp},
+    c_std::{StdError, StdResult, Storage, Timestamp, Uint128},
     cosmwasm_schema::cw_serde,
     serde::{de::DeserializeOwned, Serialize},
     utils::cycle::*,
@@ -27,6 +27,28 @@ pub fn map_key(seconds: u64, period: Period) -> String {
     }
 }
 
+/// Seconds within the period to report on, from either a date or an epoch, defaults to now
+pub fn period_key(now: u64, date: Option<String>, epoch: Option<Uint128>) -> StdResult<u64> {
+    if date.is_some() && epoch.is_some() {
+        return Err(StdError::generic_err("cannot pass both epoch and date"));
+    }
+    if let Some(d) = date {
+        Ok(parse_utc_datetime(&d)?.timestamp() as u64)
+    } else if let Some(e) = epoch {
+        Ok(e.u128() as u64)
+    } else {
+        Ok(now)
+    }
+}
+
+/// Period total of metric amounts sharing an action & context
+#[cw_serde]
+pub struct MetricTotal<A, C> {
+    pub action: A,
+    pub context: C,
+    pub amount: Uint128,
+}
+
 pub struct PeriodStorage<'a, T, Ser = Json>
 where
     T: Serialize + DeserializeOwned + Clone,
@@ -71,6 +93,36 @@ where
             .unwrap_or(vec![]))
     }
 
+    /// Totals the period's items by action & context,
+    /// `split` returns None for items that should be left out
+    pub fn period_totals<A: PartialEq, C: PartialEq>(
+        &self,
+        storage: &dyn Storage,
+        seconds: u64,
+        period: Period,
+        split: impl Fn(T) -> Option<(A, C, Uint128)>,
+    ) -> StdResult<Vec<MetricTotal<A, C>>> {
+        let mut totals: Vec<MetricTotal<A, C>> = vec![];
+        for (action, context, amount) in self
+            .load_period(storage, seconds, period)?
+            .into_iter()
+            .filter_map(split)
+        {
+            match totals
+                .iter()
+                .position(|t| t.action == action && t.context == context)
+            {
+                Some(i) => totals[i].amount += amount,
+                None => totals.push(MetricTotal {
+                    action,
+                    context,
+                    amount,
+                }),
+            }
+        }
+        Ok(totals)
+    }
+
     pub fn may_load(&self, storage: &dyn Storage, ts: Timestamp) -> StdResult<Vec<T>> {
         Ok(self.all.may_load(storage, ts.seconds())?.unwrap_or(vec![]))
     }
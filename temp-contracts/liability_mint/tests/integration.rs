@@ -60,6 +60,7 @@ fn test_liabilities(
             enable_mint: Some(true),
             enable_burn: Some(true),
             enable_transfer: Some(true),
+            max_supply: None,
         }),
         query_auth: None,
     }.test_init(Snip20::default(), &mut app, admin.clone(), "token", &[]).unwrap();
@@ -128,6 +129,8 @@ fn test_liabilities(
         amount: payback,
         msg: None,
         memo: None,
+        decoys: None,
+        entropy: None,
         padding: None,
     }.test_exec(&token, &mut app, admin.clone(), &[]).unwrap();
     